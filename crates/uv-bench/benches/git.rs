@@ -0,0 +1,19 @@
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main, measurement::WallTime};
+use uv_git_types::GitOid;
+
+/// Deserialize 10,000 object IDs from JSON, as borrowed strings.
+fn deserialize_git_oids(c: &mut Criterion<WallTime>) {
+    let oids = (0..10_000)
+        .map(|index| format!("{index:040x}"))
+        .collect::<Vec<_>>();
+    let json = serde_json::to_string(&oids).unwrap();
+
+    c.bench_function("deserialize_git_oids_10k", |b| {
+        b.iter(|| serde_json::from_str::<Vec<GitOid>>(black_box(&json)).unwrap());
+    });
+}
+
+criterion_group!(git, deserialize_git_oids);
+criterion_main!(git);
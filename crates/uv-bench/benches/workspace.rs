@@ -0,0 +1,247 @@
+use std::fmt::Write;
+use std::hint::black_box;
+use std::path::Path;
+use std::str::FromStr;
+
+use criterion::{
+    BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main, measurement::WallTime,
+};
+use uv_normalize::PackageName;
+use uv_pep508::Requirement;
+use uv_workspace::pyproject::{DependencyType, PyProjectToml};
+use uv_workspace::pyproject_mut::{DependencyTarget, PyProjectTomlMut};
+use uv_workspace::{DiscoveryOptions, Workspace, WorkspaceCache};
+
+/// Create a `pyproject.toml` with the given number of dependencies.
+fn pyproject(name: &str, dependencies: usize) -> String {
+    let mut contents = format!(
+        "[project]\nname = \"{name}\"\nversion = \"0.1.0\"\nrequires-python = \">=3.12\"\ndependencies = [\n"
+    );
+    for index in 0..dependencies {
+        let _ = writeln!(
+            contents,
+            "    \"package-{index}>={index}.0,<{}.0 ; python_version >= '3.12'\",",
+            index + 1
+        );
+    }
+    contents.push_str("]\n");
+    contents
+}
+
+/// The sizes of the `project.dependencies` arrays to edit.
+const SIZES: [usize; 3] = [10, 100, 500];
+
+/// Create a `pyproject.toml` with the given number of dependencies, sorted by name.
+fn sorted_pyproject(dependencies: usize) -> String {
+    let mut contents =
+        "[project]\nname = \"albatross\"\nversion = \"0.1.0\"\ndependencies = [\n".to_string();
+    for index in 0..dependencies {
+        let _ = writeln!(contents, "    \"package-{index:04}>={index}.0\",");
+    }
+    contents.push_str("]\n");
+    contents
+}
+
+/// Load, edit, and save `pyproject.toml` files with large `project.dependencies` arrays.
+fn edit_dependencies(c: &mut Criterion<WallTime>) {
+    let requirement = Requirement::from_str("zstandard>=0.23").unwrap();
+    let batch = (0..50)
+        .map(|index| Requirement::from_str(&format!("new-package-{index}>=1.0")).unwrap())
+        .collect::<Vec<_>>();
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    let mut group = c.benchmark_group("edit_dependencies");
+    for size in SIZES {
+        let contents = sorted_pyproject(size);
+        let load =
+            || PyProjectTomlMut::from_toml(&contents, DependencyTarget::PyProjectToml).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("load", size), &contents, |b, contents| {
+            b.iter(|| {
+                PyProjectTomlMut::from_toml(black_box(contents), DependencyTarget::PyProjectToml)
+                    .unwrap()
+            });
+        });
+
+        // Add a dependency at the end of the array.
+        group.bench_function(BenchmarkId::new("add", size), |b| {
+            b.iter_batched(
+                load,
+                |mut pyproject| {
+                    pyproject
+                        .add_dependency(black_box(&requirement), None, false)
+                        .unwrap();
+                    pyproject
+                },
+                BatchSize::SmallInput,
+            );
+        });
+
+        // Add a dependency in the middle of the array, retaining the sort order.
+        let middle = Requirement::from_str(&format!("package-{:04}a", size / 2)).unwrap();
+        group.bench_function(BenchmarkId::new("sort", size), |b| {
+            b.iter_batched(
+                load,
+                |mut pyproject| {
+                    pyproject
+                        .add_dependency(black_box(&middle), None, false)
+                        .unwrap();
+                    pyproject
+                },
+                BatchSize::SmallInput,
+            );
+        });
+
+        // Add 50 dependencies, one by one and as a batch.
+        group.bench_function(BenchmarkId::new("add_each_50", size), |b| {
+            b.iter_batched(
+                load,
+                |mut pyproject| {
+                    for requirement in &batch {
+                        pyproject
+                            .add_dependency(black_box(requirement), None, false)
+                            .unwrap();
+                    }
+                    pyproject
+                },
+                BatchSize::SmallInput,
+            );
+        });
+        group.bench_function(BenchmarkId::new("add_batch_50", size), |b| {
+            b.iter_batched(
+                load,
+                |mut pyproject| {
+                    pyproject
+                        .add_dependencies(
+                            &DependencyType::Production,
+                            black_box(&batch)
+                                .iter()
+                                .map(|requirement| (requirement, None)),
+                            false,
+                        )
+                        .unwrap();
+                    pyproject
+                },
+                BatchSize::SmallInput,
+            );
+        });
+
+        let path = temp_dir.path().join(format!("pyproject-{size}.toml"));
+        group.bench_function(BenchmarkId::new("save", size), |b| {
+            b.iter_batched(
+                || {
+                    let mut pyproject = load();
+                    pyproject.add_dependency(&requirement, None, false).unwrap();
+                    pyproject
+                },
+                |mut pyproject| {
+                    pyproject.save_to(black_box(&path)).unwrap();
+                    pyproject
+                },
+                BatchSize::SmallInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+/// Derive the typed [`PyProjectToml`] of a large, loaded document, by parsing its contents again
+/// or by deserializing the parsed document.
+fn pyproject_toml_large(c: &mut Criterion<WallTime>) {
+    let contents = pyproject("albatross", 500);
+    let document = PyProjectTomlMut::from_toml(&contents, DependencyTarget::PyProjectToml).unwrap();
+
+    c.bench_function("pyproject_toml_large_reparse", |b| {
+        b.iter(|| {
+            PyProjectToml::from_string(black_box(&document).to_string(), "pyproject.toml").unwrap()
+        });
+    });
+    c.bench_function("pyproject_toml_large_from_document", |b| {
+        b.iter(|| black_box(&document).pyproject_toml().unwrap());
+    });
+}
+
+/// Normalize the names of 500 dependencies, most of which are already normalized, as when
+/// matching existing entries against a new dependency.
+fn normalize_names(c: &mut Criterion<WallTime>) {
+    let names = (0..500)
+        .map(|index| {
+            if index % 10 == 0 {
+                format!("Package_{index}")
+            } else {
+                format!("package-{index}")
+            }
+        })
+        .collect::<Vec<_>>();
+    let name = PackageName::from_str("package-250").unwrap();
+
+    let mut group = c.benchmark_group("normalize_names");
+    group.bench_function("from_str_500", |b| {
+        b.iter(|| {
+            black_box(&names)
+                .iter()
+                .filter(|key| PackageName::from_str(key).is_ok_and(|key| key == name))
+                .count()
+        });
+    });
+    group.bench_function("normalize_500", |b| {
+        b.iter(|| {
+            black_box(&names)
+                .iter()
+                .filter(|key| PackageName::normalize(key).is_ok_and(|key| key == name.as_str()))
+                .count()
+        });
+    });
+    group.finish();
+}
+
+/// Discover workspaces with 50 and 100 members.
+fn discover_workspace(c: &mut Criterion<WallTime>) {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    for members in [50, 100] {
+        let root = tempfile::tempdir().unwrap();
+        write_workspace(root.path(), members);
+
+        c.bench_function(&format!("discover_workspace_{members}_members"), |b| {
+            b.iter(|| {
+                runtime
+                    .block_on(Workspace::discover(
+                        black_box(root.path()),
+                        &DiscoveryOptions::default(),
+                        &WorkspaceCache::default(),
+                    ))
+                    .unwrap()
+            });
+        });
+    }
+}
+
+/// Write a workspace with the given number of members to the given directory.
+fn write_workspace(root: &Path, members: usize) {
+    let mut contents = pyproject("albatross", 10);
+    contents.push_str("\n[tool.uv.workspace]\nmembers = [\"packages/*\"]\n");
+    fs_err::write(root.join("pyproject.toml"), contents).unwrap();
+
+    for index in 0..members {
+        let member = root.join("packages").join(format!("member-{index}"));
+        fs_err::create_dir_all(&member).unwrap();
+        fs_err::write(
+            member.join("pyproject.toml"),
+            pyproject(&format!("member-{index}"), 20),
+        )
+        .unwrap();
+    }
+}
+
+criterion_group!(
+    workspace,
+    edit_dependencies,
+    pyproject_toml_large,
+    normalize_names,
+    discover_workspace
+);
+criterion_main!(workspace);
@@ -357,6 +357,17 @@ impl Display for Requirement {
                         if subdirectory.is_some() { "&" } else { "#" }
                     )?;
                 }
+                if let Some(submodules) = git.submodules() {
+                    write!(
+                        f,
+                        "{}submodules={submodules}",
+                        if subdirectory.is_some() || git.lfs().enabled() {
+                            "&"
+                        } else {
+                            "#"
+                        }
+                    )?;
+                }
             }
             RequirementSource::Path { url, .. } => {
                 write!(f, " @ {url}")?;
@@ -446,6 +457,10 @@ impl CacheKey for Requirement {
                 if git.lfs().enabled() {
                     1u8.cache_key(state);
                 }
+                if let Some(submodules) = git.submodules() {
+                    2u8.cache_key(state);
+                    submodules.cache_key(state);
+                }
                 url.cache_key(state);
             }
             RequirementSource::Path {
@@ -780,6 +795,17 @@ impl Display for RequirementSource {
                         if subdirectory.is_some() { "&" } else { "#" }
                     )?;
                 }
+                if let Some(submodules) = git.submodules() {
+                    write!(
+                        f,
+                        "{}submodules={submodules}",
+                        if subdirectory.is_some() || git.lfs().enabled() {
+                            "&"
+                        } else {
+                            "#"
+                        }
+                    )?;
+                }
             }
             Self::Path { url, .. } => {
                 write!(f, "{url}")?;
@@ -876,6 +902,12 @@ impl From<RequirementSource> for RequirementSourceWire {
                     url.query_pairs_mut().append_pair("lfs", "true");
                 }
 
+                // Persist whether to check out submodules only when set for the repository.
+                if let Some(submodules) = git.submodules() {
+                    url.query_pairs_mut()
+                        .append_pair("submodules", if submodules { "true" } else { "false" });
+                }
+
                 // Put the requested reference in the query.
                 match git.reference() {
                     GitReference::Branch(branch) => {
@@ -894,7 +926,7 @@ impl From<RequirementSource> for RequirementSourceWire {
 
                 // Put the precise commit in the fragment.
                 if let Some(precise) = git.precise() {
-                    url.set_fragment(Some(&precise.to_string()));
+                    url.set_fragment(Some(precise.as_str()));
                 }
 
                 Self::Git {
@@ -953,6 +985,7 @@ impl TryFrom<RequirementSourceWire> for RequirementSource {
                 let mut reference = GitReference::DefaultBranch;
                 let mut subdirectory: Option<PortablePathBuf> = None;
                 let mut lfs = GitLfs::Disabled;
+                let mut submodules = None;
                 for (key, val) in repository.query_pairs() {
                     match &*key {
                         "tag" => reference = GitReference::Tag(val.into_owned()),
@@ -962,6 +995,7 @@ impl TryFrom<RequirementSourceWire> for RequirementSource {
                             subdirectory = Some(PortablePathBuf::from(val.as_ref()));
                         }
                         "lfs" => lfs = GitLfs::from(val.eq_ignore_ascii_case("true")),
+                        "submodules" => submodules = Some(val.eq_ignore_ascii_case("true")),
                         _ => {}
                     }
                 }
@@ -989,6 +1023,9 @@ impl TryFrom<RequirementSourceWire> for RequirementSource {
                 if lfs.enabled() {
                     frags.push("lfs=true".to_string());
                 }
+                if let Some(submodules) = submodules {
+                    frags.push(format!("submodules={submodules}"));
+                }
                 if !frags.is_empty() {
                     url.set_fragment(Some(&frags.join("&")));
                 }
@@ -996,7 +1033,8 @@ impl TryFrom<RequirementSourceWire> for RequirementSource {
                 let url = VerbatimUrl::from_url(url);
 
                 Ok(Self::Git {
-                    git: GitUrl::from_fields(repository, reference, precise, lfs)?,
+                    git: GitUrl::from_fields(repository, reference, precise, lfs)?
+                        .with_submodules(submodules),
                     subdirectory: subdirectory.map(Box::<Path>::from),
                     url,
                 })
@@ -1078,7 +1116,9 @@ impl TryFrom<RequirementSourceWire> for RequirementSource {
 mod tests {
     use std::path::PathBuf;
 
+    use uv_git_types::{GitLfs, GitReference, GitUrl};
     use uv_pep508::{MarkerTree, VerbatimUrl};
+    use uv_redacted::DisplaySafeUrl;
 
     use crate::{Requirement, RequirementSource};
 
@@ -1123,5 +1163,34 @@ mod tests {
         let raw = toml::to_string(&requirement).unwrap();
         let deserialized: Requirement = toml::from_str(&raw).unwrap();
         assert_eq!(requirement, deserialized);
+
+        let requirement = Requirement {
+            name: "foo".parse().unwrap(),
+            extras: Box::new([]),
+            groups: Box::new([]),
+            marker: MarkerTree::TRUE,
+            source: RequirementSource::Git {
+                git: GitUrl::from_reference(
+                    DisplaySafeUrl::parse("https://github.com/astral-test/uv-public-pypackage")
+                        .unwrap(),
+                    GitReference::Tag("0.0.1".to_string()),
+                    GitLfs::Disabled,
+                )
+                .unwrap()
+                .with_submodules(Some(false)),
+                subdirectory: None,
+                url: VerbatimUrl::from_url(
+                    DisplaySafeUrl::parse(
+                        "git+https://github.com/astral-test/uv-public-pypackage@0.0.1#submodules=false",
+                    )
+                    .unwrap(),
+                ),
+            },
+            origin: None,
+        };
+
+        let raw = toml::to_string(&requirement).unwrap();
+        let deserialized: Requirement = toml::from_str(&raw).unwrap();
+        assert_eq!(requirement, deserialized);
     }
 }
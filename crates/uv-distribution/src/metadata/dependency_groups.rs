@@ -85,8 +85,8 @@ impl SourcedDependencyGroups {
 
         // The subsequent API takes an absolute path to the dir the pyproject is in
         let empty = PathBuf::new();
-        let absolute_pyproject_path =
-            std::path::absolute(pyproject_path).map_err(WorkspaceError::Normalize)?;
+        let absolute_pyproject_path = std::path::absolute(pyproject_path)
+            .map_err(|err| WorkspaceError::Normalize(pyproject_path.to_path_buf(), err))?;
         let project_dir = absolute_pyproject_path.parent().unwrap_or(&empty);
         let project = VirtualProject::discover(project_dir, &discovery, cache).await?;
 
@@ -169,6 +169,7 @@ impl LoweredRequirement {
                             tag,
                             branch,
                             lfs,
+                            submodules,
                             marker,
                             ..
                         } => {
@@ -179,6 +180,7 @@ impl LoweredRequirement {
                                 tag,
                                 branch,
                                 lfs,
+                                submodules,
                             )?;
                             (source, marker)
                         }
@@ -415,6 +417,7 @@ impl LoweredRequirement {
                             tag,
                             branch,
                             lfs,
+                            submodules,
                             marker,
                             ..
                         } => {
@@ -425,6 +428,7 @@ impl LoweredRequirement {
                                 tag,
                                 branch,
                                 lfs,
+                                submodules,
                             )?;
                             (source, marker)
                         }
@@ -619,6 +623,7 @@ fn git_source(
     tag: Option<String>,
     branch: Option<String>,
     lfs: Option<bool>,
+    submodules: Option<bool>,
 ) -> Result<RequirementSource, LoweringError> {
     let reference = match (rev, tag, branch) {
         (None, None, None) => GitReference::DefaultBranch,
@@ -649,6 +654,9 @@ fn git_source(
     if lfs.enabled() {
         frags.push("lfs=true".to_string());
     }
+    if let Some(submodules) = submodules {
+        frags.push(format!("submodules={submodules}"));
+    }
     if !frags.is_empty() {
         url.set_fragment(Some(&frags.join("&")));
     }
@@ -659,7 +667,7 @@ fn git_source(
 
     Ok(RequirementSource::Git {
         url,
-        git: GitUrl::from_fields(repository, reference, None, lfs)?,
+        git: GitUrl::from_fields(repository, reference, None, lfs)?.with_submodules(submodules),
         subdirectory,
     })
 }
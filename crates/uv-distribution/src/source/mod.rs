@@ -23,7 +23,7 @@ use url::Url;
 use zip::ZipArchive;
 
 use uv_auth::CredentialsCache;
-use uv_cache::{Cache, CacheBucket, CacheEntry, CacheShard, Removal, WheelCache};
+use uv_cache::{Cache, CacheBucket, CacheEntry, CacheShard, Refresh, Removal, WheelCache};
 use uv_cache_info::CacheInfo;
 use uv_client::{
     CacheControl, CachedClientError, Connectivity, DataWithCachePolicy, RegistryClient,
@@ -444,6 +444,15 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         name.and_then(|name| self.build_context.extra_build_variables().get(name))
     }
 
+    /// Returns `true` if the commits that Git references were previously resolved to must be
+    /// resolved again for the given package, under the [`Refresh`] policy.
+    fn must_revalidate_git(&self, name: Option<&PackageName>) -> bool {
+        match name {
+            Some(name) => self.build_context.cache().must_revalidate_package(name),
+            None => matches!(self.build_context.cache().refresh(), Refresh::All(_)),
+        }
+    }
+
     /// Build a source distribution from a remote URL.
     async fn url<'data>(
         &self,
@@ -1577,6 +1586,7 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
                 resource.git,
                 client.unmanaged.disable_ssl(resource.git.repository()),
                 client.unmanaged.connectivity() == Connectivity::Offline,
+                self.must_revalidate_git(source.name()),
                 self.build_context.cache().bucket(CacheBucket::Git),
                 self.reporter
                     .clone()
@@ -1792,6 +1802,7 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
                 resource.git,
                 client.unmanaged.disable_ssl(resource.git.repository()),
                 client.unmanaged.connectivity() == Connectivity::Offline,
+                self.must_revalidate_git(source.name()),
                 self.build_context.cache().bucket(CacheBucket::Git),
                 self.reporter
                     .clone()
@@ -2056,6 +2067,7 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
                 git,
                 client.unmanaged.disable_ssl(git.repository()),
                 client.unmanaged.connectivity() == Connectivity::Offline,
+                self.must_revalidate_git(source.name()),
                 self.build_context.cache().bucket(CacheBucket::Git),
                 self.reporter
                     .clone()
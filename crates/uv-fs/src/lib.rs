@@ -1,3 +1,4 @@
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
 #[cfg(feature = "tokio")]
@@ -8,6 +9,8 @@ use encoding_rs_io::DecodeReaderBytes;
 use tempfile::NamedTempFile;
 use tracing::warn;
 
+use uv_warnings::warn_user;
+
 pub use crate::locked_file::*;
 pub use crate::path::*;
 
@@ -253,6 +256,98 @@ pub fn write_atomic_sync(path: impl AsRef<Path>, data: impl AsRef<[u8]>) -> std:
     persist_with_retry_sync(temp_file, path.as_ref())
 }
 
+/// Write `data` to `path` atomically, as in [`write_atomic_sync`], preserving the permissions of
+/// any existing file, e.g., to replace a user's `pyproject.toml`.
+///
+/// If `path` is a symlink, the target of the symlink is replaced, rather than the symlink itself.
+///
+/// If `durable` is set, the file is flushed to disk before it's renamed, and the directory is
+/// flushed after, such that the rename itself is durable.
+pub fn overwrite_atomic_sync(
+    path: impl AsRef<Path>,
+    data: impl AsRef<[u8]>,
+    durable: bool,
+) -> std::io::Result<()> {
+    let path = resolve_symlinks(path.as_ref())?;
+    let path = path.as_path();
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let mut temp_file = tempfile_in(parent)?;
+    temp_file.write_all(data.as_ref())?;
+
+    match fs_err::metadata(path) {
+        Ok(metadata) => fs_err::set_permissions(temp_file.path(), metadata.permissions())?,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+
+    // On Windows, this calls `FlushFileBuffers`.
+    if durable {
+        temp_file.as_file().sync_all()?;
+    }
+
+    // On failure, the temporary file is removed when dropped.
+    persist_with_retry_sync(temp_file, path)?;
+
+    // Some filesystems (e.g., some network mounts) don't support flushing directories; since the
+    // file itself was flushed, treat that as a warning.
+    if durable && let Err(err) = sync_dir(parent) {
+        warn_user!(
+            "Failed to flush directory `{}` after writing `{}`: {err}",
+            parent.user_display(),
+            path.user_display()
+        );
+    }
+
+    Ok(())
+}
+
+/// The maximum number of symlinks to follow when resolving a path, matching Linux.
+const MAX_SYMLINKS: usize = 40;
+
+/// Follow the chain of symlinks starting at `path` to its final target.
+///
+/// Unlike [`fs_err::canonicalize`], the target doesn't need to exist, and the remaining components
+/// of the path are left as-is.
+fn resolve_symlinks(path: &Path) -> std::io::Result<PathBuf> {
+    let mut path = path.to_path_buf();
+    for _ in 0..MAX_SYMLINKS {
+        match fs_err::symlink_metadata(&path) {
+            Ok(metadata) if metadata.file_type().is_symlink() => {
+                let target = fs_err::read_link(&path)?;
+                // A relative target is relative to the directory containing the symlink.
+                path = match path.parent() {
+                    Some(parent) => parent.join(target),
+                    None => target,
+                };
+            }
+            Ok(_) => return Ok(path),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(path),
+            Err(err) => return Err(err),
+        }
+    }
+    Err(std::io::Error::other(format!(
+        "Too many levels of symbolic links: `{}`",
+        path.user_display()
+    )))
+}
+
+/// Flush the directory entries of the given directory to disk.
+#[cfg(unix)]
+fn sync_dir(path: &Path) -> std::io::Result<()> {
+    fs_err::File::open(path)?.sync_all()
+}
+
+/// Flush the directory entries of the given directory to disk.
+///
+/// On Windows, directories can't be flushed; the rename is made durable by flushing the file.
+#[cfg(not(unix))]
+fn sync_dir(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
 /// Copy `from` to `to` atomically using a temporary file and atomic rename.
 pub fn copy_atomic_sync(from: impl AsRef<Path>, to: impl AsRef<Path>) -> std::io::Result<()> {
     let temp_file = tempfile_in(to.as_ref().parent().expect("Write path must have a parent"))?;
@@ -705,3 +800,40 @@ pub fn copy_dir_all(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> std::io::Re
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(unix)]
+    fn overwrite_through_symlink() -> std::io::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let target = temp_dir.path().join("shared").join("pyproject.toml");
+        fs_err::create_dir_all(target.parent().unwrap())?;
+        fs_err::write(&target, "[project]\n")?;
+
+        let project = temp_dir.path().join("project");
+        fs_err::create_dir_all(&project)?;
+        let link = project.join("pyproject.toml");
+        fs_err::os::unix::fs::symlink(Path::new("../shared/pyproject.toml"), &link)?;
+
+        overwrite_atomic_sync(&link, "[project]\nname = \"foo\"\n", true)?;
+
+        // The symlink is preserved, and the target is updated.
+        assert!(fs_err::symlink_metadata(&link)?.file_type().is_symlink());
+        assert_eq!(
+            fs_err::read_link(&link)?,
+            Path::new("../shared/pyproject.toml")
+        );
+        assert_eq!(
+            fs_err::read_to_string(&target)?,
+            "[project]\nname = \"foo\"\n"
+        );
+
+        // No temporary files are left behind next to the symlink.
+        assert_eq!(fs_err::read_dir(&project)?.count(), 1);
+
+        Ok(())
+    }
+}
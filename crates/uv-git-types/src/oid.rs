@@ -1,34 +1,211 @@
-use std::fmt::{Display, Formatter};
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter, LowerHex, UpperHex};
+use std::hash::{Hash, Hasher};
 use std::str::{self, FromStr};
 
 use thiserror::Error;
 
+#[cfg(feature = "proptest")]
+pub mod strategy;
+
 /// Unique identity of any Git object (commit, tree, blob, tag).
 ///
-/// This type's `FromStr` implementation validates that it's exactly 40 hex characters, i.e. a
-/// full-length git commit.
+/// This type's `FromStr` implementation validates that it's exactly 40 or 64 hex characters, i.e.
+/// a full-length SHA-1 or SHA-256 object ID, respectively, and normalizes it to lowercase.
 ///
-/// If Git's SHA-256 support becomes more widespread in the future (in particular if GitHub ever
-/// adds support), we might need to make this an enum.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Equality, ordering, and hashing all agree with the string representation, e.g., object IDs
+/// sort lexicographically by [`GitOid::as_str`], regardless of their [`HashKind`]. They're
+/// implemented by hand, together, to uphold the contract that equal object IDs hash identically
+/// (e.g., when keying a `HashMap` of checkouts); deriving only some of them would break it.
+#[derive(Copy, Clone, Eq)]
 pub struct GitOid {
-    bytes: [u8; 40],
+    /// The hex representation, padded with zeros for SHA-1 object IDs.
+    bytes: [u8; 64],
+    kind: HashKind,
+}
+
+/// The hash function used to compute a [`GitOid`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HashKind {
+    /// SHA-1, Git's default, with 40-character object IDs.
+    Sha1,
+    /// SHA-256, as used by repositories initialized with `--object-format=sha256`, with
+    /// 64-character object IDs.
+    Sha256,
+}
+
+impl HashKind {
+    /// The length of the hex representation of an object ID.
+    pub const fn hex_len(self) -> usize {
+        self.raw_len() * 2
+    }
+
+    /// The length of the raw, binary representation of an object ID.
+    pub const fn raw_len(self) -> usize {
+        match self {
+            Self::Sha1 => 20,
+            Self::Sha256 => 32,
+        }
+    }
 }
 
+/// The minimum length of an abbreviated object ID, matching Git's own minimum.
+const MIN_ABBREVIATION_LEN: usize = 4;
+
 impl GitOid {
+    /// The SHA-1 object ID of the empty tree.
+    pub const EMPTY_TREE_SHA1: Self = Self::from_static("4b825dc642cb6eb9a060caf7ff4b6f8fb5d4e8fa");
+
+    /// The SHA-1 null object ID, i.e., all zeros, which Git uses to denote a missing object.
+    pub const NULL_SHA1: Self = Self::from_static("0000000000000000000000000000000000000000");
+
+    /// The SHA-256 null object ID, i.e., all zeros.
+    pub const NULL_SHA256: Self =
+        Self::from_static("0000000000000000000000000000000000000000000000000000000000000000");
+
+    /// Create an object ID from a string literal, e.g., for a constant.
+    ///
+    /// Equivalent to [`GitOid::from_str`], but usable in `const` contexts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string isn't a valid object ID; at compile time, if evaluated in a `const`
+    /// context.
+    pub const fn from_static(s: &'static str) -> Self {
+        let kind = if s.len() == HashKind::Sha1.hex_len() {
+            HashKind::Sha1
+        } else if s.len() == HashKind::Sha256.hex_len() {
+            HashKind::Sha256
+        } else {
+            panic!("Object ID must be exactly 40 or 64 hex characters");
+        };
+
+        let mut bytes = [0; 64];
+        let mut index = 0;
+        while index < s.len() {
+            let byte = s.as_bytes()[index];
+            assert!(byte.is_ascii_hexdigit(), "Object ID must be hex characters");
+            bytes[index] = byte.to_ascii_lowercase();
+            index += 1;
+        }
+        Self { bytes, kind }
+    }
+
     /// Return the string representation of an object ID.
     pub fn as_str(&self) -> &str {
-        str::from_utf8(&self.bytes).unwrap()
+        str::from_utf8(&self.bytes[..self.kind.hex_len()]).unwrap()
+    }
+
+    /// Create an object ID from its raw, binary representation, i.e., 20 bytes for SHA-1 or 32
+    /// bytes for SHA-256.
+    pub fn from_raw_bytes(raw: &[u8]) -> Result<Self, OidParseError> {
+        let kind = if raw.len() == HashKind::Sha1.raw_len() {
+            HashKind::Sha1
+        } else if raw.len() == HashKind::Sha256.raw_len() {
+            HashKind::Sha256
+        } else {
+            return Err(OidParseError::WrongRawLength { len: raw.len() });
+        };
+
+        let mut bytes = [0; 64];
+        hex::encode_to_slice(raw, &mut bytes[..kind.hex_len()])
+            .map_err(|_| OidParseError::WrongRawLength { len: raw.len() })?;
+        Ok(Self { bytes, kind })
+    }
+
+    /// Return the raw, binary representation of the object ID.
+    pub fn to_raw_bytes(&self) -> Vec<u8> {
+        // The hex representation is validated on construction.
+        hex::decode(self.as_str()).unwrap_or_default()
+    }
+
+    /// Returns `true` if this is the null object ID, i.e., all zeros.
+    ///
+    /// Git uses the null object ID as a sentinel, e.g., for a deleted reference or an unborn
+    /// branch. It parses like any other object ID, but never refers to an actual commit, so it
+    /// shouldn't be used to pin a revision.
+    pub fn is_null(&self) -> bool {
+        self.bytes.iter().all(|byte| *byte == b'0' || *byte == 0)
+    }
+
+    /// Return the hash function used to compute the object ID.
+    pub fn hash_kind(&self) -> HashKind {
+        self.kind
+    }
+
+    /// Returns `true` if the given, possibly abbreviated, object ID refers to this object ID, i.e.,
+    /// if it's a case-insensitive prefix of it.
+    ///
+    /// Returns `false` if the reference is longer than this object ID, and an error if it isn't a
+    /// valid abbreviation, i.e., at least four hex characters.
+    pub fn matches(&self, reference: &str) -> Result<bool, OidParseError> {
+        validate_hex(reference)?;
+
+        if reference.len() < MIN_ABBREVIATION_LEN {
+            return Err(OidParseError::AbbreviationTooShort {
+                input: reference.to_string(),
+            });
+        }
+
+        Ok(self
+            .as_str()
+            .as_bytes()
+            .get(..reference.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(reference.as_bytes())))
+    }
+
+    /// Check whether the object ID satisfies the requested revision, e.g., to verify that a
+    /// checked-out commit is the one that was requested.
+    ///
+    /// A full object ID must match exactly, and an abbreviated one (of at least four hex
+    /// characters) must be a prefix, ignoring case. Anything else, like a branch or tag name,
+    /// can't be verified against the object ID alone.
+    pub fn satisfies(&self, requested: &str) -> RevisionMatch {
+        match self.matches(requested) {
+            Ok(true) if requested.len() == self.as_str().len() => RevisionMatch::Exact,
+            Ok(true) => RevisionMatch::PrefixMatch,
+            Ok(false) => RevisionMatch::Mismatch,
+            Err(_) => RevisionMatch::NotASha,
+        }
+    }
+
+    /// Return the first `len` characters of the object ID, or the full object ID if it's shorter,
+    /// borrowed from the object ID itself.
+    pub fn abbrev(&self, len: usize) -> &str {
+        let oid = self.as_str();
+        &oid[..len.min(oid.len())]
     }
 
     /// Return a truncated representation, i.e., the first 16 characters of the SHA.
     pub fn as_short_str(&self) -> &str {
-        &self.as_str()[..16]
+        self.abbrev(16)
     }
 
     /// Return a (very) truncated representation, i.e., the first 8 characters of the SHA.
     pub fn as_tiny_str(&self) -> &str {
-        &self.as_str()[..8]
+        self.abbrev(8)
+    }
+}
+
+/// The result of checking whether a [`GitOid`] satisfies a requested revision, with
+/// [`GitOid::satisfies`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RevisionMatch {
+    /// The requested revision is the full object ID.
+    Exact,
+    /// The requested revision is an abbreviation of the object ID.
+    PrefixMatch,
+    /// The requested revision is a full or abbreviated object ID, but a different one.
+    Mismatch,
+    /// The requested revision isn't an object ID, e.g., it's a branch or tag name, or too short
+    /// to be an abbreviation.
+    NotASha,
+}
+
+impl RevisionMatch {
+    /// Returns `true` if the object ID is the requested revision, in full or abbreviated.
+    pub fn is_match(self) -> bool {
+        matches!(self, Self::Exact | Self::PrefixMatch)
     }
 }
 
@@ -36,49 +213,220 @@ impl GitOid {
 pub enum OidParseError {
     #[error("Object ID cannot be parsed from empty string")]
     Empty,
-    #[error("Object ID must be exactly 40 hex characters")]
-    WrongLength,
-    #[error("Object ID must be valid hex characters")]
-    NotHex,
+    #[error(
+        "Object ID `{input}` is {len} characters, but must be a full object ID of exactly 40 or 64 hex characters"
+    )]
+    TooShortForFull { input: String, len: usize },
+    #[error("Object ID `{input}` is {len} characters, but must be exactly 40 or 64 hex characters")]
+    WrongLength { input: String, len: usize },
+    #[error("Raw object ID must be exactly 20 or 32 bytes, but found {len}")]
+    WrongRawLength { len: usize },
+    #[error(
+        "Abbreviated object ID `{input}` must be at least {MIN_ABBREVIATION_LEN} hex characters"
+    )]
+    AbbreviationTooShort { input: String },
+    #[error(
+        "Object ID `{input}` must be hex characters, but found `{character}` at offset {offset}{hint}",
+        hint = if input.contains('/') { " (it looks like a branch name)" } else { "" }
+    )]
+    InvalidCharacter {
+        input: String,
+        character: char,
+        offset: usize,
+    },
+}
+
+/// The maximum number of characters of the input to include in an [`OidParseError`].
+const MAX_INPUT_LEN: usize = 80;
+
+/// Return the given input for an [`OidParseError`], truncated to [`MAX_INPUT_LEN`] characters.
+fn error_input(input: &str) -> String {
+    match input.char_indices().nth(MAX_INPUT_LEN) {
+        Some((offset, _)) => format!("{}...", &input[..offset]),
+        None => input.to_string(),
+    }
+}
+
+/// Validate that the given, possibly abbreviated, object ID is non-empty and consists of hex
+/// characters.
+fn validate_hex(input: &str) -> Result<(), OidParseError> {
+    validate_hex_bytes(input.as_bytes())
+}
+
+/// Validate that the given, possibly abbreviated, object ID is non-empty and consists of ASCII
+/// hex characters, without requiring it to be valid UTF-8.
+///
+/// Offsets in errors are byte offsets, which agree with [`validate_hex`] for valid UTF-8.
+fn validate_hex_bytes(input: &[u8]) -> Result<(), OidParseError> {
+    if input.is_empty() {
+        return Err(OidParseError::Empty);
+    }
+
+    if let Some(offset) = input.iter().position(|byte| !byte.is_ascii_hexdigit()) {
+        // Decode the offending character, which may span multiple bytes (or be invalid UTF-8).
+        let character = String::from_utf8_lossy(&input[offset..])
+            .chars()
+            .next()
+            .unwrap_or(char::REPLACEMENT_CHARACTER);
+        return Err(OidParseError::InvalidCharacter {
+            input: error_input(&String::from_utf8_lossy(input)),
+            character,
+            offset,
+        });
+    }
+
+    Ok(())
 }
 
 impl FromStr for GitOid {
     type Err = OidParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.is_empty() {
-            return Err(OidParseError::Empty);
-        }
+        Self::try_from(s.as_bytes())
+    }
+}
 
-        if s.len() != 40 {
-            return Err(OidParseError::WrongLength);
-        }
+/// Parses an object ID from its hex representation, e.g., as read from the output of a Git
+/// command, validating the bytes directly rather than as UTF-8 first.
+///
+/// Equivalent to [`GitOid::from_str`], with the same errors. For the raw, binary representation,
+/// use [`GitOid::from_raw_bytes`].
+impl TryFrom<&[u8]> for GitOid {
+    type Error = OidParseError;
 
-        if !s.chars().all(|ch| ch.is_ascii_hexdigit()) {
-            return Err(OidParseError::NotHex);
-        }
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        validate_hex_bytes(value)?;
+
+        // The input is ASCII hex, and so valid UTF-8.
+        let input = || String::from_utf8_lossy(value);
+        let kind = if value.len() == HashKind::Sha1.hex_len() {
+            HashKind::Sha1
+        } else if value.len() == HashKind::Sha256.hex_len() {
+            HashKind::Sha256
+        } else if value.len() < HashKind::Sha1.hex_len() {
+            return Err(OidParseError::TooShortForFull {
+                input: input().into_owned(),
+                len: value.len(),
+            });
+        } else {
+            return Err(OidParseError::WrongLength {
+                input: error_input(&input()),
+                len: value.len(),
+            });
+        };
 
-        let mut bytes = [0; 40];
-        bytes.copy_from_slice(s.as_bytes());
-        Ok(Self { bytes })
+        // Git prints object IDs in lowercase; normalize such that equivalent IDs compare equal.
+        let mut bytes = [0; 64];
+        bytes[..value.len()].copy_from_slice(value);
+        bytes.make_ascii_lowercase();
+        Ok(Self { bytes, kind })
     }
 }
 
+/// Compares the object ID against a string, ignoring case. Abbreviated object IDs are unequal.
+impl PartialEq<str> for GitOid {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str().eq_ignore_ascii_case(other)
+    }
+}
+
+impl PartialEq<&str> for GitOid {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<GitOid> for str {
+    fn eq(&self, other: &GitOid) -> bool {
+        other == self
+    }
+}
+
+impl PartialEq<GitOid> for &str {
+    fn eq(&self, other: &GitOid) -> bool {
+        other == *self
+    }
+}
+
+impl AsRef<str> for GitOid {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq for GitOid {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Ord for GitOid {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl PartialOrd for GitOid {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Hashes the string representation, such that equal object IDs hash identically, consistent with
+/// [`PartialEq`] and [`Ord`].
+impl Hash for GitOid {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+/// Formats the object ID as its hex representation, e.g., `GitOid("4a23745b...")`, rather than
+/// its padded buffer.
+impl Debug for GitOid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("GitOid").field(&self.as_str()).finish()
+    }
+}
+
+/// Formats the object ID in lowercase hex, truncated to the precision (e.g., `{oid:.7}`), if
+/// any, and padded to the width.
 impl Display for GitOid {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.as_str())
+        f.pad(self.as_str())
     }
 }
 
+impl LowerHex for GitOid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl UpperHex for GitOid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut bytes = self.bytes;
+        bytes.make_ascii_uppercase();
+        f.pad(str::from_utf8(&bytes[..self.kind.hex_len()]).unwrap())
+    }
+}
+
+/// Serializes as a hex string for human-readable formats (e.g., in lockfiles), and as raw bytes
+/// otherwise.
 impl serde::Serialize for GitOid {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        self.as_str().serialize(serializer)
+        if serializer.is_human_readable() {
+            self.as_str().serialize(serializer)
+        } else {
+            serializer.serialize_bytes(&self.to_raw_bytes())
+        }
     }
 }
 
+/// Deserializes from a hex string or raw bytes, parsed directly into the inline buffer, i.e.,
+/// without allocating for borrowed input.
 impl<'de> serde::Deserialize<'de> for GitOid {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -90,23 +438,47 @@ impl<'de> serde::Deserialize<'de> for GitOid {
             type Value = GitOid;
 
             fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
-                f.write_str("a string")
+                f.write_str("a hex string or raw bytes")
             }
 
             fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
                 GitOid::from_str(v).map_err(serde::de::Error::custom)
             }
+
+            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                GitOid::from_raw_bytes(v).map_err(serde::de::Error::custom)
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(Visitor)
+        } else {
+            deserializer.deserialize_bytes(Visitor)
         }
+    }
+}
 
-        deserializer.deserialize_str(Visitor)
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for GitOid {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        std::borrow::Cow::Borrowed("GitOid")
+    }
+
+    fn json_schema(_generator: &mut schemars::generate::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "type": "string",
+            "pattern": r"^([0-9a-fA-F]{40}|[0-9a-fA-F]{64})$",
+            "description": "A full Git object ID, i.e., 40 (SHA-1) or 64 (SHA-256) hex characters"
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::hash::{DefaultHasher, Hash, Hasher};
     use std::str::FromStr;
 
-    use super::{GitOid, OidParseError};
+    use super::{GitOid, HashKind, OidParseError, RevisionMatch};
 
     #[test]
     fn git_oid() {
@@ -116,15 +488,557 @@ mod tests {
         assert_eq!(GitOid::from_str(""), Err(OidParseError::Empty));
         assert_eq!(
             GitOid::from_str(&str::repeat("a", 41)),
-            Err(OidParseError::WrongLength)
+            Err(OidParseError::WrongLength {
+                input: str::repeat("a", 41),
+                len: 41
+            })
         );
         assert_eq!(
             GitOid::from_str(&str::repeat("a", 39)),
-            Err(OidParseError::WrongLength)
+            Err(OidParseError::TooShortForFull {
+                input: str::repeat("a", 39),
+                len: 39
+            })
         );
         assert_eq!(
             GitOid::from_str(&str::repeat("x", 40)),
-            Err(OidParseError::NotHex)
+            Err(OidParseError::InvalidCharacter {
+                input: str::repeat("x", 40),
+                character: 'x',
+                offset: 0
+            })
+        );
+        assert_eq!(
+            GitOid::from_str("4a23745badf5bf5ef7928f1e346e9986bd696d8g"),
+            Err(OidParseError::InvalidCharacter {
+                input: "4a23745badf5bf5ef7928f1e346e9986bd696d8g".to_string(),
+                character: 'g',
+                offset: 39
+            })
+        );
+        assert_eq!(
+            GitOid::from_str("main").unwrap_err().to_string(),
+            "Object ID `main` must be hex characters, but found `m` at offset 0"
+        );
+        assert_eq!(
+            GitOid::from_str("feature/sha256").unwrap_err().to_string(),
+            "Object ID `feature/sha256` must be hex characters, but found `t` at offset 3 (it looks like a branch name)"
+        );
+        assert_eq!(
+            GitOid::from_str("4a23745").unwrap_err().to_string(),
+            "Object ID `4a23745` is 7 characters, but must be a full object ID of exactly 40 or 64 hex characters"
+        );
+        assert_eq!(
+            GitOid::from_str(&str::repeat("a", 100))
+                .unwrap_err()
+                .to_string(),
+            format!(
+                "Object ID `{}...` is 100 characters, but must be exactly 40 or 64 hex characters",
+                str::repeat("a", 80)
+            )
+        );
+    }
+
+    #[test]
+    fn abbreviated() {
+        // Abbreviated object IDs are rejected, such that truncating a `GitOid` can't panic.
+        for abbreviated in [
+            "4a23745",
+            "4a23745badf5bf5e",
+            "4a23745badf5bf5ef7928f1e346e9986",
+        ] {
+            assert_eq!(
+                GitOid::from_str(abbreviated),
+                Err(OidParseError::TooShortForFull {
+                    input: abbreviated.to_string(),
+                    len: abbreviated.len()
+                })
+            );
+        }
+
+        let oid = GitOid::from_str("4a23745badf5bf5ef7928f1e346e9986bd696d82").unwrap();
+        assert_eq!(oid.as_short_str(), "4a23745badf5bf5e");
+        assert_eq!(oid.as_tiny_str(), "4a23745b");
+    }
+
+    #[test]
+    fn sha256() {
+        let sha1 = GitOid::from_str("4a23745badf5bf5ef7928f1e346e9986bd696d82").unwrap();
+        assert_eq!(sha1.hash_kind(), HashKind::Sha1);
+
+        let sha256 = "4a23745badf5bf5ef7928f1e346e9986bd696d824a23745badf5bf5ef7928f1e";
+        let oid = GitOid::from_str(sha256).unwrap();
+        assert_eq!(oid.hash_kind(), HashKind::Sha256);
+        assert_eq!(oid.as_str(), sha256);
+        assert_eq!(oid.to_string(), sha256);
+        assert_eq!(oid.as_short_str(), "4a23745badf5bf5e");
+
+        // A SHA-1 object ID that's a prefix of a SHA-256 object ID sorts before it.
+        assert_ne!(oid, sha1);
+        assert!(sha1 < oid);
+
+        for len in [41, 63, 65] {
+            assert!(matches!(
+                GitOid::from_str(&str::repeat("a", len)),
+                Err(OidParseError::WrongLength { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn case() {
+        let lower = GitOid::from_str("4a23745badf5bf5ef7928f1e346e9986bd696d82").unwrap();
+        let upper = GitOid::from_str("4A23745BADF5BF5EF7928F1E346E9986BD696D82").unwrap();
+        let mixed = GitOid::from_str("4a23745BADF5bf5ef7928f1e346e9986bd696D82").unwrap();
+        assert_eq!(lower, upper);
+        assert_eq!(lower, mixed);
+        assert_eq!(
+            upper.to_string(),
+            "4a23745badf5bf5ef7928f1e346e9986bd696d82"
+        );
+
+        let hash = |oid: &GitOid| {
+            let mut hasher = DefaultHasher::new();
+            oid.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&lower), hash(&upper));
+    }
+
+    #[test]
+    fn raw_bytes() {
+        for hex in [
+            "4a23745badf5bf5ef7928f1e346e9986bd696d82",
+            "4a23745badf5bf5ef7928f1e346e9986bd696d824a23745badf5bf5ef7928f1e",
+        ] {
+            let oid = GitOid::from_str(hex).unwrap();
+            let raw = oid.to_raw_bytes();
+            assert_eq!(raw.len(), oid.hash_kind().raw_len());
+            assert_eq!(GitOid::from_raw_bytes(&raw).unwrap(), oid);
+            assert_eq!(GitOid::from_raw_bytes(&raw).unwrap().as_str(), hex);
+        }
+
+        let oid = GitOid::from_str("00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff").unwrap();
+        assert_eq!(oid.to_raw_bytes(), [0x00, 0xff].repeat(10));
+
+        assert_eq!(
+            GitOid::from_raw_bytes(&[]),
+            Err(OidParseError::WrongRawLength { len: 0 })
+        );
+        assert_eq!(
+            GitOid::from_raw_bytes(&[0; 21]),
+            Err(OidParseError::WrongRawLength { len: 21 })
+        );
+    }
+
+    #[test]
+    fn try_from_bytes() {
+        let oid = GitOid::try_from(b"4A23745BADF5BF5EF7928F1E346E9986BD696D82".as_slice()).unwrap();
+        assert_eq!(
+            oid,
+            GitOid::from_str("4a23745badf5bf5ef7928f1e346e9986bd696d82").unwrap()
+        );
+
+        assert_eq!(GitOid::try_from(b"".as_slice()), Err(OidParseError::Empty));
+        assert_eq!(
+            GitOid::try_from(b"4a23745".as_slice()),
+            Err(OidParseError::TooShortForFull {
+                input: "4a23745".to_string(),
+                len: 7
+            })
+        );
+        assert_eq!(
+            GitOid::try_from(b"4a23745badf5bf5ef7928f1e346e9986bd696d82\n".as_slice()),
+            Err(OidParseError::InvalidCharacter {
+                input: "4a23745badf5bf5ef7928f1e346e9986bd696d82\n".to_string(),
+                character: '\n',
+                offset: 40
+            })
+        );
+
+        // Offsets are byte offsets, for multi-byte characters and invalid UTF-8 alike.
+        assert_eq!(
+            GitOid::from_str("4a2é"),
+            Err(OidParseError::InvalidCharacter {
+                input: "4a2é".to_string(),
+                character: 'é',
+                offset: 3
+            })
+        );
+        assert_eq!(
+            GitOid::try_from("4a2é".as_bytes()),
+            GitOid::from_str("4a2é")
+        );
+        assert_eq!(
+            GitOid::try_from(b"4a2\xff".as_slice()),
+            Err(OidParseError::InvalidCharacter {
+                input: "4a2\u{fffd}".to_string(),
+                character: char::REPLACEMENT_CHARACTER,
+                offset: 3
+            })
+        );
+    }
+
+    #[test]
+    fn matches() {
+        let oid = GitOid::from_str("4a23745badf5bf5ef7928f1e346e9986bd696d82").unwrap();
+        assert_eq!(oid.matches("4a23"), Ok(true));
+        assert_eq!(oid.matches("4A23745B"), Ok(true));
+        assert_eq!(
+            oid.matches("4a23745badf5bf5ef7928f1e346e9986bd696d82"),
+            Ok(true)
+        );
+        assert_eq!(oid.matches("4a23745c"), Ok(false));
+        assert_eq!(oid.matches("a23745ba"), Ok(false));
+
+        // A reference longer than the object ID doesn't match.
+        assert_eq!(
+            oid.matches("4a23745badf5bf5ef7928f1e346e9986bd696d8200"),
+            Ok(false)
+        );
+
+        assert_eq!(oid.matches(""), Err(OidParseError::Empty));
+        assert_eq!(
+            oid.matches("4a2"),
+            Err(OidParseError::AbbreviationTooShort {
+                input: "4a2".to_string()
+            })
+        );
+        assert_eq!(
+            oid.matches("main"),
+            Err(OidParseError::InvalidCharacter {
+                input: "main".to_string(),
+                character: 'm',
+                offset: 0
+            })
+        );
+
+        let sha256 =
+            GitOid::from_str("4a23745badf5bf5ef7928f1e346e9986bd696d824a23745badf5bf5ef7928f1e")
+                .unwrap();
+        assert_eq!(
+            sha256.matches("4a23745badf5bf5ef7928f1e346e9986bd696d824a23"),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn satisfies() {
+        let oid = GitOid::from_str("4a23745badf5bf5ef7928f1e346e9986bd696d82").unwrap();
+        assert_eq!(
+            oid.satisfies("4a23745badf5bf5ef7928f1e346e9986bd696d82"),
+            RevisionMatch::Exact
+        );
+        assert_eq!(
+            oid.satisfies("4A23745BADF5BF5EF7928F1E346E9986BD696D82"),
+            RevisionMatch::Exact
+        );
+        assert_eq!(oid.satisfies("4a23745"), RevisionMatch::PrefixMatch);
+        assert_eq!(oid.satisfies("4A23"), RevisionMatch::PrefixMatch);
+        assert_eq!(
+            oid.satisfies("5a23745badf5bf5ef7928f1e346e9986bd696d82"),
+            RevisionMatch::Mismatch
+        );
+        assert_eq!(oid.satisfies("5a23745"), RevisionMatch::Mismatch);
+
+        // Longer than a SHA-1 object ID, e.g., a SHA-256 object ID, never matches.
+        assert_eq!(
+            oid.satisfies("4a23745badf5bf5ef7928f1e346e9986bd696d824a23745badf5bf5ef7928f1e"),
+            RevisionMatch::Mismatch
+        );
+
+        // Too short to be an abbreviation, or not hex at all.
+        assert_eq!(oid.satisfies("4a2"), RevisionMatch::NotASha);
+        assert_eq!(oid.satisfies(""), RevisionMatch::NotASha);
+        assert_eq!(oid.satisfies("main"), RevisionMatch::NotASha);
+        assert_eq!(oid.satisfies("v1.0.0"), RevisionMatch::NotASha);
+
+        assert!(RevisionMatch::Exact.is_match());
+        assert!(RevisionMatch::PrefixMatch.is_match());
+        assert!(!RevisionMatch::Mismatch.is_match());
+        assert!(!RevisionMatch::NotASha.is_match());
+    }
+
+    #[test]
+    fn abbrev() {
+        let oid = GitOid::from_str("4a23745badf5bf5ef7928f1e346e9986bd696d82").unwrap();
+        assert_eq!(oid.abbrev(7), "4a23745");
+        assert_eq!(oid.abbrev(0), "");
+        assert_eq!(oid.abbrev(40), oid.as_str());
+        assert_eq!(oid.abbrev(100), oid.as_str());
+
+        // SHA-256 object IDs are clamped to their own length.
+        let sha256 = "4a23745badf5bf5ef7928f1e346e9986bd696d824a23745badf5bf5ef7928f1e";
+        let oid = GitOid::from_str(sha256).unwrap();
+        assert_eq!(oid.abbrev(41), &sha256[..41]);
+        assert_eq!(oid.abbrev(usize::MAX), sha256);
+    }
+
+    #[test]
+    fn format() {
+        let oid = GitOid::from_str("4a23745badf5bf5ef7928f1e346e9986bd696d82").unwrap();
+        assert_eq!(format!("{oid}"), "4a23745badf5bf5ef7928f1e346e9986bd696d82");
+        assert_eq!(format!("{oid:.7}"), "4a23745");
+        assert_eq!(format!("{oid:.0}"), "");
+        assert_eq!(
+            format!("{oid:.100}"),
+            "4a23745badf5bf5ef7928f1e346e9986bd696d82"
+        );
+        assert_eq!(
+            format!("{oid:>44}"),
+            "    4a23745badf5bf5ef7928f1e346e9986bd696d82"
+        );
+        assert_eq!(format!("{oid:-<10.7}"), "4a23745---");
+        assert_eq!(format!("{oid:^11.7}"), "  4a23745  ");
+        assert_eq!(
+            format!("{oid:x}"),
+            "4a23745badf5bf5ef7928f1e346e9986bd696d82"
+        );
+        assert_eq!(
+            format!("{oid:X}"),
+            "4A23745BADF5BF5EF7928F1E346E9986BD696D82"
+        );
+        assert_eq!(format!("{oid:.8X}"), "4A23745B");
+        assert_eq!(
+            format!("{oid:?}"),
+            r#"GitOid("4a23745badf5bf5ef7928f1e346e9986bd696d82")"#
+        );
+        assert_eq!(
+            format!(
+                "{:?}",
+                Some(GitOid::from_str("4A23745BADF5BF5EF7928F1E346E9986BD696D82").unwrap())
+            ),
+            r#"Some(GitOid("4a23745badf5bf5ef7928f1e346e9986bd696d82"))"#
+        );
+    }
+
+    /// Equal object IDs that were written in a different case hash identically.
+    ///
+    /// Guards the manual `impl Hash for GitOid`, which must stay consistent with the manual
+    /// `impl PartialEq for GitOid`; don't replace either with a derive.
+    #[test]
+    fn hash_case_insensitive() {
+        let hash = |oid: &GitOid| {
+            let mut hasher = DefaultHasher::new();
+            oid.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let sha1_lower = GitOid::from_str("4a23745badf5bf5ef7928f1e346e9986bd696d82").unwrap();
+        let sha1_upper = GitOid::from_str("4A23745BADF5BF5EF7928F1E346E9986BD696D82").unwrap();
+        assert_eq!(sha1_lower, sha1_upper);
+        assert_eq!(hash(&sha1_lower), hash(&sha1_upper));
+
+        let sha256_lower =
+            GitOid::from_str("4a23745badf5bf5ef7928f1e346e9986bd696d824a23745badf5bf5ef7928f1e")
+                .unwrap();
+        let sha256_upper =
+            GitOid::from_str("4A23745BADF5BF5EF7928F1E346E9986BD696D824A23745BADF5BF5EF7928F1E")
+                .unwrap();
+        assert_eq!(sha256_lower, sha256_upper);
+        assert_eq!(hash(&sha256_lower), hash(&sha256_upper));
+    }
+
+    #[test]
+    fn hash_eq() {
+        let hash = |oid: &GitOid| {
+            let mut hasher = DefaultHasher::new();
+            oid.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        for hex in [
+            "4a23745badf5bf5ef7928f1e346e9986bd696d82",
+            "4a23745badf5bf5ef7928f1e346e9986bd696d824a23745badf5bf5ef7928f1e",
+        ] {
+            let lower = GitOid::from_str(hex).unwrap();
+            let upper = GitOid::from_str(&hex.to_ascii_uppercase()).unwrap();
+            let raw = GitOid::from_raw_bytes(&lower.to_raw_bytes()).unwrap();
+            let bytes = GitOid::try_from(hex.as_bytes()).unwrap();
+            for oid in [upper, raw, bytes] {
+                assert_eq!(lower, oid);
+                assert_eq!(hash(&lower), hash(&oid));
+            }
+        }
+
+        // A SHA-1 object ID isn't equal to a SHA-256 object ID that it's a prefix of, even if
+        // the latter is zero-padded.
+        let sha1 = GitOid::from_str("4a23745badf5bf5ef7928f1e346e9986bd696d82").unwrap();
+        let sha256 =
+            GitOid::from_str("4a23745badf5bf5ef7928f1e346e9986bd696d82000000000000000000000000")
+                .unwrap();
+        assert_ne!(sha1, sha256);
+        assert_ne!(hash(&sha1), hash(&sha256));
+
+        let map = [(sha1, "sha1"), (sha256, "sha256")]
+            .into_iter()
+            .collect::<std::collections::HashMap<_, _>>();
+        assert_eq!(
+            map[&GitOid::from_str("4A23745BADF5BF5EF7928F1E346E9986BD696D82").unwrap()],
+            "sha1"
+        );
+    }
+
+    #[test]
+    fn ordering() {
+        let sha1 = GitOid::from_str("ff00000000000000000000000000000000000000").unwrap();
+        let sha256 =
+            GitOid::from_str("0000000000000000000000000000000000000000000000000000000000000000")
+                .unwrap();
+        let prefixed =
+            GitOid::from_str("ff00000000000000000000000000000000000000000000000000000000000000")
+                .unwrap();
+
+        // Object IDs sort by their string representation, not by length.
+        let mut oids = vec![prefixed, sha1, sha256];
+        oids.sort();
+        assert_eq!(oids, [sha256, sha1, prefixed]);
+        assert!(oids.iter().map(GitOid::as_str).is_sorted());
+
+        // Hashing agrees with the string representation.
+        let hash = |value: &dyn Fn(&mut DefaultHasher)| {
+            let mut hasher = DefaultHasher::new();
+            value(&mut hasher);
+            hasher.finish()
+        };
+        for oid in oids {
+            assert_eq!(
+                hash(&|hasher| oid.hash(hasher)),
+                hash(&|hasher| oid.as_str().hash(hasher))
+            );
+        }
+    }
+
+    #[test]
+    fn from_static() {
+        const OID: GitOid = GitOid::from_static("4A23745BADF5BF5EF7928F1E346E9986BD696D82");
+        assert_eq!(
+            OID,
+            GitOid::from_str("4a23745badf5bf5ef7928f1e346e9986bd696d82").unwrap()
+        );
+        assert_eq!(
+            GitOid::EMPTY_TREE_SHA1.as_str(),
+            "4b825dc642cb6eb9a060caf7ff4b6f8fb5d4e8fa"
+        );
+        assert_eq!(GitOid::NULL_SHA1.as_str(), str::repeat("0", 40));
+
+        let sha256 = "4a23745badf5bf5ef7928f1e346e9986bd696d824a23745badf5bf5ef7928f1e";
+        assert_eq!(
+            GitOid::from_static(sha256),
+            GitOid::from_str(sha256).unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Object ID must be hex characters")]
+    fn from_static_invalid() {
+        let _ = GitOid::from_static("not-a-sha-at-all!!not-a-sha-at-all!!!!!!");
+    }
+
+    #[test]
+    fn serde() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Source {
+            precise: GitOid,
+        }
+
+        for hex in [
+            "4a23745badf5bf5ef7928f1e346e9986bd696d82",
+            "4a23745badf5bf5ef7928f1e346e9986bd696d824a23745badf5bf5ef7928f1e",
+        ] {
+            let source = Source {
+                precise: GitOid::from_str(hex).unwrap(),
+            };
+
+            // Human-readable formats use the hex string.
+            let json = serde_json::to_string(&source).unwrap();
+            assert_eq!(json, format!(r#"{{"precise":"{hex}"}}"#));
+            assert_eq!(serde_json::from_str::<Source>(&json).unwrap(), source);
+
+            let toml = toml::to_string(&source).unwrap();
+            assert_eq!(toml, format!("precise = \"{hex}\"\n"));
+            assert_eq!(toml::from_str::<Source>(&toml).unwrap(), source);
+
+            // Binary formats use the raw bytes.
+            let msgpack = rmp_serde::to_vec(&source).unwrap();
+            assert_eq!(
+                msgpack.len(),
+                source.precise.hash_kind().raw_len() + 3,
+                "expected a one-element array of a bin8 value"
+            );
+            assert_eq!(rmp_serde::from_slice::<Source>(&msgpack).unwrap(), source);
+        }
+
+        // Owned strings are accepted too, with the same errors as borrowed strings.
+        let owned = serde_json::json!({ "precise": "4a23745badf5bf5ef7928f1e346e9986bd696d82" });
+        assert_eq!(
+            serde_json::from_value::<Source>(owned).unwrap().precise,
+            GitOid::from_str("4a23745badf5bf5ef7928f1e346e9986bd696d82").unwrap()
+        );
+        let borrowed = serde_json::from_str::<Source>(r#"{"precise":"main"}"#).unwrap_err();
+        let owned =
+            serde_json::from_value::<Source>(serde_json::json!({ "precise": "main" })).unwrap_err();
+        assert_eq!(
+            borrowed.to_string(),
+            "Object ID `main` must be hex characters, but found `m` at offset 0 at line 1 column 17"
+        );
+        assert_eq!(
+            owned.to_string(),
+            "Object ID `main` must be hex characters, but found `m` at offset 0"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "schemars")]
+    fn json_schema() {
+        let schema = schemars::schema_for!(GitOid);
+        let pattern = schema
+            .get("pattern")
+            .and_then(serde_json::Value::as_str)
+            .unwrap();
+        let pattern = regex::Regex::new(pattern).unwrap();
+
+        assert!(pattern.is_match("4a23745badf5bf5ef7928f1e346e9986bd696d82"));
+        assert!(
+            pattern.is_match("4a23745badf5bf5ef7928f1e346e9986bd696d824a23745badf5bf5ef7928f1e")
+        );
+        assert!(!pattern.is_match("4a23745"));
+        assert!(!pattern.is_match("main"));
+    }
+
+    #[test]
+    fn eq_str() {
+        let oid = GitOid::from_str("4a23745badf5bf5ef7928f1e346e9986bd696d82").unwrap();
+        assert_eq!(oid, "4a23745badf5bf5ef7928f1e346e9986bd696d82");
+        assert_eq!(oid, "4A23745BADF5BF5EF7928F1E346E9986BD696D82");
+        assert_eq!(*"4a23745badf5bf5ef7928f1e346e9986bd696d82", oid);
+        assert_eq!("4A23745BADF5BF5EF7928F1E346E9986BD696D82", oid);
+
+        // Abbreviated object IDs aren't equal, even if they're a prefix.
+        assert_ne!(oid, "4a23745");
+        assert_ne!("4a23745", oid);
+        assert_ne!(oid, "");
+
+        let as_ref: &str = oid.as_ref();
+        assert_eq!(as_ref, oid.as_str());
+    }
+
+    #[test]
+    fn is_null() {
+        assert!(GitOid::NULL_SHA1.is_null());
+        assert!(GitOid::NULL_SHA256.is_null());
+        assert_eq!(GitOid::NULL_SHA256.hash_kind(), HashKind::Sha256);
+        assert!(!GitOid::EMPTY_TREE_SHA1.is_null());
+        assert!(
+            !GitOid::from_str("0000000000000000000000000000000000000001")
+                .unwrap()
+                .is_null()
+        );
+
+        // The null object ID parses like any other.
+        assert_eq!(
+            GitOid::from_str(&str::repeat("0", 40)),
+            Ok(GitOid::NULL_SHA1)
         );
     }
 }
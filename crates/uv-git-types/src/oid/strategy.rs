@@ -0,0 +1,104 @@
+//! [`proptest`] strategies for generating object IDs, valid and otherwise.
+
+use std::str::FromStr;
+
+use proptest::prelude::*;
+use proptest::sample::Index;
+
+use crate::GitOid;
+
+/// Generates valid object IDs, as strings, across both hash widths and in mixed case.
+pub fn oid_string() -> impl Strategy<Value = String> {
+    prop_oneof!["[0-9a-fA-F]{40}", "[0-9a-fA-F]{64}"]
+}
+
+/// Generates strings that are almost, but not quite, valid object IDs: a valid object ID with a
+/// single non-hex character, or a hex string that's one character too short or too long.
+pub fn almost_valid_oid_string() -> impl Strategy<Value = String> {
+    prop_oneof![
+        (oid_string(), any::<Index>(), "[g-zG-Z/ ._^~-]").prop_map(
+            |(mut oid, index, character)| {
+                let index = index.index(oid.len());
+                oid.replace_range(index..=index, &character);
+                oid
+            }
+        ),
+        prop_oneof![
+            "[0-9a-fA-F]{39}",
+            "[0-9a-fA-F]{41}",
+            "[0-9a-fA-F]{63}",
+            "[0-9a-fA-F]{65}",
+        ],
+    ]
+}
+
+/// Generates uniformly random object IDs across both hash widths.
+impl Arbitrary for GitOid {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        oid_string()
+            .prop_filter_map("invalid object ID", |oid| Self::from_str(&oid).ok())
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::{DefaultHasher, Hash, Hasher};
+    use std::str::FromStr;
+
+    use proptest::prelude::*;
+
+    use crate::GitOid;
+
+    use super::{almost_valid_oid_string, oid_string};
+
+    proptest! {
+        #[test]
+        fn roundtrip(oid in any::<GitOid>()) {
+            prop_assert_eq!(GitOid::from_str(oid.as_str()), Ok(oid));
+            prop_assert_eq!(oid.as_str().len(), oid.hash_kind().hex_len());
+        }
+
+        #[test]
+        fn hash_eq(first in any::<GitOid>(), second in any::<GitOid>()) {
+            let hash = |oid: &GitOid| {
+                let mut hasher = DefaultHasher::new();
+                oid.hash(&mut hasher);
+                hasher.finish()
+            };
+            prop_assert_eq!(first == second, first.as_str() == second.as_str());
+            prop_assert_eq!(first.cmp(&second).is_eq(), first == second);
+            if first == second {
+                prop_assert_eq!(hash(&first), hash(&second));
+            }
+
+            let upper = GitOid::from_str(&first.as_str().to_ascii_uppercase()).unwrap();
+            prop_assert_eq!(upper, first);
+            prop_assert_eq!(hash(&upper), hash(&first));
+        }
+
+        #[test]
+        fn almost_valid(oid in almost_valid_oid_string()) {
+            prop_assert!(GitOid::from_str(&oid).is_err());
+            prop_assert_eq!(GitOid::try_from(oid.as_bytes()), GitOid::from_str(&oid));
+        }
+
+        #[test]
+        fn try_from_bytes(oid in prop_oneof![oid_string(), almost_valid_oid_string(), ".*"]) {
+            prop_assert_eq!(GitOid::try_from(oid.as_bytes()), GitOid::from_str(&oid));
+        }
+
+        #[test]
+        fn try_from_arbitrary_bytes(bytes in proptest::collection::vec(any::<u8>(), 0..80)) {
+            // Arbitrary bytes, including invalid UTF-8, never panic, and agree with the string
+            // parser whenever they're valid UTF-8.
+            let oid = GitOid::try_from(bytes.as_slice());
+            if let Ok(string) = std::str::from_utf8(&bytes) {
+                prop_assert_eq!(oid, GitOid::from_str(string));
+            }
+        }
+    }
+}
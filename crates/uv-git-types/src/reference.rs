@@ -1,5 +1,21 @@
 use std::fmt::Display;
-use std::str;
+use std::str::{self, FromStr};
+
+use crate::GitOid;
+
+/// Whether a Git reference that was resolved previously (e.g., in an earlier run) needs to be
+/// resolved again against the remote before it's reused.
+///
+/// Commits are never revalidated, and branches are always revalidated: the policy only decides
+/// whether tags, which are conventionally immutable but can be force-moved, are trusted.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RefreshPolicy {
+    /// Reuse the commit a tag was previously resolved to.
+    TagsTrusted,
+    /// Resolve tags again, like branches, to pick up tags that have been moved.
+    #[default]
+    TagsRevalidated,
+}
 
 /// A reference to commit or commit-ish.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -9,8 +25,14 @@ pub enum GitReference {
     /// A specific tag.
     Tag(String),
     /// From a reference that's ambiguously a branch or tag.
+    ///
+    /// Resolved as a branch first, and then as a tag, e.g., if `v1.0` is both a branch and a tag,
+    /// the branch is used.
     BranchOrTag(String),
     /// From a reference that's ambiguously a commit, branch, or tag.
+    ///
+    /// Resolved as a branch first, then as a tag, and then as a (possibly abbreviated) commit,
+    /// e.g., a branch named `deadbeef` takes precedence over a commit with that prefix.
     BranchOrTagOrCommit(String),
     /// From a named reference, like `refs/pull/493/head`.
     NamedRef(String),
@@ -21,6 +43,10 @@ pub enum GitReference {
 impl GitReference {
     /// Creates a [`GitReference`] from an arbitrary revision string, which could represent a
     /// branch, tag, commit, or named ref.
+    ///
+    /// Revisions starting with `refs/` are named refs; revisions of at least seven hex characters,
+    /// including full commit SHAs, could be a commit, branch, or tag; and anything else is a
+    /// branch or tag.
     pub fn from_rev(rev: String) -> Self {
         if rev.starts_with("refs/") {
             Self::NamedRef(rev)
@@ -55,8 +81,39 @@ impl GitReference {
         }
     }
 
+    /// Returns `true` if the reference always resolves to the same commit, i.e., it's a full commit
+    /// SHA.
+    pub fn is_immutable(&self) -> bool {
+        match self {
+            Self::BranchOrTagOrCommit(rev) => GitOid::from_str(rev).is_ok(),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if a previous resolution of this reference must be checked against the
+    /// remote before it's reused, under the given [`RefreshPolicy`].
+    ///
+    /// References that are ambiguously a branch or tag (e.g., `rev = "v1.0"`) are treated as
+    /// branches.
+    pub fn requires_revalidation(&self, policy: RefreshPolicy) -> bool {
+        if self.is_immutable() {
+            return false;
+        }
+        match self {
+            Self::Tag(_) => policy == RefreshPolicy::TagsRevalidated,
+            Self::NamedRef(rev) if rev.starts_with("refs/tags/") => {
+                policy == RefreshPolicy::TagsRevalidated
+            }
+            Self::Branch(_)
+            | Self::BranchOrTag(_)
+            | Self::BranchOrTagOrCommit(_)
+            | Self::NamedRef(_)
+            | Self::DefaultBranch => true,
+        }
+    }
+
     /// Returns the kind of this reference.
-    pub fn kind_str(&self) -> &str {
+    pub fn kind_str(&self) -> &'static str {
         match self {
             Self::Branch(_) => "branch",
             Self::Tag(_) => "tag",
@@ -78,3 +135,84 @@ impl Display for GitReference {
 fn looks_like_commit_hash(rev: &str) -> bool {
     rev.len() >= 7 && rev.chars().all(|ch| ch.is_ascii_hexdigit())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{GitReference, RefreshPolicy};
+
+    #[test]
+    fn from_rev() {
+        let from_rev = |rev: &str| GitReference::from_rev(rev.to_string());
+
+        assert_eq!(
+            from_rev("4a23745badf5bf5ef7928f1e346e9986bd696d82"),
+            GitReference::BranchOrTagOrCommit(
+                "4a23745badf5bf5ef7928f1e346e9986bd696d82".to_string()
+            )
+        );
+        assert_eq!(
+            from_rev("4A23745"),
+            GitReference::BranchOrTagOrCommit("4A23745".to_string())
+        );
+        assert_eq!(
+            from_rev("refs/pull/1/head"),
+            GitReference::NamedRef("refs/pull/1/head".to_string())
+        );
+
+        // Short hex strings are more likely to be a branch (e.g., `cafe`) than a commit.
+        assert_eq!(
+            from_rev("cafe"),
+            GitReference::BranchOrTag("cafe".to_string())
+        );
+        assert_eq!(
+            from_rev("v1.0"),
+            GitReference::BranchOrTag("v1.0".to_string())
+        );
+        assert_eq!(
+            from_rev("feature/4a23745"),
+            GitReference::BranchOrTag("feature/4a23745".to_string())
+        );
+    }
+
+    #[test]
+    fn requires_revalidation() {
+        let commit = GitReference::BranchOrTagOrCommit(
+            "4a23745badf5bf5ef7928f1e346e9986bd696d82".to_string(),
+        );
+        assert!(commit.is_immutable());
+
+        // An abbreviated commit could also be a branch.
+        let short = GitReference::BranchOrTagOrCommit("4a23745".to_string());
+        assert!(!short.is_immutable());
+
+        let tag = GitReference::Tag("v1.0".to_string());
+        let named_tag = GitReference::NamedRef("refs/tags/v1.0".to_string());
+        assert!(!tag.is_immutable());
+
+        for (reference, trusted, revalidated) in [
+            (&commit, false, false),
+            (&short, true, true),
+            (&tag, false, true),
+            (&named_tag, false, true),
+            (&GitReference::Branch("main".to_string()), true, true),
+            (&GitReference::BranchOrTag("v1.0".to_string()), true, true),
+            (
+                &GitReference::NamedRef("refs/pull/1/head".to_string()),
+                true,
+                true,
+            ),
+            (&GitReference::DefaultBranch, true, true),
+        ] {
+            assert_eq!(
+                reference.requires_revalidation(RefreshPolicy::TagsTrusted),
+                trusted,
+                "{reference:?}"
+            );
+            assert_eq!(
+                reference.requires_revalidation(RefreshPolicy::TagsRevalidated),
+                revalidated,
+                "{reference:?}"
+            );
+        }
+    }
+}
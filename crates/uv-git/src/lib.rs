@@ -1,12 +1,28 @@
 pub use crate::credentials::{GIT_STORE, store_credentials_from_url};
-pub use crate::git::{GIT, GIT_LFS, GitError};
+pub use crate::git::{
+    FetchStrategy, GIT, GIT_LFS, GitError, GitResolveError, GitSubmodule, NotCachedError,
+};
+pub use crate::ls_remote::{
+    DefaultBranch, LsRemoteParseError, RemoteRef, find_default_branch, find_reference,
+    parse_ls_remote, resolve_default_branch, resolve_reference,
+};
+pub use crate::progress::{ChannelProgress, GitProgress, GitProgressEvent};
+pub use crate::reference_cache::ReferenceCache;
+pub use crate::requirement_url::{GitRequirementUrl, GitRequirementUrlError};
 pub use crate::resolver::{
     GitResolver, GitResolverError, RepositoryReference, ResolvedRepositoryReference,
 };
+pub use crate::revision::{ParsedRevision, Revision, RevisionParseError, extract_revision};
 pub use crate::source::{Fetch, GitSource, Reporter};
 
 mod credentials;
 mod git;
+mod ls_remote;
+mod progress;
 mod rate_limit;
+mod reference_cache;
+mod requirement_url;
 mod resolver;
+mod revision;
+mod scp_url;
 mod source;
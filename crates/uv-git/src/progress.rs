@@ -0,0 +1,288 @@
+//! Reporting the progress of Git fetches and checkouts.
+
+use std::io::Read;
+use std::process::{Output, Stdio};
+
+use anyhow::{Context, Result, anyhow};
+use cargo_util::{ProcessBuilder, ProcessError};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+
+use uv_redacted::DisplaySafeUrl;
+
+use crate::git::GitError;
+
+/// Receives progress updates from Git fetches and checkouts, e.g., to render a progress bar.
+///
+/// Every method defaults to a no-op. Returning an error from any method cancels the operation,
+/// which then fails with [`GitError::Cancelled`].
+pub trait GitProgress: Send + Sync {
+    /// Callback to invoke when a fetch starts negotiating with the remote.
+    fn on_negotiation_start(&self, _url: &DisplaySafeUrl) -> Result<()> {
+        Ok(())
+    }
+
+    /// Callback to invoke as objects are received, with the number of objects received so far,
+    /// the total number of objects, and the number of bytes received so far, if known.
+    fn on_objects_received(&self, _received: u64, _total: u64, _bytes: Option<u64>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Callback to invoke as the deltas among the received objects are resolved.
+    fn on_resolving_deltas(&self, _resolved: u64, _total: u64) -> Result<()> {
+        Ok(())
+    }
+
+    /// Callback to invoke once the files of a checkout have been written.
+    fn on_checkout_files(&self, _written: u64, _total: u64) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A progress update, as sent by a [`ChannelProgress`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GitProgressEvent {
+    /// See [`GitProgress::on_negotiation_start`].
+    NegotiationStarted { url: DisplaySafeUrl },
+    /// See [`GitProgress::on_objects_received`].
+    ObjectsReceived {
+        received: u64,
+        total: u64,
+        bytes: Option<u64>,
+    },
+    /// See [`GitProgress::on_resolving_deltas`].
+    ResolvingDeltas { resolved: u64, total: u64 },
+    /// See [`GitProgress::on_checkout_files`].
+    CheckoutFiles { written: u64, total: u64 },
+}
+
+/// A [`GitProgress`] that sends every update over a channel, e.g., to a task that renders
+/// progress bars.
+///
+/// If the receiver is dropped, the operation is cancelled.
+#[derive(Debug, Clone)]
+pub struct ChannelProgress(UnboundedSender<GitProgressEvent>);
+
+impl ChannelProgress {
+    /// Create a [`ChannelProgress`], along with the receiver for its updates.
+    pub fn new() -> (Self, UnboundedReceiver<GitProgressEvent>) {
+        let (sender, receiver) = unbounded_channel();
+        (Self(sender), receiver)
+    }
+
+    fn send(&self, event: GitProgressEvent) -> Result<()> {
+        self.0
+            .send(event)
+            .map_err(|_| anyhow!("the progress receiver was dropped"))
+    }
+}
+
+impl GitProgress for ChannelProgress {
+    fn on_negotiation_start(&self, url: &DisplaySafeUrl) -> Result<()> {
+        self.send(GitProgressEvent::NegotiationStarted { url: url.clone() })
+    }
+
+    fn on_objects_received(&self, received: u64, total: u64, bytes: Option<u64>) -> Result<()> {
+        self.send(GitProgressEvent::ObjectsReceived {
+            received,
+            total,
+            bytes,
+        })
+    }
+
+    fn on_resolving_deltas(&self, resolved: u64, total: u64) -> Result<()> {
+        self.send(GitProgressEvent::ResolvingDeltas { resolved, total })
+    }
+
+    fn on_checkout_files(&self, written: u64, total: u64) -> Result<()> {
+        self.send(GitProgressEvent::CheckoutFiles { written, total })
+    }
+}
+
+/// Invoke a [`GitProgress`] callback, marking any error as a cancellation.
+pub(crate) fn report(callback: impl FnOnce() -> Result<()>) -> Result<()> {
+    callback().map_err(|err| GitError::Cancelled(err).into())
+}
+
+/// Run a Git command that reports its progress on stderr (e.g., `git fetch --progress`),
+/// forwarding the progress to `progress` as it's printed.
+///
+/// As with [`ProcessBuilder::exec_with_output`], the output is captured and a non-zero exit
+/// status is an error. If a callback fails, the command is killed.
+pub(crate) fn exec_with_progress(
+    cmd: &ProcessBuilder,
+    progress: &dyn GitProgress,
+) -> Result<Output> {
+    let mut child = cmd
+        .build_command()
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| ProcessError::could_not_execute(cmd))?;
+    let mut stdout = child.stdout.take().context("missing stdout")?;
+    let mut stderr = child.stderr.take().context("missing stderr")?;
+
+    // Read stdout on another thread, so that neither pipe fills up and blocks the command.
+    let stdout = std::thread::spawn(move || {
+        let mut buffer = Vec::new();
+        stdout.read_to_end(&mut buffer).map(|_| buffer)
+    });
+
+    // Progress lines are terminated by a carriage return while they're being updated, and by a
+    // newline once they're done.
+    let mut output = Vec::new();
+    let mut start = 0;
+    let mut chunk = [0; 4096];
+    let result = loop {
+        let read = match stderr.read(&mut chunk) {
+            Ok(0) => break Ok(()),
+            Ok(read) => read,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(err) => break Err(err.into()),
+        };
+        output.extend_from_slice(&chunk[..read]);
+        let mut callback = Ok(());
+        while let Some(end) = output[start..]
+            .iter()
+            .position(|byte| matches!(byte, b'\r' | b'\n'))
+        {
+            let line = String::from_utf8_lossy(&output[start..start + end]).into_owned();
+            start += end + 1;
+            callback = report_line(&line, progress);
+            if callback.is_err() {
+                break;
+            }
+        }
+        if let Err(err) = callback {
+            break Err(err);
+        }
+    };
+
+    if let Err(err) = result {
+        // Stop the command, e.g., if the operation was cancelled.
+        let _ = child.kill();
+        let _ = child.wait();
+        let _ = stdout.join();
+        return Err(err);
+    }
+
+    let status = child
+        .wait()
+        .with_context(|| ProcessError::could_not_execute(cmd))?;
+    let stdout = stdout
+        .join()
+        .map_err(|_| anyhow!("failed to read the output of: {cmd}"))??;
+    let output = Output {
+        status,
+        stdout,
+        stderr: output,
+    };
+    if !output.status.success() {
+        return Err(ProcessError::new(
+            &format!("process didn't exit successfully: {cmd}"),
+            Some(output.status),
+            Some(&output),
+        )
+        .into());
+    }
+    Ok(output)
+}
+
+/// Forward a single line of progress output to `progress`, if it's one we recognize.
+fn report_line(line: &str, progress: &dyn GitProgress) -> Result<()> {
+    // Small fetches are unpacked into loose objects, rather than kept as a pack.
+    if let Some(rest) = line
+        .strip_prefix("Receiving objects:")
+        .or_else(|| line.strip_prefix("Unpacking objects:"))
+        && let Some((received, total)) = parse_counts(rest)
+    {
+        let bytes = rest
+            .split_once("), ")
+            .and_then(|(_, rest)| parse_bytes(rest));
+        report(|| progress.on_objects_received(received, total, bytes))
+    } else if let Some(rest) = line.strip_prefix("Resolving deltas:")
+        && let Some((resolved, total)) = parse_counts(rest)
+    {
+        report(|| progress.on_resolving_deltas(resolved, total))
+    } else {
+        Ok(())
+    }
+}
+
+/// Parse the counts in a progress line, e.g., `(40/100)` in `  40% (40/100), 1.00 KiB`.
+fn parse_counts(line: &str) -> Option<(u64, u64)> {
+    let (_, rest) = line.split_once('(')?;
+    let (counts, _) = rest.split_once(')')?;
+    let (done, total) = counts.split_once('/')?;
+    Some((done.trim().parse().ok()?, total.trim().parse().ok()?))
+}
+
+/// Parse the amount of data in a progress line, e.g., `1.50 KiB` in `1.50 KiB | 1.50 MiB/s`.
+fn parse_bytes(line: &str) -> Option<u64> {
+    let mut parts = line.split_whitespace();
+    let value = parts.next()?.parse::<f64>().ok()?;
+    let unit = match parts.next()?.trim_end_matches(',') {
+        "bytes" => 1u64,
+        "KiB" => 1 << 10,
+        "MiB" => 1 << 20,
+        "GiB" => 1 << 30,
+        _ => return None,
+    };
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    Some((value * unit as f64) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use anyhow::{Result, anyhow};
+
+    use super::{GitProgress, parse_bytes, parse_counts, report_line};
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<(u64, u64, Option<u64>)>>);
+
+    impl GitProgress for Recorder {
+        fn on_objects_received(&self, received: u64, total: u64, bytes: Option<u64>) -> Result<()> {
+            self.0.lock().unwrap().push((received, total, bytes));
+            Ok(())
+        }
+
+        fn on_resolving_deltas(&self, _resolved: u64, _total: u64) -> Result<()> {
+            Err(anyhow!("stop"))
+        }
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(parse_counts("  40% (40/100)"), Some((40, 100)));
+        assert_eq!(parse_counts(" 100% (5/5), done."), Some((5, 5)));
+        assert_eq!(parse_counts(" 5, done."), None);
+        assert_eq!(parse_bytes("1.50 KiB | 1.50 MiB/s, done."), Some(1536));
+        assert_eq!(parse_bytes("512 bytes | 512.00 KiB/s"), Some(512));
+        assert_eq!(parse_bytes("done."), None);
+
+        let recorder = Recorder::default();
+        for line in [
+            "remote: Enumerating objects: 5, done.",
+            "Receiving objects:  40% (2/5)",
+            "Receiving objects: 100% (5/5), 1.50 KiB | 1.50 MiB/s, done.",
+            "Unpacking objects: 100% (3/3), 200 bytes | 200.00 KiB/s, done.",
+        ] {
+            report_line(line, &recorder).unwrap();
+        }
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            [(2, 5, None), (5, 5, Some(1536)), (3, 3, Some(200))]
+        );
+
+        // Errors from callbacks are cancellations.
+        let err = report_line("Resolving deltas:  50% (1/2)", &recorder).unwrap_err();
+        assert_eq!(err.to_string(), "Git operation was cancelled");
+    }
+}
@@ -0,0 +1,464 @@
+//! A persistent cache of the commits that Git references were resolved to.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use uv_cache_key::RepositoryUrl;
+use uv_git_types::{GitOid, GitReference, GitUrl, RefreshPolicy};
+
+use crate::git::{GitError, GitResolveError, is_local};
+use crate::ls_remote::resolve_reference;
+
+/// The version of the on-disk format; files with any other version are ignored.
+const VERSION: u32 = 1;
+
+/// A persistent cache mapping a repository URL and a [`GitReference`] to the commit it was
+/// resolved to, and when.
+///
+/// Entries for full commit SHAs (and, if trusted, tags) are reused indefinitely; entries for
+/// references that require revalidation (e.g., branches) are reused until they're older than the
+/// maximum age, which defaults to zero (i.e., they're always revalidated).
+///
+/// The cache is best-effort: a missing, unreadable, or corrupt file is treated as empty.
+#[derive(Debug, Clone)]
+pub struct ReferenceCache {
+    /// The path to the cache file.
+    path: PathBuf,
+    /// The entries, keyed by canonical repository URL, then by reference.
+    entries: BTreeMap<String, BTreeMap<String, ReferenceCacheEntry>>,
+    /// The maximum age of an entry for a reference that requires revalidation.
+    max_age: Duration,
+}
+
+/// A cached resolution of a single reference.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ReferenceCacheEntry {
+    /// The commit the reference was resolved to.
+    sha: GitOid,
+    /// When the reference was resolved, in seconds since the Unix epoch.
+    timestamp: u64,
+}
+
+/// The on-disk representation of a [`ReferenceCache`].
+#[derive(Debug, Serialize, Deserialize)]
+struct ReferenceCacheFile {
+    version: u32,
+    repositories: BTreeMap<String, BTreeMap<String, ReferenceCacheEntry>>,
+}
+
+impl ReferenceCache {
+    /// Read the cache at the given path.
+    ///
+    /// Never fails: if the file doesn't exist, can't be read, or can't be parsed, the cache is
+    /// empty.
+    pub fn read(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let entries = read_entries(&path);
+        Self {
+            path,
+            entries,
+            max_age: Duration::ZERO,
+        }
+    }
+
+    /// Reuse entries for references that require revalidation (e.g., branches) until they're
+    /// older than `max_age`.
+    #[must_use]
+    pub fn with_max_age(self, max_age: Duration) -> Self {
+        Self { max_age, ..self }
+    }
+
+    /// The path to the cache file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Return the cached commit for the `reference` in the repository at `url`, if the cache has
+    /// one that's still fresh under the given [`RefreshPolicy`].
+    pub fn get(
+        &self,
+        url: &RepositoryUrl,
+        reference: &GitReference,
+        policy: RefreshPolicy,
+    ) -> Option<GitOid> {
+        let entry = self
+            .entries
+            .get(&url.to_string())?
+            .get(&reference_key(reference))?;
+        if reference.requires_revalidation(policy) {
+            let age = Duration::from_secs(now().saturating_sub(entry.timestamp));
+            if age >= self.max_age {
+                debug!(
+                    "Cached resolution of {} `{}` in `{url}` is stale",
+                    reference.kind_str(),
+                    reference.as_rev()
+                );
+                return None;
+            }
+        }
+        Some(entry.sha)
+    }
+
+    /// Record that the `reference` in the repository at `url` resolved to `sha`, as of now.
+    ///
+    /// The entry isn't persisted until [`ReferenceCache::write`] is called.
+    pub fn insert(&mut self, url: &RepositoryUrl, reference: &GitReference, sha: GitOid) {
+        self.entries.entry(url.to_string()).or_default().insert(
+            reference_key(reference),
+            ReferenceCacheEntry {
+                sha,
+                timestamp: now(),
+            },
+        );
+    }
+
+    /// Persist the cache, merging in any entries written by other processes since it was read.
+    ///
+    /// When both have an entry for the same reference, the most recent one wins. The file is
+    /// replaced atomically, so concurrent readers never see a partially written cache.
+    pub fn write(&self) -> io::Result<()> {
+        let mut repositories = read_entries(&self.path);
+        for (url, references) in &self.entries {
+            let merged = repositories.entry(url.clone()).or_default();
+            for (reference, entry) in references {
+                if merged
+                    .get(reference)
+                    .is_none_or(|existing| existing.timestamp <= entry.timestamp)
+                {
+                    merged.insert(reference.clone(), *entry);
+                }
+            }
+        }
+
+        let data = serde_json::to_vec(&ReferenceCacheFile {
+            version: VERSION,
+            repositories,
+        })
+        .map_err(io::Error::other)?;
+        if let Some(parent) = self.path.parent() {
+            fs_err::create_dir_all(parent)?;
+        }
+        uv_fs::write_atomic_sync(&self.path, data)
+    }
+
+    /// Resolve the reference of the given [`GitUrl`] to a commit, consulting the cache before
+    /// running `git ls-remote`.
+    ///
+    /// If `refresh` is set, the cache is bypassed (but still updated). Failing to persist the
+    /// cache isn't an error.
+    ///
+    /// When `offline`, references in remote repositories are resolved from the cache alone, even
+    /// if the entry is stale; a missing entry is a [`GitError::NotCached`] error.
+    pub fn resolve(
+        &mut self,
+        url: &GitUrl,
+        policy: RefreshPolicy,
+        refresh: bool,
+        disable_ssl: bool,
+        offline: bool,
+    ) -> Result<GitOid, GitResolveError> {
+        let repository = RepositoryUrl::new(url.repository());
+        if offline && !is_local(url.repository()) {
+            // Without network connectivity, a stale resolution is better than none.
+            let entry = self
+                .entries
+                .get(&repository.to_string())
+                .and_then(|references| references.get(&reference_key(url.reference())))
+                .ok_or_else(|| {
+                    anyhow::Error::from(GitError::not_cached(
+                        url.repository(),
+                        url.reference(),
+                        None,
+                    ))
+                })?;
+            return Ok(entry.sha);
+        }
+        if !refresh && let Some(sha) = self.get(&repository, url.reference(), policy) {
+            debug!(
+                "Using cached resolution of {} `{}` in `{}`: {sha}",
+                url.reference().kind_str(),
+                url.reference().as_rev(),
+                url.repository()
+            );
+            return Ok(sha);
+        }
+
+        let sha = resolve_reference(url, url.reference(), disable_ssl, offline)?;
+        self.insert(&repository, url.reference(), sha);
+        if let Err(err) = self.write() {
+            debug!(
+                "Failed to write Git reference cache to `{}`: {err}",
+                self.path.display()
+            );
+        }
+        Ok(sha)
+    }
+}
+
+/// Read the entries from the cache file at `path`, treating any failure as an empty cache.
+fn read_entries(path: &Path) -> BTreeMap<String, BTreeMap<String, ReferenceCacheEntry>> {
+    let data = match fs_err::read(path) {
+        Ok(data) => data,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return BTreeMap::new(),
+        Err(err) => {
+            debug!("Ignoring unreadable Git reference cache: {err}");
+            return BTreeMap::new();
+        }
+    };
+    match serde_json::from_slice::<ReferenceCacheFile>(&data) {
+        Ok(file) if file.version == VERSION => file.repositories,
+        Ok(file) => {
+            debug!(
+                "Ignoring Git reference cache at `{}` with unsupported version: {}",
+                path.display(),
+                file.version
+            );
+            BTreeMap::new()
+        }
+        Err(err) => {
+            debug!(
+                "Ignoring corrupt Git reference cache at `{}`: {err}",
+                path.display()
+            );
+            BTreeMap::new()
+        }
+    }
+}
+
+/// The key for a reference within a repository, e.g., `branch:main`.
+fn reference_key(reference: &GitReference) -> String {
+    format!("{}:{}", reference.kind_str(), reference.as_rev())
+}
+
+/// The current time, in seconds since the Unix epoch.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use anyhow::Result;
+    use cargo_util::ProcessBuilder;
+
+    use uv_cache_key::RepositoryUrl;
+    use uv_git_types::{GitLfs, GitOid, GitReference, GitUrl, RefreshPolicy};
+    use uv_redacted::DisplaySafeUrl;
+
+    use crate::GIT;
+    use crate::git::tests::fixture;
+
+    use super::ReferenceCache;
+
+    fn repository() -> RepositoryUrl {
+        RepositoryUrl::new(&DisplaySafeUrl::parse("https://github.com/astral-sh/uv.git").unwrap())
+    }
+
+    fn sha(byte: char) -> GitOid {
+        byte.to_string().repeat(40).parse().unwrap()
+    }
+
+    #[test]
+    fn freshness() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("git").join("references.json");
+
+        let commit = GitReference::BranchOrTagOrCommit(sha('a').to_string());
+        let tag = GitReference::Tag("v1.0".to_string());
+        let branch = GitReference::Branch("main".to_string());
+
+        let mut cache = ReferenceCache::read(&path);
+        cache.insert(&repository(), &commit, sha('a'));
+        cache.insert(&repository(), &tag, sha('b'));
+        cache.insert(&repository(), &branch, sha('c'));
+        cache.write()?;
+
+        // Commits are always fresh; tags are fresh if trusted; branches are always stale by default.
+        let cache = ReferenceCache::read(&path);
+        for policy in [RefreshPolicy::TagsTrusted, RefreshPolicy::TagsRevalidated] {
+            assert_eq!(cache.get(&repository(), &commit, policy), Some(sha('a')));
+            assert_eq!(cache.get(&repository(), &branch, policy), None);
+        }
+        assert_eq!(
+            cache.get(&repository(), &tag, RefreshPolicy::TagsTrusted),
+            Some(sha('b'))
+        );
+        assert_eq!(
+            cache.get(&repository(), &tag, RefreshPolicy::TagsRevalidated),
+            None
+        );
+
+        // With a maximum age, recently resolved branches are reused.
+        let cache = cache.with_max_age(Duration::from_secs(60 * 60));
+        assert_eq!(
+            cache.get(&repository(), &branch, RefreshPolicy::TagsRevalidated),
+            Some(sha('c'))
+        );
+
+        // URLs are canonicalized.
+        let alias = RepositoryUrl::new(&DisplaySafeUrl::parse("https://github.com/Astral-Sh/UV/")?);
+        assert_eq!(
+            cache.get(&alias, &commit, RefreshPolicy::TagsRevalidated),
+            Some(sha('a'))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn corrupt() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("references.json");
+        let commit = GitReference::BranchOrTagOrCommit(sha('a').to_string());
+
+        for contents in [
+            &b"{\"version\": 1, \"repositories\": {\"https://github.com/astral"[..],
+            b"\xff\xfe",
+            b"{\"version\": 2, \"repositories\": {}}",
+            b"{\"version\": 1, \"repositories\": {\"https://github.com/astral-sh/uv\": {\"branch:main\": {\"sha\": \"xyz\", \"timestamp\": 0}}}}",
+        ] {
+            fs_err::write(&path, contents)?;
+            let mut cache = ReferenceCache::read(&path);
+            assert_eq!(
+                cache.get(&repository(), &commit, RefreshPolicy::TagsTrusted),
+                None
+            );
+
+            // The corrupt file is replaced on the next write.
+            cache.insert(&repository(), &commit, sha('a'));
+            cache.write()?;
+            assert_eq!(
+                ReferenceCache::read(&path).get(&repository(), &commit, RefreshPolicy::TagsTrusted),
+                Some(sha('a'))
+            );
+        }
+
+        // A directory in place of the file is a miss, too.
+        let cache = ReferenceCache::read(temp_dir.path());
+        assert_eq!(
+            cache.get(&repository(), &commit, RefreshPolicy::TagsTrusted),
+            None
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn merge() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("references.json");
+        let first = GitReference::Tag("v1.0".to_string());
+        let second = GitReference::Tag("v2.0".to_string());
+
+        // Two processes read the cache, and each writes a different entry.
+        let mut one = ReferenceCache::read(&path);
+        let mut two = ReferenceCache::read(&path);
+        one.insert(&repository(), &first, sha('a'));
+        two.insert(&repository(), &second, sha('b'));
+        one.write()?;
+        two.write()?;
+
+        let cache = ReferenceCache::read(&path);
+        assert_eq!(
+            cache.get(&repository(), &first, RefreshPolicy::TagsTrusted),
+            Some(sha('a'))
+        );
+        assert_eq!(
+            cache.get(&repository(), &second, RefreshPolicy::TagsTrusted),
+            Some(sha('b'))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn resolve() -> Result<()> {
+        let remote_dir = tempfile::tempdir()?;
+        let commits = fixture(remote_dir.path(), 3)?;
+        let url = GitUrl::from_reference(
+            DisplaySafeUrl::from_file_path(remote_dir.path()).unwrap(),
+            GitReference::Branch("main".to_string()),
+            GitLfs::from(false),
+        )?;
+
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("references.json");
+        let mut cache = ReferenceCache::read(&path).with_max_age(Duration::from_secs(60 * 60));
+        assert_eq!(
+            cache.resolve(&url, RefreshPolicy::TagsRevalidated, false, false, true)?,
+            commits[0]
+        );
+
+        // Once cached, the remote isn't consulted, even by another process.
+        fs_err::remove_dir_all(remote_dir.path())?;
+        let mut cache = ReferenceCache::read(&path).with_max_age(Duration::from_secs(60 * 60));
+        assert_eq!(
+            cache.resolve(&url, RefreshPolicy::TagsRevalidated, false, false, true)?,
+            commits[0]
+        );
+
+        // Unless the caller asks for a refresh.
+        assert!(
+            cache
+                .resolve(&url, RefreshPolicy::TagsRevalidated, true, false, true)
+                .is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn default_branch() -> Result<()> {
+        let remote_dir = tempfile::tempdir()?;
+        let commits = fixture(remote_dir.path(), 3)?;
+        let url = GitUrl::from_reference(
+            DisplaySafeUrl::from_file_path(remote_dir.path()).unwrap(),
+            GitReference::DefaultBranch,
+            GitLfs::from(false),
+        )?;
+        let git = |args: &[&str]| -> Result<()> {
+            ProcessBuilder::new(GIT.as_ref()?)
+                .args(args)
+                .cwd(remote_dir.path())
+                .exec_with_output()?;
+            Ok(())
+        };
+
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("references.json");
+        let mut cache = ReferenceCache::read(&path);
+        assert_eq!(
+            cache.resolve(&url, RefreshPolicy::TagsTrusted, false, false, true)?,
+            commits[0]
+        );
+
+        // The default branch can change between runs, so it's always revalidated, even if tags
+        // are trusted.
+        git(&["branch", "next", commits[2].as_str()])?;
+        git(&["symbolic-ref", "HEAD", "refs/heads/next"])?;
+        let mut cache = ReferenceCache::read(&path);
+        assert_eq!(
+            cache.resolve(&url, RefreshPolicy::TagsTrusted, false, false, true)?,
+            commits[2]
+        );
+
+        // Within the maximum age, the cached resolution is used, as for any branch.
+        git(&["symbolic-ref", "HEAD", "refs/heads/main"])?;
+        let mut cache = ReferenceCache::read(&path).with_max_age(Duration::from_secs(60 * 60));
+        assert_eq!(
+            cache.resolve(&url, RefreshPolicy::TagsTrusted, false, false, true)?,
+            commits[2]
+        );
+
+        Ok(())
+    }
+}
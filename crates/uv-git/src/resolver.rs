@@ -143,11 +143,15 @@ impl GitResolver {
     }
 
     /// Fetch a remote Git repository.
+    ///
+    /// If `refresh` is set, references are resolved against the remote again, even if they were
+    /// resolved by a previous fetch (e.g., with `--refresh`).
     pub async fn fetch(
         &self,
         url: &GitUrl,
         disable_ssl: bool,
         offline: bool,
+        refresh: bool,
         cache: PathBuf,
         reporter: Option<Arc<dyn Reporter>>,
     ) -> Result<Fetch, GitResolverError> {
@@ -190,6 +194,8 @@ impl GitResolver {
             source
         };
 
+        let source = source.with_refresh(refresh);
+
         let fetch = tokio::task::spawn_blocking(move || source.fetch())
             .await?
             .map_err(GitResolverError::Git)?;
@@ -0,0 +1,303 @@
+//! Extracting the revision from a Git URL, as written in a requirement or a lockfile.
+
+use std::borrow::Cow;
+use std::str::FromStr;
+
+use percent_encoding::percent_decode_str;
+use thiserror::Error;
+
+use uv_git_types::{GitOid, GitReference, HashKind};
+
+use crate::scp_url::ScpUrl;
+
+/// A revision extracted from a Git URL with [`extract_revision`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedRevision {
+    /// The URL with the revision removed, but any query parameters and other fragment parameters
+    /// (e.g., `subdirectory`) retained.
+    pub url: String,
+    /// The revision, percent-decoded.
+    pub revision: Revision,
+}
+
+/// A revision in a Git URL, classified by its syntax alone.
+///
+/// The classification doesn't consult the repository, so a branch or tag named like a commit
+/// (e.g., `deadbeef`) is classified as an abbreviated commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Revision {
+    /// A full commit SHA.
+    Commit(GitOid),
+    /// An abbreviated commit SHA, i.e., a hex string that's too short to be a full commit SHA.
+    AbbreviatedCommit(String),
+    /// A named reference, like a branch, a tag, or `refs/pull/1/head`.
+    Named(String),
+}
+
+impl Revision {
+    /// Classify the given revision.
+    fn from_rev(rev: String) -> Self {
+        if let Ok(oid) = GitOid::from_str(&rev) {
+            return Self::Commit(oid);
+        }
+        // Agree with `GitReference` on which revisions could be commits.
+        if rev.len() < HashKind::Sha256.hex_len()
+            && matches!(
+                GitReference::from_rev(rev.clone()),
+                GitReference::BranchOrTagOrCommit(_)
+            )
+        {
+            Self::AbbreviatedCommit(rev)
+        } else {
+            Self::Named(rev)
+        }
+    }
+
+    /// Return the revision as written, i.e., as a full or abbreviated commit SHA, or a
+    /// reference name.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Commit(oid) => oid.as_str(),
+            Self::AbbreviatedCommit(rev) | Self::Named(rev) => rev,
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RevisionParseError {
+    #[error("Git URL has an empty revision after `@`: `{0}`")]
+    EmptyRevision(String),
+    #[error("Git URL has multiple revisions (`{first}` and `{second}`): `{url}`")]
+    MultipleRevisions {
+        url: String,
+        first: String,
+        second: String,
+    },
+    #[error("Git URL revision `{0}` is not valid UTF-8 after percent-decoding")]
+    InvalidEncoding(String),
+}
+
+/// Extract the revision from a Git URL, e.g., `v1.0` from
+/// `git+https://github.com/pallets/flask.git@v1.0#subdirectory=src`.
+///
+/// The revision can follow an `@` at the end of the URL path, or be given as a bare fragment
+/// parameter (e.g., `#4a23745badf5bf5ef7928f1e346e9986bd696d82`), before or after a
+/// `subdirectory` or `egg` parameter. Returns `None` if the URL doesn't include a revision, and an
+/// error if it includes more than one.
+pub fn extract_revision(url: &str) -> Result<Option<ParsedRevision>, RevisionParseError> {
+    let (rest, fragment) = match url.split_once('#') {
+        Some((rest, fragment)) => (rest, Some(fragment)),
+        None => (url, None),
+    };
+    let (base, query) = match rest.split_once('?') {
+        Some((base, query)) => (base, Some(query)),
+        None => (rest, None),
+    };
+
+    // Look for an `@` in the path, skipping over any credentials in the authority (e.g.,
+    // `git+ssh://git@github.com/...`), or the user in an scp-like URL (e.g.,
+    // `git@github.com:...`).
+    let path_start = if let Some(scheme_end) = base.find("://") {
+        let authority_start = scheme_end + "://".len();
+        base[authority_start..]
+            .find('/')
+            .map_or(base.len(), |offset| authority_start + offset)
+    } else if let Some(scp) = ScpUrl::parse(base) {
+        base.len() - scp.path.len()
+    } else {
+        0
+    };
+    let (base, path_revision) = match base[path_start..].rsplit_once('@') {
+        Some((path, revision)) => {
+            if revision.is_empty() {
+                return Err(RevisionParseError::EmptyRevision(url.to_string()));
+            }
+            (&base[..path_start + path.len()], Some(revision))
+        }
+        None => (base, None),
+    };
+
+    // Any fragment parameter without a key is a revision; retain the others.
+    let mut parameters = Vec::new();
+    let mut fragment_revision = None;
+    for parameter in fragment
+        .into_iter()
+        .flat_map(|fragment| fragment.split('&'))
+    {
+        if parameter.is_empty() {
+            continue;
+        }
+        if parameter.contains('=') {
+            parameters.push(parameter);
+        } else if let Some(first) = fragment_revision.replace(parameter) {
+            return Err(RevisionParseError::MultipleRevisions {
+                url: url.to_string(),
+                first: first.to_string(),
+                second: parameter.to_string(),
+            });
+        }
+    }
+
+    let revision = match (path_revision, fragment_revision) {
+        (None, None) => return Ok(None),
+        (Some(revision), None) | (None, Some(revision)) => revision,
+        (Some(first), Some(second)) => {
+            return Err(RevisionParseError::MultipleRevisions {
+                url: url.to_string(),
+                first: first.to_string(),
+                second: second.to_string(),
+            });
+        }
+    };
+    let revision = percent_decode_str(revision)
+        .decode_utf8()
+        .map_err(|_| RevisionParseError::InvalidEncoding(revision.to_string()))
+        .map(Cow::into_owned)?;
+
+    let mut url = base.to_string();
+    if let Some(query) = query {
+        url.push('?');
+        url.push_str(query);
+    }
+    if !parameters.is_empty() {
+        url.push('#');
+        url.push_str(&parameters.join("&"));
+    }
+
+    Ok(Some(ParsedRevision {
+        url,
+        revision: Revision::from_rev(revision),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use uv_git_types::GitOid;
+
+    use super::{ParsedRevision, Revision, RevisionParseError, extract_revision};
+
+    const SHA: &str = "4a23745badf5bf5ef7928f1e346e9986bd696d82";
+
+    fn extract(url: &str) -> Option<(String, Revision)> {
+        extract_revision(url)
+            .unwrap()
+            .map(|ParsedRevision { url, revision }| (url, revision))
+    }
+
+    #[test]
+    fn path() {
+        assert_eq!(
+            extract("git+https://github.com/pallets/flask.git@v1.0"),
+            Some((
+                "git+https://github.com/pallets/flask.git".to_string(),
+                Revision::Named("v1.0".to_string())
+            ))
+        );
+        assert_eq!(
+            extract(&format!("git+https://github.com/pallets/flask.git@{SHA}")),
+            Some((
+                "git+https://github.com/pallets/flask.git".to_string(),
+                Revision::Commit(GitOid::from_str(SHA).unwrap())
+            ))
+        );
+        assert_eq!(
+            extract("git+https://github.com/pallets/flask.git@4a23745"),
+            Some((
+                "git+https://github.com/pallets/flask.git".to_string(),
+                Revision::AbbreviatedCommit("4a23745".to_string())
+            ))
+        );
+
+        // Credentials aren't mistaken for a revision.
+        assert_eq!(
+            extract("git+ssh://git@github.com/pallets/flask.git@refs/pull/1/head"),
+            Some((
+                "git+ssh://git@github.com/pallets/flask.git".to_string(),
+                Revision::Named("refs/pull/1/head".to_string())
+            ))
+        );
+        assert_eq!(extract("git+ssh://git@github.com/pallets/flask.git"), None);
+        assert_eq!(
+            extract("git@github.com:pallets/flask.git@v1.0"),
+            Some((
+                "git@github.com:pallets/flask.git".to_string(),
+                Revision::Named("v1.0".to_string())
+            ))
+        );
+        assert_eq!(extract("git+git@github.com:pallets/flask.git"), None);
+        assert_eq!(extract("git+https://github.com/pallets/flask.git"), None);
+    }
+
+    #[test]
+    fn fragment() {
+        assert_eq!(
+            extract(&format!(
+                "git+https://github.com/pallets/flask.git?rev=main#{SHA}"
+            )),
+            Some((
+                "git+https://github.com/pallets/flask.git?rev=main".to_string(),
+                Revision::Commit(GitOid::from_str(SHA).unwrap())
+            ))
+        );
+
+        // The subdirectory can appear before or after the revision, and is retained.
+        for url in [
+            "git+https://github.com/pallets/flask.git@v1.0#subdirectory=src",
+            "git+https://github.com/pallets/flask.git#v1.0&subdirectory=src",
+            "git+https://github.com/pallets/flask.git#subdirectory=src&v1.0",
+        ] {
+            assert_eq!(
+                extract(url),
+                Some((
+                    "git+https://github.com/pallets/flask.git#subdirectory=src".to_string(),
+                    Revision::Named("v1.0".to_string())
+                )),
+                "{url}"
+            );
+        }
+        assert_eq!(
+            extract("git+https://github.com/pallets/flask.git#egg=flask&subdirectory=src"),
+            None
+        );
+    }
+
+    #[test]
+    fn percent_encoded() {
+        assert_eq!(
+            extract("git+https://github.com/pallets/flask.git#feature%2Fsha256&subdirectory=src"),
+            Some((
+                "git+https://github.com/pallets/flask.git#subdirectory=src".to_string(),
+                Revision::Named("feature/sha256".to_string())
+            ))
+        );
+        assert_eq!(
+            extract("git+https://github.com/pallets/flask.git@caf%C3%A9"),
+            Some((
+                "git+https://github.com/pallets/flask.git".to_string(),
+                Revision::Named("café".to_string())
+            ))
+        );
+        assert_eq!(
+            extract_revision("git+https://github.com/pallets/flask.git@%FF"),
+            Err(RevisionParseError::InvalidEncoding("%FF".to_string()))
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            extract_revision("git+https://github.com/pallets/flask.git@"),
+            Err(RevisionParseError::EmptyRevision(
+                "git+https://github.com/pallets/flask.git@".to_string()
+            ))
+        );
+        assert_eq!(
+            extract_revision("git+https://github.com/pallets/flask.git@v1.0#v2.0")
+                .unwrap_err()
+                .to_string(),
+            "Git URL has multiple revisions (`v1.0` and `v2.0`): `git+https://github.com/pallets/flask.git@v1.0#v2.0`"
+        );
+    }
+}
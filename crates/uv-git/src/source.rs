@@ -10,11 +10,17 @@ use anyhow::Result;
 use tracing::{debug, instrument};
 
 use uv_cache_key::{RepositoryUrl, cache_digest};
-use uv_git_types::{GitOid, GitReference, GitUrl};
+use uv_git_types::{GitOid, GitReference, GitUrl, RefreshPolicy};
 use uv_redacted::DisplaySafeUrl;
 
 use crate::GIT_STORE;
-use crate::git::{GitDatabase, GitRemote};
+use crate::git::{
+    FetchStrategy, GitDatabase, GitError, GitRemote, GitResolveError, GitSubmodule, Submodules,
+    is_cancelled, is_local,
+};
+use crate::ls_remote::resolve_reference;
+use crate::progress::GitProgress;
+use crate::reference_cache::ReferenceCache;
 
 /// A remote Git source that can be checked out locally.
 pub struct GitSource {
@@ -24,10 +30,21 @@ pub struct GitSource {
     disable_ssl: bool,
     /// Whether to operate without network connectivity.
     offline: bool,
+    /// Whether to revalidate references that were resolved by a previous fetch.
+    refresh_policy: RefreshPolicy,
+    /// Whether to revalidate references that were resolved by a previous fetch, regardless of
+    /// the [`RefreshPolicy`].
+    refresh: bool,
+    /// Whether to check out submodules, unless set for the repository specifically.
+    submodules: bool,
+    /// Whether to verify that an existing checkout matches the revision before reusing it.
+    paranoid: bool,
     /// The path to the Git source database.
     cache: PathBuf,
     /// The reporter to use for this source.
     reporter: Option<Arc<dyn Reporter>>,
+    /// The receiver of progress updates for fetches and checkouts, if any.
+    progress: Option<Arc<dyn GitProgress>>,
 }
 
 impl GitSource {
@@ -37,8 +54,13 @@ impl GitSource {
             git,
             disable_ssl: false,
             offline,
+            refresh_policy: RefreshPolicy::default(),
+            refresh: false,
+            submodules: true,
+            paranoid: false,
             cache: cache.into(),
             reporter: None,
+            progress: None,
         }
     }
 
@@ -51,6 +73,39 @@ impl GitSource {
         }
     }
 
+    /// Verify that an existing checkout still matches the revision (i.e., that its `HEAD` is the
+    /// expected commit, and its working tree is unmodified) before reusing it.
+    #[must_use]
+    pub fn paranoid(self) -> Self {
+        Self {
+            paranoid: true,
+            ..self
+        }
+    }
+
+    /// Set the [`RefreshPolicy`] to use for references that were resolved by a previous fetch.
+    #[must_use]
+    pub fn with_refresh_policy(self, refresh_policy: RefreshPolicy) -> Self {
+        Self {
+            refresh_policy,
+            ..self
+        }
+    }
+
+    /// Set whether to resolve references against the remote again, even if they were resolved by a
+    /// previous fetch (e.g., with `--refresh`).
+    #[must_use]
+    pub fn with_refresh(self, refresh: bool) -> Self {
+        Self { refresh, ..self }
+    }
+
+    /// Set whether to check out submodules (the default), for repositories that don't set it
+    /// themselves (see [`GitUrl::with_submodules`]).
+    #[must_use]
+    pub fn with_submodules(self, submodules: bool) -> Self {
+        Self { submodules, ..self }
+    }
+
     /// Set the [`Reporter`] to use for the [`GitSource`].
     #[must_use]
     pub fn with_reporter(self, reporter: Arc<dyn Reporter>) -> Self {
@@ -60,6 +115,15 @@ impl GitSource {
         }
     }
 
+    /// Set the [`GitProgress`] to report the progress of fetches and checkouts to.
+    #[must_use]
+    pub fn with_progress(self, progress: Arc<dyn GitProgress>) -> Self {
+        Self {
+            progress: Some(progress),
+            ..self
+        }
+    }
+
     /// Fetch the underlying Git repository at the given revision.
     #[instrument(skip(self), fields(repository = %self.git.repository(), rev = ?self.git.precise()))]
     pub fn fetch(self) -> Result<Fetch> {
@@ -81,70 +145,230 @@ impl GitSource {
 
         // Fetch the commit, if we don't already have it. Wrapping this section in a closure makes
         // it easier to short-circuit this in the cases where we do have the commit.
-        let (db, actual_rev, maybe_task) = || -> Result<(GitDatabase, GitOid, Option<usize>)> {
-            let git_remote = GitRemote::new(&remote);
-            let maybe_db = git_remote.db_at(&db_path).ok();
-
-            // If we have a locked revision, and we have a pre-existing database which has that
-            // revision, then no update needs to happen.
-            // When requested, we also check if LFS artifacts have been fetched and validated.
-            if let (Some(rev), Some(db)) = (self.git.precise(), &maybe_db) {
-                if db.contains(rev) && (!lfs_requested || db.contains_lfs_artifacts(rev)) {
-                    debug!("Using existing Git source `{}`", self.git.repository());
+        let (db, actual_rev, strategy, maybe_task) =
+            || -> Result<(GitDatabase, GitOid, FetchStrategy, Option<usize>)> {
+                let git_remote = GitRemote::new(&remote);
+                let mut maybe_db = git_remote.db_at(&db_path).ok();
+
+                // The commits that references were resolved to by previous fetches, e.g., by other
+                // processes.
+                let mut references = ReferenceCache::read(self.cache.join("references.json"));
+                let url = GitUrl::from_fields(
+                    remote.clone().into_owned(),
+                    self.git.reference().clone(),
+                    None,
+                    self.git.lfs(),
+                )?;
+
+                // If we have a locked revision, and we have a pre-existing database which has that
+                // revision, then no update needs to happen.
+                // When requested, we also check if LFS artifacts have been fetched and validated.
+                if let (Some(rev), Some(db)) = (self.git.precise(), &maybe_db) {
+                    if db.contains(rev) && (!lfs_requested || db.contains_lfs_artifacts(rev)) {
+                        debug!("Using existing Git source `{}`", self.git.repository());
+                        return Ok((
+                            maybe_db
+                                .unwrap()
+                                .with_lfs_ready(lfs_requested.then_some(true)),
+                            rev,
+                            FetchStrategy::Local,
+                            None,
+                        ));
+                    }
+                }
+
+                // Without network connectivity, only use what's already in the database (local
+                // repositories can still be fetched from, below).
+                if self.offline && !is_local(&remote) {
+                    let rev = maybe_db.as_ref().and_then(|db| match self.git.precise() {
+                        Some(rev) => db.contains(rev).then_some(rev),
+                        None => is_listed(self.git.reference())
+                            .then(|| {
+                                references
+                                    .resolve(
+                                        &url,
+                                        self.refresh_policy,
+                                        false,
+                                        self.disable_ssl,
+                                        self.offline,
+                                    )
+                                    .ok()
+                            })
+                            .flatten()
+                            .filter(|rev| db.contains(*rev))
+                            .or_else(|| db.resolve(self.git.reference()).ok()),
+                    });
+                    let (Some(db), Some(rev)) = (maybe_db, rev) else {
+                        return Err(GitError::not_cached(
+                            self.git.repository(),
+                            self.git.reference(),
+                            self.git.precise(),
+                        )
+                        .into());
+                    };
+                    if lfs_requested && !db.contains_lfs_artifacts(rev) {
+                        return Err(GitError::not_cached(
+                            self.git.repository(),
+                            self.git.reference(),
+                            Some(rev),
+                        )
+                        .into());
+                    }
+                    debug!(
+                        "Using cached Git source `{}` at {rev}, since network connectivity is disabled",
+                        self.git.repository()
+                    );
+                    return Ok((
+                        db.with_lfs_ready(lfs_requested.then_some(true)),
+                        rev,
+                        FetchStrategy::Local,
+                        None,
+                    ));
+                }
+
+                // If the revision isn't locked, but it's an exact commit hash (or, depending on the
+                // refresh policy, a tag), and we do have a pre-existing database, then check whether
+                // the database can resolve it. If so, treat it like it's locked.
+                // When requested, we also check if LFS artifacts have been fetched and validated.
+                if let Some(db) = &maybe_db
+                    && !self.refresh
+                    && !self
+                        .git
+                        .reference()
+                        .requires_revalidation(self.refresh_policy)
+                    && let Ok(oid) = db.resolve(self.git.reference())
+                    && (!lfs_requested || db.contains_lfs_artifacts(oid))
+                {
+                    debug!(
+                        "Using existing Git source `{}` at {} `{}`",
+                        self.git.repository(),
+                        self.git.reference().kind_str(),
+                        self.git.reference().as_rev()
+                    );
                     return Ok((
                         maybe_db
                             .unwrap()
                             .with_lfs_ready(lfs_requested.then_some(true)),
-                        rev,
+                        oid,
+                        FetchStrategy::Local,
                         None,
                     ));
                 }
-            }
 
-            // If the revision isn't locked, but it looks like it might be an exact commit hash,
-            // and we do have a pre-existing database, then check whether it is, in fact, a commit
-            // hash. If so, treat it like it's locked.
-            // When requested, we also check if LFS artifacts have been fetched and validated.
-            if let Some(db) = &maybe_db {
-                if let GitReference::BranchOrTagOrCommit(maybe_commit) = self.git.reference() {
-                    if let Ok(oid) = maybe_commit.parse::<GitOid>() {
-                        if db.contains(oid) && (!lfs_requested || db.contains_lfs_artifacts(oid)) {
-                            // This reference is an exact commit. Treat it like it's locked.
-                            debug!("Using existing Git source `{}`", self.git.repository());
-                            return Ok((
-                                maybe_db
-                                    .unwrap()
-                                    .with_lfs_ready(lfs_requested.then_some(true)),
-                                oid,
-                                None,
-                            ));
-                        }
+                // If a previous fetch (e.g., by another process) resolved the reference to a commit
+                // that's still fresh, and the database has that commit, then no update needs to
+                // happen either.
+                if self.git.precise().is_none()
+                    && !self.refresh
+                    && is_listed(self.git.reference())
+                    && let Some(oid) =
+                        references.get(&canonical, self.git.reference(), self.refresh_policy)
+                    && let Some(db) = maybe_db.take_if(|db| {
+                        db.contains(oid) && (!lfs_requested || db.contains_lfs_artifacts(oid))
+                    })
+                {
+                    record(&db, self.git.reference(), oid);
+                    debug!(
+                        "Using existing Git source `{}` at {} `{}` ({oid})",
+                        self.git.repository(),
+                        self.git.reference().kind_str(),
+                        self.git.reference().as_rev()
+                    );
+                    return Ok((
+                        db.with_lfs_ready(lfs_requested.then_some(true)),
+                        oid,
+                        FetchStrategy::Local,
+                        None,
+                    ));
+                }
+
+                // ... otherwise, we use this state to update the Git database. Note that we still check
+                // for being offline here, for example in the situation that we have a locked revision
+                // but the database doesn't have it.
+                debug!("Updating Git source `{}`", self.git.repository());
+
+                // Report the checkout operation to the reporter.
+                let task = self.reporter.as_ref().map(|reporter| {
+                    reporter.on_checkout_start(git_remote.url(), self.git.reference().as_rev())
+                });
+
+                let result = git_remote.checkout(
+                    &db_path,
+                    maybe_db,
+                    self.git.reference(),
+                    self.git.precise(),
+                    self.disable_ssl,
+                    self.offline,
+                    lfs_requested,
+                    self.progress.as_deref(),
+                );
+                let (db, actual_rev, strategy) = match result {
+                    Ok(result) => result,
+                    // If a branch or tag can't be fetched, list the references on the remote to
+                    // find out why: the reference may be missing (in which case similar
+                    // references are reported), or it may be a version tag that's named with (or
+                    // without) a `v` prefix on the remote, in which case that tag is fetched
+                    // instead.
+                    Err(err)
+                        if self.git.precise().is_none()
+                            && is_listed(self.git.reference())
+                            && !is_cancelled(&err) =>
+                    {
+                        let oid = match resolve_reference(
+                            &url,
+                            self.git.reference(),
+                            self.disable_ssl,
+                            self.offline,
+                        ) {
+                            Ok(oid) => oid,
+                            Err(
+                                resolve_err @ (GitResolveError::ReferenceNotFound { .. }
+                                | GitResolveError::AmbiguousVersionTag { .. }),
+                            ) => return Err(err.context(resolve_err)),
+                            Err(resolve_err) => {
+                                debug!(
+                                    "Failed to list the references of `{}`: {resolve_err}",
+                                    self.git.repository()
+                                );
+                                return Err(err);
+                            }
+                        };
+                        debug!(
+                            "Fetching {} `{}` of `{}` at {oid}, as listed by the remote",
+                            self.git.reference().kind_str(),
+                            self.git.reference().as_rev(),
+                            self.git.repository()
+                        );
+                        let (db, actual_rev, strategy) = git_remote.checkout(
+                            &db_path,
+                            git_remote.db_at(&db_path).ok(),
+                            self.git.reference(),
+                            Some(oid),
+                            self.disable_ssl,
+                            self.offline,
+                            lfs_requested,
+                            self.progress.as_deref(),
+                        )?;
+                        record(&db, self.git.reference(), actual_rev);
+                        (db, actual_rev, strategy)
+                    }
+                    Err(err) => return Err(err),
+                };
+
+                // Remember the commit the reference was resolved to, for later fetches (e.g., by
+                // other processes, or without network connectivity).
+                if self.git.precise().is_none() && is_listed(self.git.reference()) {
+                    references.insert(&canonical, self.git.reference(), actual_rev);
+                    if let Err(err) = references.write() {
+                        debug!(
+                            "Failed to write Git reference cache to `{}`: {err}",
+                            references.path().display()
+                        );
                     }
                 }
-            }
 
-            // ... otherwise, we use this state to update the Git database. Note that we still check
-            // for being offline here, for example in the situation that we have a locked revision
-            // but the database doesn't have it.
-            debug!("Updating Git source `{}`", self.git.repository());
-
-            // Report the checkout operation to the reporter.
-            let task = self.reporter.as_ref().map(|reporter| {
-                reporter.on_checkout_start(git_remote.url(), self.git.reference().as_rev())
-            });
-
-            let (db, actual_rev) = git_remote.checkout(
-                &db_path,
-                maybe_db,
-                self.git.reference(),
-                self.git.precise(),
-                self.disable_ssl,
-                self.offline,
-                lfs_requested,
-            )?;
-
-            Ok((db, actual_rev, task))
-        }()?;
+                Ok((db, actual_rev, strategy, task))
+            }()?;
 
         // Don’t use the full hash, in order to contribute less to reaching the
         // path length limit on Windows.
@@ -159,6 +383,13 @@ impl GitSource {
         } else {
             ident
         };
+        // Similarly, keep checkouts without submodules apart from those with them.
+        let submodules = self.git.submodules().unwrap_or(self.submodules);
+        let ident = if submodules {
+            ident
+        } else {
+            cache_digest(&(ident, "no-submodules"))
+        };
         let checkout_path = self
             .cache
             .join("checkouts")
@@ -168,7 +399,22 @@ impl GitSource {
         // Check out `actual_rev` from the database to a scoped location on the
         // filesystem. This will use hard links and such to ideally make the
         // checkout operation here pretty fast.
-        let checkout = db.copy_to(actual_rev, &checkout_path)?;
+        let checkout = db.copy_to(
+            actual_rev,
+            &checkout_path,
+            submodules.then_some(Submodules {
+                remote: &remote,
+                disable_ssl: self.disable_ssl,
+                offline: self.offline,
+            }),
+            self.paranoid,
+            self.progress.as_deref(),
+        )?;
+        let submodules = if submodules {
+            checkout.submodules()?
+        } else {
+            Vec::new()
+        };
 
         // Report the checkout operation to the reporter.
         if let Some(task) = maybe_task {
@@ -181,10 +427,39 @@ impl GitSource {
             git: self.git.with_precise(actual_rev),
             path: checkout_path,
             lfs_ready: checkout.lfs_ready().unwrap_or(false),
+            strategy,
+            submodules,
         })
     }
 }
 
+/// Returns `true` if the reference can be resolved by listing the references on the remote (i.e.,
+/// with `git ls-remote`), e.g., to explain why fetching it failed.
+///
+/// Abbreviated commits aren't listed by the remote, and named refs (e.g., `refs/pull/1/head`) may
+/// be hidden by it, so neither is resolved that way.
+fn is_listed(reference: &GitReference) -> bool {
+    match reference {
+        GitReference::Branch(_)
+        | GitReference::Tag(_)
+        | GitReference::BranchOrTag(_)
+        | GitReference::DefaultBranch => true,
+        GitReference::BranchOrTagOrCommit(_) | GitReference::NamedRef(_) => false,
+    }
+}
+
+/// Record the commit that the reference was resolved to in the database, e.g., for resolving it
+/// while offline. Failing to do so isn't an error, since the commit was fetched regardless.
+fn record(db: &GitDatabase, reference: &GitReference, oid: GitOid) {
+    if let Err(err) = db.record(reference, oid) {
+        debug!(
+            "Failed to record {} `{}` at {oid} in the Git database: {err}",
+            reference.kind_str(),
+            reference.as_rev()
+        );
+    }
+}
+
 pub struct Fetch {
     /// The [`GitUrl`] reference that was fetched.
     git: GitUrl,
@@ -192,6 +467,10 @@ pub struct Fetch {
     path: PathBuf,
     /// Git LFS artifacts have been initialized (if requested).
     lfs_ready: bool,
+    /// How the revision was made available in the Git database.
+    strategy: FetchStrategy,
+    /// The submodules that were checked out, if any.
+    submodules: Vec<GitSubmodule>,
 }
 
 impl Fetch {
@@ -207,6 +486,16 @@ impl Fetch {
         &self.lfs_ready
     }
 
+    /// How the revision was made available, e.g., whether it was already present locally.
+    pub fn strategy(&self) -> FetchStrategy {
+        self.strategy
+    }
+
+    /// The submodules that were checked out, recursively, and their commits.
+    pub fn submodules(&self) -> &[GitSubmodule] {
+        &self.submodules
+    }
+
     pub fn into_git(self) -> GitUrl {
         self.git
     }
@@ -223,3 +512,592 @@ pub trait Reporter: Send + Sync {
     /// Callback to invoke when a repository checkout completes.
     fn on_checkout_complete(&self, url: &DisplaySafeUrl, rev: &str, index: usize);
 }
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use cargo_util::ProcessBuilder;
+
+    use std::io;
+    use std::net::TcpListener;
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+
+    use uv_cache_key::{RepositoryUrl, cache_digest};
+    use uv_git_types::{GitLfs, GitReference, GitUrl, RefreshPolicy};
+    use uv_redacted::DisplaySafeUrl;
+
+    use crate::git::tests::fixture;
+    use crate::git::{FetchStrategy, GitError, GitRepository, GitResolveError};
+    use crate::{
+        ChannelProgress, GIT, GitProgress, GitProgressEvent, ReferenceCache, resolve_reference,
+    };
+
+    use super::GitSource;
+
+    /// Run `git` in `path`, with a fixed identity.
+    fn git(path: &Path, args: &[&str]) -> Result<()> {
+        ProcessBuilder::new(GIT.as_ref()?)
+            .arg("-c")
+            .arg("user.name=uv")
+            .arg("-c")
+            .arg("user.email=uv@example.com")
+            .args(args)
+            .cwd(path)
+            .exec_with_output()?;
+        Ok(())
+    }
+
+    #[test]
+    fn moved_tag() -> Result<()> {
+        let remote_dir = tempfile::tempdir()?;
+        let commits = fixture(remote_dir.path(), 3)?;
+        let tag = |commit: &str| -> Result<()> {
+            ProcessBuilder::new(GIT.as_ref()?)
+                .arg("tag")
+                .arg("--force")
+                .arg("v1.0")
+                .arg(commit)
+                .cwd(remote_dir.path())
+                .exec_with_output()?;
+            Ok(())
+        };
+        tag(commits[2].as_str())?;
+
+        let cache = tempfile::tempdir()?;
+        let url = GitUrl::from_reference(
+            DisplaySafeUrl::from_file_path(remote_dir.path()).unwrap(),
+            GitReference::Tag("v1.0".to_string()),
+            GitLfs::from(false),
+        )?;
+        let fetch = |policy: RefreshPolicy| {
+            GitSource::new(url.clone(), cache.path(), true)
+                .with_refresh_policy(policy)
+                .fetch()
+        };
+
+        let first = fetch(RefreshPolicy::TagsRevalidated)?;
+        assert_eq!(first.git().precise(), Some(commits[2]));
+        assert_eq!(first.strategy(), FetchStrategy::Reference);
+
+        // Force-move the tag on the remote.
+        tag(commits[0].as_str())?;
+
+        // A trusted tag is reused without contacting the remote.
+        let trusted = fetch(RefreshPolicy::TagsTrusted)?;
+        assert_eq!(trusted.git().precise(), Some(commits[2]));
+        assert_eq!(trusted.strategy(), FetchStrategy::Local);
+
+        // A revalidated tag picks up the move.
+        let revalidated = fetch(RefreshPolicy::TagsRevalidated)?;
+        assert_eq!(revalidated.git().precise(), Some(commits[0]));
+        assert_eq!(revalidated.strategy(), FetchStrategy::Reference);
+
+        // Full commit SHAs are reused under either policy, since they can't move.
+        let commit = GitSource::new(
+            url.clone()
+                .with_reference(GitReference::BranchOrTagOrCommit(commits[1].to_string())),
+            cache.path(),
+            true,
+        )
+        .fetch()?;
+        assert_eq!(commit.git().precise(), Some(commits[1]));
+        assert_eq!(commit.strategy(), FetchStrategy::Local);
+
+        Ok(())
+    }
+
+    #[test]
+    fn reference_cache() -> Result<()> {
+        let remote_dir = tempfile::tempdir()?;
+        let commits = fixture(remote_dir.path(), 3)?;
+        git(remote_dir.path(), &["tag", "v1.0", commits[2].as_str()])?;
+        let cache = tempfile::tempdir()?;
+        let url = GitUrl::from_reference(
+            DisplaySafeUrl::from_file_path(remote_dir.path()).unwrap(),
+            GitReference::Tag("v1.0".to_string()),
+            GitLfs::from(false),
+        )?;
+        let fetch = |refresh: bool| {
+            GitSource::new(url.clone(), cache.path(), true)
+                .with_refresh_policy(RefreshPolicy::TagsTrusted)
+                .with_refresh(refresh)
+                .fetch()
+        };
+
+        // The resolution is persisted.
+        assert_eq!(fetch(false)?.git().precise(), Some(commits[2]));
+        let references = ReferenceCache::read(cache.path().join("references.json"));
+        assert_eq!(
+            references.get(
+                &RepositoryUrl::new(url.repository()),
+                url.reference(),
+                RefreshPolicy::TagsTrusted
+            ),
+            Some(commits[2])
+        );
+
+        // Even if the database no longer has the tag itself, a trusted tag still resolves from
+        // the cache without contacting the remote, although it has moved on the remote since.
+        let db = fs_err::read_dir(cache.path().join("db"))?
+            .next()
+            .unwrap()?
+            .path();
+        git(&db, &["update-ref", "-d", "refs/remotes/origin/tags/v1.0"])?;
+        git(
+            remote_dir.path(),
+            &["tag", "--force", "v1.0", commits[0].as_str()],
+        )?;
+        let cached = fetch(false)?;
+        assert_eq!(cached.git().precise(), Some(commits[2]));
+        assert_eq!(cached.strategy(), FetchStrategy::Local);
+
+        // Unless the caller asks for a refresh, which updates the cache.
+        let refreshed = fetch(true)?;
+        assert_eq!(refreshed.git().precise(), Some(commits[0]));
+        assert_eq!(refreshed.strategy(), FetchStrategy::Reference);
+        git(&db, &["update-ref", "-d", "refs/remotes/origin/tags/v1.0"])?;
+        let cached = fetch(false)?;
+        assert_eq!(cached.git().precise(), Some(commits[0]));
+        assert_eq!(cached.strategy(), FetchStrategy::Local);
+
+        Ok(())
+    }
+
+    #[test]
+    fn missing_reference() -> Result<()> {
+        let remote_dir = tempfile::tempdir()?;
+        let commits = fixture(remote_dir.path(), 1)?;
+        git(remote_dir.path(), &["tag", "release", commits[0].as_str()])?;
+        let url = GitUrl::from_reference(
+            DisplaySafeUrl::from_file_path(remote_dir.path()).unwrap(),
+            GitReference::Branch("release".to_string()),
+            GitLfs::from(false),
+        )?;
+
+        // A missing branch is reported along with the tag of the same name.
+        let cache = tempfile::tempdir()?;
+        let Err(err) = GitSource::new(url.clone(), cache.path(), true).fetch() else {
+            panic!("expected the missing branch to fail");
+        };
+        assert!(
+            matches!(
+                err.downcast_ref(),
+                Some(GitResolveError::ReferenceNotFound { near_misses, .. })
+                    if near_misses == &["refs/tags/release"]
+            ),
+            "{err:?}"
+        );
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Git branch `release` was not found in `{}` (did you mean: refs/tags/release?)",
+                url.repository()
+            )
+        );
+
+        // The error from the fetch itself is retained.
+        assert!(
+            format!("{err:#}").contains("failed to fetch branch `release`"),
+            "{err:#}"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn version_tag() -> Result<()> {
+        let remote_dir = tempfile::tempdir()?;
+        let commits = fixture(remote_dir.path(), 2)?;
+        git(remote_dir.path(), &["tag", "v1.2.3", commits[1].as_str()])?;
+        let cache = tempfile::tempdir()?;
+        let fetch = |tag: &str| {
+            GitSource::new(
+                GitUrl::from_reference(
+                    DisplaySafeUrl::from_file_path(remote_dir.path()).unwrap(),
+                    GitReference::Tag(tag.to_string()),
+                    GitLfs::from(false),
+                )?,
+                cache.path(),
+                true,
+            )
+            .fetch()
+        };
+
+        // A bare version resolves to the `v`-prefixed tag.
+        let resolved = fetch("1.2.3")?;
+        assert_eq!(resolved.git().precise(), Some(commits[1]));
+        assert_eq!(
+            resolved.git().reference(),
+            &GitReference::Tag("1.2.3".to_string())
+        );
+
+        // If both variants exist, the ambiguity is an error.
+        git(remote_dir.path(), &["tag", "V1.2.3", commits[0].as_str()])?;
+        let Err(err) = fetch("1.2.3") else {
+            panic!("expected `1.2.3` to be ambiguous");
+        };
+        assert!(
+            matches!(
+                err.downcast_ref(),
+                Some(GitResolveError::AmbiguousVersionTag { candidates, .. })
+                    if candidates == &[
+                        ("v1.2.3".to_string(), commits[1]),
+                        ("V1.2.3".to_string(), commits[0]),
+                    ]
+            ),
+            "{err:?}"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn submodules() -> Result<()> {
+        // A parent repository with a submodule at a URL relative to the parent's.
+        let temp_dir = tempfile::tempdir()?;
+        let dependency = temp_dir.path().join("dependency");
+        let parent = temp_dir.path().join("parent");
+        fs_err::create_dir_all(&dependency)?;
+        fs_err::create_dir_all(&parent)?;
+        let dependency_commits = fixture(&dependency, 2)?;
+        fixture(&parent, 1)?;
+        git(&parent, &["checkout", "--quiet", "main"])?;
+        fs_err::write(
+            parent.join(".gitmodules"),
+            "[submodule \"vendor\"]\n\tpath = vendor/dependency\n\turl = ../dependency\n",
+        )?;
+        git(
+            &parent,
+            &[
+                "update-index",
+                "--add",
+                "--cacheinfo",
+                &format!("160000,{},vendor/dependency", dependency_commits[1]),
+            ],
+        )?;
+        git(&parent, &["add", ".gitmodules"])?;
+        git(&parent, &["commit", "--quiet", "--message=Add submodule"])?;
+
+        let cache = tempfile::tempdir()?;
+        let url = GitUrl::from_reference(
+            DisplaySafeUrl::from_file_path(&parent).unwrap(),
+            GitReference::Branch("main".to_string()),
+            GitLfs::from(false),
+        )?;
+
+        // By default, submodules are checked out at the commit recorded by the parent.
+        let fetch = GitSource::new(url.clone(), cache.path(), true).fetch()?;
+        assert_eq!(fetch.submodules().len(), 1);
+        assert_eq!(
+            fetch.submodules()[0].path,
+            PathBuf::from("vendor/dependency")
+        );
+        assert_eq!(fetch.submodules()[0].commit, dependency_commits[1]);
+        assert!(fetch.path().join("vendor/dependency/.git").exists());
+
+        // Submodules can be disabled globally, or for the repository.
+        for source in [
+            GitSource::new(url.clone(), cache.path(), true).with_submodules(false),
+            GitSource::new(url.clone().with_submodules(Some(false)), cache.path(), true),
+        ] {
+            let fetch = source.fetch()?;
+            assert!(fetch.submodules().is_empty());
+            assert!(!fetch.path().join("vendor/dependency/.git").exists());
+        }
+        let fetch = GitSource::new(url.clone().with_submodules(Some(true)), cache.path(), true)
+            .with_submodules(false)
+            .fetch()?;
+        assert_eq!(fetch.submodules().len(), 1);
+
+        // Failures name the submodule.
+        fs_err::write(
+            parent.join(".gitmodules"),
+            "[submodule \"vendor\"]\n\tpath = vendor/dependency\n\turl = ../missing\n",
+        )?;
+        git(
+            &parent,
+            &["commit", "--quiet", "--all", "--message=Break submodule"],
+        )?;
+        let Err(err) = GitSource::new(url, cache.path(), true).fetch() else {
+            panic!("expected the missing submodule to fail");
+        };
+        assert!(
+            err.to_string()
+                .contains("failed to update submodule `vendor` at `vendor/dependency`"),
+            "{err:#}"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn pruned_database() -> Result<()> {
+        let remote_dir = tempfile::tempdir()?;
+        let commits = fixture(remote_dir.path(), 3)?;
+        let cache = tempfile::tempdir()?;
+        let url = GitUrl::from_reference(
+            DisplaySafeUrl::from_file_path(remote_dir.path()).unwrap(),
+            GitReference::Branch("main".to_string()),
+            GitLfs::from(false),
+        )?
+        .with_precise(commits[1]);
+        let fetch = || GitSource::new(url.clone(), cache.path(), true).fetch();
+
+        fetch()?;
+        assert_eq!(fetch()?.strategy(), FetchStrategy::Local);
+
+        // Delete every object from the database, as if it had been pruned.
+        let db_path = fs_err::read_dir(cache.path().join("db"))?
+            .next()
+            .unwrap()?
+            .path();
+        for entry in fs_err::read_dir(db_path.join(".git").join("objects"))? {
+            let entry = entry?;
+            if entry.file_name() != "info" {
+                fs_err::remove_dir_all(entry.path())?;
+            }
+        }
+        fs_err::create_dir(db_path.join(".git").join("objects").join("pack"))?;
+        assert!(!GitRepository::open(&db_path)?.contains(commits[1])?);
+
+        // The commit is fetched again.
+        let recovered = fetch()?;
+        assert_eq!(recovered.strategy(), FetchStrategy::SingleCommit);
+        assert_eq!(recovered.git().precise(), Some(commits[1]));
+        assert!(GitRepository::open(&db_path)?.contains(commits[1])?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn paranoid() -> Result<()> {
+        let remote_dir = tempfile::tempdir()?;
+        let commits = fixture(remote_dir.path(), 1)?;
+        fs_err::write(remote_dir.path().join("README.md"), "uv\n")?;
+        git(remote_dir.path(), &["add", "README.md"])?;
+        git(
+            remote_dir.path(),
+            &["commit", "--quiet", "--message=Add README"],
+        )?;
+        let cache = tempfile::tempdir()?;
+        let url = GitUrl::from_reference(
+            DisplaySafeUrl::from_file_path(remote_dir.path()).unwrap(),
+            GitReference::DefaultBranch,
+            GitLfs::from(false),
+        )?;
+
+        let fetch = GitSource::new(url.clone(), cache.path(), true).fetch()?;
+        assert_ne!(fetch.git().precise(), Some(commits[0]));
+        let readme = fetch.path().join("README.md");
+        fs_err::write(&readme, "modified\n")?;
+
+        // By default, the modified checkout is reused as-is.
+        GitSource::new(url.clone(), cache.path(), true).fetch()?;
+        assert_eq!(fs_err::read_to_string(&readme)?, "modified\n");
+
+        // In paranoid mode, it's checked out again.
+        let fetch = GitSource::new(url, cache.path(), true).paranoid().fetch()?;
+        assert_eq!(fetch.path().join("README.md"), readme);
+        assert_eq!(fs_err::read_to_string(&readme)?, "uv\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn offline() -> Result<()> {
+        // A remote that fails the test if it's ever contacted.
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        listener.set_nonblocking(true)?;
+        let network = DisplaySafeUrl::parse(&format!(
+            "http://{}/astral-sh/uv.git",
+            listener.local_addr()?
+        ))?;
+
+        // Populate the cache from a local copy of the repository, as if it had been fetched
+        // from the network by an earlier run.
+        let remote_dir = tempfile::tempdir()?;
+        let commits = fixture(remote_dir.path(), 3)?;
+        let local = DisplaySafeUrl::from_file_path(remote_dir.path()).unwrap();
+        let cache = tempfile::tempdir()?;
+        let branch = |url: &DisplaySafeUrl, branch: &str| {
+            GitUrl::from_reference(
+                url.clone(),
+                GitReference::Branch(branch.to_string()),
+                GitLfs::from(false),
+            )
+        };
+        GitSource::new(branch(&local, "main")?, cache.path(), false).fetch()?;
+        let db = cache.path().join("db");
+        fs_err::rename(
+            db.join(cache_digest(&RepositoryUrl::new(&local))),
+            db.join(cache_digest(&RepositoryUrl::new(&network))),
+        )?;
+        let fetch = |url: GitUrl| GitSource::new(url, cache.path(), true).fetch();
+
+        // Branches resolved by the earlier run, locked commits, and full commit SHAs all resolve
+        // from the cache.
+        let main = fetch(branch(&network, "main")?)?;
+        assert_eq!(main.git().precise(), Some(commits[0]));
+        assert_eq!(main.strategy(), FetchStrategy::Local);
+        let locked = fetch(branch(&network, "main")?.with_precise(commits[1]))?;
+        assert_eq!(locked.git().precise(), Some(commits[1]));
+        let sha = fetch(GitUrl::from_reference(
+            network.clone(),
+            GitReference::BranchOrTagOrCommit(commits[2].to_string()),
+            GitLfs::from(false),
+        )?)?;
+        assert_eq!(sha.git().precise(), Some(commits[2]));
+
+        // Anything else names the revision that would need to be fetched.
+        let Err(err) = fetch(branch(&network, "missing")?) else {
+            panic!("expected the missing branch to fail");
+        };
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Git branch `missing` of `{network}` is not available in the cache, and network connectivity is disabled (i.e., with `--offline`)"
+            )
+        );
+        let missing = "1111111111111111111111111111111111111111".parse()?;
+        let Err(err) = fetch(branch(&network, "main")?.with_precise(missing)) else {
+            panic!("expected the missing commit to fail");
+        };
+        assert!(
+            matches!(
+                err.downcast_ref(),
+                Some(GitError::NotCached(err))
+                    if err.kind == "commit" && err.revision == missing.to_string()
+            ),
+            "{err}"
+        );
+
+        // References are resolved from the persistent cache, however stale, but not `ls-remote`.
+        let mut references = ReferenceCache::read(cache.path().join("references.json"));
+        references.insert(
+            &RepositoryUrl::new(&network),
+            &GitReference::Branch("main".to_string()),
+            commits[1],
+        );
+        references.write()?;
+        let main = fetch(branch(&network, "main")?)?;
+        assert_eq!(main.git().precise(), Some(commits[1]));
+        assert_eq!(main.strategy(), FetchStrategy::Local);
+        assert_eq!(
+            references.resolve(
+                &branch(&network, "main")?,
+                RefreshPolicy::TagsRevalidated,
+                true,
+                false,
+                true
+            )?,
+            commits[1]
+        );
+        assert!(
+            references
+                .resolve(
+                    &branch(&network, "missing")?,
+                    RefreshPolicy::TagsRevalidated,
+                    false,
+                    false,
+                    true
+                )
+                .is_err()
+        );
+        assert!(
+            resolve_reference(
+                &branch(&network, "main")?,
+                &GitReference::DefaultBranch,
+                false,
+                true
+            )
+            .is_err()
+        );
+
+        match listener.accept() {
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => {}
+            Ok((_, address)) => panic!("the remote was contacted by {address}"),
+            Err(err) => return Err(err.into()),
+        }
+
+        Ok(())
+    }
+
+    /// A [`GitProgress`] that cancels every operation.
+    struct Cancel;
+
+    impl GitProgress for Cancel {
+        fn on_negotiation_start(&self, _url: &DisplaySafeUrl) -> Result<()> {
+            anyhow::bail!("cancelled by the user")
+        }
+    }
+
+    #[test]
+    fn progress() -> Result<()> {
+        let remote_dir = tempfile::tempdir()?;
+        let commits = fixture(remote_dir.path(), 3)?;
+        let url = GitUrl::from_reference(
+            DisplaySafeUrl::from_file_path(remote_dir.path()).unwrap(),
+            GitReference::DefaultBranch,
+            GitLfs::from(false),
+        )?;
+
+        let cache = tempfile::tempdir()?;
+        let (progress, mut receiver) = ChannelProgress::new();
+        let fetch = GitSource::new(url.clone(), cache.path(), true)
+            .with_progress(Arc::new(progress))
+            .fetch()?;
+        assert_eq!(fetch.git().precise(), Some(commits[0]));
+
+        let mut events = Vec::new();
+        while let Ok(event) = receiver.try_recv() {
+            events.push(event);
+        }
+        assert_eq!(
+            events.first(),
+            Some(&GitProgressEvent::NegotiationStarted {
+                url: url.repository().clone()
+            })
+        );
+        assert_eq!(
+            events.last(),
+            Some(&GitProgressEvent::CheckoutFiles {
+                written: 0,
+                total: 0
+            })
+        );
+
+        // Failing callbacks cancel the operation, which is distinguishable from a Git error.
+        let cache = tempfile::tempdir()?;
+        let Err(err) = GitSource::new(url.clone(), cache.path(), true)
+            .with_progress(Arc::new(Cancel))
+            .fetch()
+        else {
+            panic!("expected the fetch to be cancelled");
+        };
+        assert!(
+            matches!(err.downcast_ref(), Some(GitError::Cancelled(_))),
+            "{err:#}"
+        );
+        assert!(
+            format!("{err:#}").contains("cancelled by the user"),
+            "{err:#}"
+        );
+
+        // As does dropping the receiver of a channel.
+        let (progress, receiver) = ChannelProgress::new();
+        drop(receiver);
+        let Err(err) = GitSource::new(url, cache.path(), true)
+            .with_progress(Arc::new(progress))
+            .fetch()
+        else {
+            panic!("expected the fetch to be cancelled");
+        };
+        assert!(
+            matches!(err.downcast_ref(), Some(GitError::Cancelled(_))),
+            "{err:#}"
+        );
+
+        Ok(())
+    }
+}
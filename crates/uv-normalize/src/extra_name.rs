@@ -175,3 +175,47 @@ impl AsRef<str> for ExtraName {
         self.as_str()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::ExtraName;
+
+    #[test]
+    fn normalize() {
+        let extra = ExtraName::from_str("Dev_Test").unwrap();
+        assert_eq!(extra, ExtraName::from_str("dev-test").unwrap());
+        assert_eq!(extra, ExtraName::from_str("DEV.-_TEST").unwrap());
+        assert_eq!(extra.to_string(), "dev-test");
+        assert!(ExtraName::from_str("dev").unwrap() < extra);
+    }
+
+    #[test]
+    fn invalid() {
+        for name in [
+            "",
+            "-dev",
+            "_dev",
+            "dev-",
+            "-",
+            "._",
+            "dev test",
+            "dev[test]",
+        ] {
+            let err = ExtraName::from_str(name).unwrap_err();
+            assert_eq!(err.as_str(), name);
+            assert!(ExtraName::from_owned(name.to_string()).is_err(), "{name:?}");
+        }
+    }
+
+    #[test]
+    fn serde() {
+        let extras: Vec<ExtraName> = serde_json::from_str(r#"["Dev_Test", "docs"]"#).unwrap();
+        assert_eq!(
+            serde_json::to_string(&extras).unwrap(),
+            r#"["dev-test","docs"]"#
+        );
+        assert!(serde_json::from_str::<ExtraName>(r#""-dev""#).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 
@@ -5,6 +6,7 @@ pub use dist_info_name::DistInfoName;
 pub use extra_name::{DefaultExtras, ExtraName};
 pub use group_name::{DEV_DEPENDENCIES, DefaultGroups, GroupName, PipGroupName};
 pub use package_name::PackageName;
+pub use verbatim_name::VerbatimName;
 
 use uv_small_str::SmallString;
 
@@ -12,25 +14,35 @@ mod dist_info_name;
 mod extra_name;
 mod group_name;
 mod package_name;
+mod verbatim_name;
 
 /// Validate and normalize an unowned package or extra name.
 pub(crate) fn validate_and_normalize_ref(
     name: impl AsRef<str>,
 ) -> Result<SmallString, InvalidNameError> {
-    let name = name.as_ref();
+    validate_and_normalize_cow(name.as_ref()).map(SmallString::from)
+}
+
+/// Validate and normalize an unowned package or extra name, borrowing it if it's already
+/// normalized.
+pub(crate) fn validate_and_normalize_cow(name: &str) -> Result<Cow<'_, str>, InvalidNameError> {
     if is_normalized(name)? {
-        Ok(SmallString::from(name))
+        Ok(Cow::Borrowed(name))
     } else {
-        Ok(SmallString::from(normalize(name)?))
+        Ok(Cow::Owned(normalize(name)?))
     }
 }
 
 /// Normalize an unowned package or extra name.
 fn normalize(name: &str) -> Result<String, InvalidNameError> {
+    if name.is_empty() {
+        return Err(InvalidNameError::new(name, None));
+    }
+
     let mut normalized = String::with_capacity(name.len());
 
     let mut last = None;
-    for char in name.bytes() {
+    for (index, char) in name.bytes().enumerate() {
         match char {
             b'A'..=b'Z' => {
                 normalized.push(char.to_ascii_lowercase() as char);
@@ -41,19 +53,19 @@ fn normalize(name: &str) -> Result<String, InvalidNameError> {
             b'-' | b'_' | b'.' => {
                 match last {
                     // Names can't start with punctuation.
-                    None => return Err(InvalidNameError(name.to_string())),
+                    None => return Err(InvalidNameError::new(name, Some(index))),
                     Some(b'-' | b'_' | b'.') => {}
                     Some(_) => normalized.push('-'),
                 }
             }
-            _ => return Err(InvalidNameError(name.to_string())),
+            _ => return Err(InvalidNameError::new(name, Some(index))),
         }
         last = Some(char);
     }
 
     // Names can't end with punctuation.
     if matches!(last, Some(b'-' | b'_' | b'.')) {
-        return Err(InvalidNameError(name.to_string()));
+        return Err(InvalidNameError::new(name, Some(name.len() - 1)));
     }
 
     Ok(normalized)
@@ -61,8 +73,13 @@ fn normalize(name: &str) -> Result<String, InvalidNameError> {
 
 /// Returns `true` if the name is already normalized.
 fn is_normalized(name: impl AsRef<str>) -> Result<bool, InvalidNameError> {
+    let name = name.as_ref();
+    if name.is_empty() {
+        return Err(InvalidNameError::new(name, None));
+    }
+
     let mut last = None;
-    for char in name.as_ref().bytes() {
+    for (index, char) in name.bytes().enumerate() {
         match char {
             b'A'..=b'Z' => {
                 // Uppercase characters need to be converted to lowercase.
@@ -76,7 +93,7 @@ fn is_normalized(name: impl AsRef<str>) -> Result<bool, InvalidNameError> {
             b'-' => {
                 match last {
                     // Names can't start with punctuation.
-                    None => return Err(InvalidNameError(name.as_ref().to_string())),
+                    None => return Err(InvalidNameError::new(name, Some(index))),
                     Some(b'-') => {
                         // Runs of `-` are normalized to a single `-`.
                         return Ok(false);
@@ -84,27 +101,50 @@ fn is_normalized(name: impl AsRef<str>) -> Result<bool, InvalidNameError> {
                     Some(_) => {}
                 }
             }
-            _ => return Err(InvalidNameError(name.as_ref().to_string())),
+            _ => return Err(InvalidNameError::new(name, Some(index))),
         }
         last = Some(char);
     }
 
     // Names can't end with punctuation.
     if matches!(last, Some(b'-' | b'_' | b'.')) {
-        return Err(InvalidNameError(name.as_ref().to_string()));
+        return Err(InvalidNameError::new(name, Some(name.len() - 1)));
     }
 
     Ok(true)
 }
 
 /// Invalid [`PackageName`] or [`ExtraName`].
+///
+/// Valid names match `^([A-Za-z0-9]|[A-Za-z0-9][A-Za-z0-9._-]*[A-Za-z0-9])$`, per PEP 508.
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct InvalidNameError(String);
+pub struct InvalidNameError {
+    name: String,
+    /// The byte offset of the first invalid character, or `None` if the name is empty.
+    position: Option<usize>,
+}
 
 impl InvalidNameError {
+    fn new(name: &str, position: Option<usize>) -> Self {
+        Self {
+            name: name.to_string(),
+            position,
+        }
+    }
+
     /// Returns the invalid name.
     pub fn as_str(&self) -> &str {
-        &self.0
+        &self.name
+    }
+
+    /// Returns the first invalid character in the name, along with its byte offset.
+    ///
+    /// Punctuation is only invalid at the start or end of a name. Returns `None` if the name is
+    /// empty.
+    pub fn invalid_char(&self) -> Option<(usize, char)> {
+        let position = self.position?;
+        let char = self.name.get(position..)?.chars().next()?;
+        Some((position, char))
     }
 }
 
@@ -114,7 +154,7 @@ impl Display for InvalidNameError {
             f,
             "Not a valid package or extra name: \"{}\". Names must start and end with a letter or \
             digit and may only contain -, _, ., and alphanumeric characters.",
-            self.0
+            self.name
         )
     }
 }
@@ -194,6 +234,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn borrowed() {
+        for input in ["friendly-bard", "friendlybard", "1okay", "package-0499"] {
+            assert!(
+                matches!(validate_and_normalize_cow(input).unwrap(), Cow::Borrowed(name) if name == input),
+                "{input:?}"
+            );
+        }
+
+        for input in [
+            "Friendly-Bard",
+            "friendly.bard",
+            "friendly_bard",
+            "friendly--bard",
+            "FrIeNdLy-._.-bArD",
+        ] {
+            assert!(
+                matches!(validate_and_normalize_cow(input).unwrap(), Cow::Owned(name) if name == "friendly-bard"),
+                "{input:?}"
+            );
+        }
+
+        assert!(validate_and_normalize_cow("-friendly-bard").is_err());
+    }
+
     #[test]
     fn check() {
         let inputs = ["friendly-bard", "friendlybard"];
@@ -240,4 +305,23 @@ mod tests {
             assert!(is_normalized(input).is_err());
         }
     }
+
+    #[test]
+    fn invalid_char() {
+        let invalid = [
+            (" starts-with-space", Some((0, ' '))),
+            ("-starts-with-dash", Some((0, '-'))),
+            ("Ends-With-Dot.", Some((13, '.'))),
+            ("ends-with-dash-", Some((14, '-'))),
+            ("includes!invalid-char", Some((8, '!'))),
+            ("space in middle", Some((5, ' '))),
+            ("Alpha-α", Some((6, 'α'))),
+            ("", None),
+        ];
+        for (input, expected) in invalid {
+            let err = validate_and_normalize_ref(input).unwrap_err();
+            assert_eq!(err.as_str(), input);
+            assert_eq!(err.invalid_char(), expected, "{input:?}");
+        }
+    }
 }
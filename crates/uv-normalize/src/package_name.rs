@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use std::borrow::{Borrow, Cow};
 use std::cmp::PartialEq;
 use std::str::FromStr;
 
@@ -6,7 +6,7 @@ use serde::{Deserialize, Deserializer, Serialize};
 
 use uv_small_str::SmallString;
 
-use crate::{InvalidNameError, validate_and_normalize_ref};
+use crate::{InvalidNameError, validate_and_normalize_cow, validate_and_normalize_ref};
 
 /// The normalized name of a package.
 ///
@@ -32,6 +32,16 @@ use crate::{InvalidNameError, validate_and_normalize_ref};
 pub struct PackageName(SmallString);
 
 impl PackageName {
+    /// Create a validated, normalized package name.
+    ///
+    /// The name must match the PEP 508 grammar; if it doesn't, the [`InvalidNameError`] reports
+    /// the first invalid character.
+    ///
+    /// See: <https://peps.python.org/pep-0508/#names>
+    pub fn new(name: &str) -> Result<Self, InvalidNameError> {
+        validate_and_normalize_ref(name).map(Self)
+    }
+
     /// Create a validated, normalized package name.
     ///
     /// At present, this is no more efficient than calling [`PackageName::from_str`].
@@ -40,6 +50,14 @@ impl PackageName {
         validate_and_normalize_ref(&name).map(Self)
     }
 
+    /// Validate and normalize a package name, without creating a [`PackageName`].
+    ///
+    /// The name is borrowed if it's already normalized, so that comparing names to a
+    /// [`PackageName`] (e.g., the keys of a table) only allocates for names that need to change.
+    pub fn normalize(name: &str) -> Result<Cow<'_, str>, InvalidNameError> {
+        validate_and_normalize_cow(name)
+    }
+
     /// Escape this name with underscores (`_`) instead of dashes (`-`)
     ///
     /// See: <https://packaging.python.org/en/latest/specifications/recording-installed-packages/#recording-installed-packages>
@@ -80,7 +98,7 @@ impl FromStr for PackageName {
     type Err = InvalidNameError;
 
     fn from_str(name: &str) -> Result<Self, Self::Err> {
-        validate_and_normalize_ref(name).map(Self)
+        Self::new(name)
     }
 }
 
@@ -122,3 +140,94 @@ impl AsRef<str> for PackageName {
         &self.0
     }
 }
+
+impl Borrow<str> for PackageName {
+    /// Allows maps keyed by [`PackageName`] to be queried by a `&str`, which only matches if the
+    /// string is the normalized name.
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+    use std::str::FromStr;
+
+    use serde::{Deserialize, Serialize};
+
+    use super::PackageName;
+
+    #[test]
+    fn display() {
+        let name = PackageName::from_str("Friendly_Bard").unwrap();
+        assert_eq!(name.to_string(), "friendly-bard");
+        assert_eq!(name.as_ref(), "friendly-bard");
+    }
+
+    #[test]
+    fn borrow() {
+        let mut map = HashMap::new();
+        map.insert(PackageName::from_str("Friendly.Bard").unwrap(), 1);
+        assert_eq!(map.get("friendly-bard"), Some(&1));
+        assert_eq!(map.get("Friendly.Bard"), None);
+
+        let map = BTreeMap::from([(PackageName::from_str("friendly-bard").unwrap(), 1)]);
+        assert_eq!(map.get("friendly-bard"), Some(&1));
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Lock {
+        package: Vec<Package>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Package {
+        name: PackageName,
+    }
+
+    #[test]
+    fn serde_json() {
+        let lock: Lock =
+            serde_json::from_str(r#"{"package": [{"name": "Friendly_Bard"}, {"name": "anyio"}]}"#)
+                .unwrap();
+        assert_eq!(lock.package[0].name.as_str(), "friendly-bard");
+
+        let json = serde_json::to_string(&lock).unwrap();
+        assert_eq!(
+            json,
+            r#"{"package":[{"name":"friendly-bard"},{"name":"anyio"}]}"#
+        );
+        assert_eq!(serde_json::from_str::<Lock>(&json).unwrap(), lock);
+
+        let err = serde_json::from_str::<Package>(r#"{"name": "-friendly-bard"}"#).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Not a valid package or extra name")
+        );
+    }
+
+    #[test]
+    fn serde_toml() {
+        let lock: Lock = toml::from_str(
+            r#"
+            [[package]]
+            name = "Friendly.Bard"
+
+            [[package]]
+            name = "anyio"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(lock.package[0].name.as_str(), "friendly-bard");
+
+        let toml = toml::to_string(&lock).unwrap();
+        assert_eq!(
+            toml,
+            "[[package]]\nname = \"friendly-bard\"\n\n[[package]]\nname = \"anyio\"\n"
+        );
+        assert_eq!(toml::from_str::<Lock>(&toml).unwrap(), lock);
+
+        assert!(toml::from_str::<Package>(r#"name = "friendly bard""#).is_err());
+    }
+}
@@ -0,0 +1,159 @@
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use uv_small_str::SmallString;
+
+use crate::{InvalidNameError, PackageName};
+
+/// A [`PackageName`], along with its spelling as written by the user (e.g., `Django` or
+/// `ruamel.yaml`).
+///
+/// Equality, ordering, and hashing only consider the normalized name, while [`Display`] shows the
+/// verbatim spelling. To save memory, the verbatim spelling is only stored if it differs from the
+/// normalized name.
+#[derive(Debug, Clone)]
+pub struct VerbatimName {
+    name: PackageName,
+    verbatim: Option<SmallString>,
+}
+
+impl VerbatimName {
+    /// Create a validated package name, retaining its verbatim spelling.
+    pub fn new(name: &str) -> Result<Self, InvalidNameError> {
+        let normalized = PackageName::new(name)?;
+        let verbatim = (normalized.as_str() != name).then(|| SmallString::from(name));
+        Ok(Self {
+            name: normalized,
+            verbatim,
+        })
+    }
+
+    /// Returns the name as written, e.g., `Django`.
+    pub fn as_verbatim(&self) -> &str {
+        self.verbatim.as_deref().unwrap_or(self.name.as_str())
+    }
+
+    /// Returns the normalized name, e.g., `django`.
+    pub fn as_normalized(&self) -> &PackageName {
+        &self.name
+    }
+
+    /// Returns the normalized name, discarding the verbatim spelling.
+    pub fn into_normalized(self) -> PackageName {
+        self.name
+    }
+
+    /// Returns `true` if the name was written in its normalized form.
+    pub fn is_normalized(&self) -> bool {
+        self.verbatim.is_none()
+    }
+}
+
+impl From<PackageName> for VerbatimName {
+    fn from(name: PackageName) -> Self {
+        Self {
+            name,
+            verbatim: None,
+        }
+    }
+}
+
+impl PartialEq for VerbatimName {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for VerbatimName {}
+
+impl PartialOrd for VerbatimName {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for VerbatimName {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.name.cmp(&other.name)
+    }
+}
+
+impl Hash for VerbatimName {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+    }
+}
+
+impl FromStr for VerbatimName {
+    type Err = InvalidNameError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::new(name)
+    }
+}
+
+impl Display for VerbatimName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_verbatim())
+    }
+}
+
+impl Serialize for VerbatimName {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_verbatim())
+    }
+}
+
+impl<'de> Deserialize<'de> for VerbatimName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = <Cow<'_, str>>::deserialize(deserializer)?;
+        Self::new(&name).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::str::FromStr;
+
+    use crate::PackageName;
+
+    use super::VerbatimName;
+
+    #[test]
+    fn verbatim() {
+        let name = VerbatimName::from_str("ruamel.yaml").unwrap();
+        assert_eq!(name.as_verbatim(), "ruamel.yaml");
+        assert_eq!(name.as_normalized().as_str(), "ruamel-yaml");
+        assert_eq!(name.to_string(), "ruamel.yaml");
+        assert!(!name.is_normalized());
+
+        // Normalized names aren't stored twice.
+        let name = VerbatimName::from_str("django").unwrap();
+        assert_eq!(name.as_verbatim(), "django");
+        assert!(name.is_normalized());
+
+        assert!(VerbatimName::from_str("-django").is_err());
+    }
+
+    #[test]
+    fn equality() {
+        let upper = VerbatimName::from_str("Django").unwrap();
+        let lower = VerbatimName::from(PackageName::from_str("django").unwrap());
+        assert_eq!(upper, lower);
+        assert_eq!(HashSet::from([upper, lower]).len(), 1);
+    }
+
+    #[test]
+    fn serde() {
+        let name: VerbatimName = serde_json::from_str(r#""Ruamel.YAML""#).unwrap();
+        assert_eq!(name.as_normalized().as_str(), "ruamel-yaml");
+        assert_eq!(serde_json::to_string(&name).unwrap(), r#""Ruamel.YAML""#);
+        assert!(serde_json::from_str::<VerbatimName>(r#""ruamel yaml""#).is_err());
+    }
+}
@@ -0,0 +1,268 @@
+//! Semantic equality of requirements, beyond comparing their string representations.
+
+use std::borrow::Cow;
+
+use percent_encoding::percent_decode_str;
+use url::Url;
+
+use uv_pep440::{Operator, VersionSpecifier};
+
+use crate::{Pep508Url, Requirement, VersionOrUrl};
+
+/// Returns `true` if the two requirements are the same requirement, even if they're spelled
+/// differently.
+///
+/// Two requirements are equal if they have:
+///
+/// - The same normalized name (e.g., `Foo_Bar` and `foo-bar`).
+/// - The same set of normalized extras, regardless of order or duplicates.
+/// - The same version specifiers, regardless of order or duplicates, where versions are compared
+///   per PEP 440 (e.g., `>=2.0, <3` and `<3,>=2.0`, or `==2.0.0` and `==2.0`). No specifiers at
+///   all is the same as an empty set of specifiers.
+/// - The same markers, which are normalized on parsing (e.g., `python_version > '3.8'` and
+///   `python_version>"3.8"`).
+/// - The same URL, ignoring a trailing slash on the path and the choice of percent-encoding
+///   (e.g., `https://example.com/foo%2Dbar/` and `https://example.com/foo-bar`).
+///
+/// The following are deliberately _not_ considered equal, even where they may admit the same
+/// versions, since rewriting one into the other would change what the user wrote:
+///
+/// - `~=` and prefix matches (`==2.*`), unless their release segments are written identically:
+///   `~=2.1` allows `2.9` while `~=2.1.0` does not, and `==2.*` differs from `==2.0.*`. Likewise,
+///   `~=2.1` is not equal to its expanded form, `>=2.1, ==2.*`.
+/// - Specifier sets that are only logically equivalent, e.g., `>=1.0, >1.0` and `>1.0`, or
+///   `==1.0` and `>=1.0, <=1.0`.
+/// - Arbitrary equality (`===`), which compares versions as strings, and `==`.
+/// - Versions with and without a local segment, e.g., `==1.0` and `==1.0+local`.
+/// - A version specifier and a URL, even if the URL points to a matching version.
+///
+/// The requirements' origins are ignored.
+pub fn requirements_equal<T: Pep508Url>(a: &Requirement<T>, b: &Requirement<T>) -> bool {
+    a.name == b.name
+        && is_subset(&a.extras, &b.extras, PartialEq::eq)
+        && is_subset(&b.extras, &a.extras, PartialEq::eq)
+        && version_or_url_equal(a.version_or_url.as_ref(), b.version_or_url.as_ref())
+        && a.marker == b.marker
+}
+
+fn version_or_url_equal<T: Pep508Url>(
+    a: Option<&VersionOrUrl<T>>,
+    b: Option<&VersionOrUrl<T>>,
+) -> bool {
+    match (a, b) {
+        (Some(VersionOrUrl::Url(a)), Some(VersionOrUrl::Url(b))) => urls_equal(a, b),
+        (Some(VersionOrUrl::Url(_)), _) | (_, Some(VersionOrUrl::Url(_))) => false,
+        _ => {
+            let (a, b) = (specifiers(a), specifiers(b));
+            is_subset(a, b, specifiers_equal) && is_subset(b, a, specifiers_equal)
+        }
+    }
+}
+
+/// Returns the version specifiers of a requirement, treating a missing version as no specifiers.
+fn specifiers<T: Pep508Url>(version_or_url: Option<&VersionOrUrl<T>>) -> &[VersionSpecifier] {
+    match version_or_url {
+        Some(VersionOrUrl::VersionSpecifier(specifiers)) => specifiers,
+        Some(VersionOrUrl::Url(_)) | None => &[],
+    }
+}
+
+/// Returns `true` if every item in `a` is equal to an item in `b`.
+fn is_subset<T>(a: &[T], b: &[T], eq: impl Fn(&T, &T) -> bool) -> bool {
+    a.iter().all(|a| b.iter().any(|b| eq(a, b)))
+}
+
+fn specifiers_equal(a: &VersionSpecifier, b: &VersionSpecifier) -> bool {
+    if a.operator() != b.operator() {
+        return false;
+    }
+    match a.operator() {
+        // The number of release segments is significant.
+        Operator::TildeEqual | Operator::EqualStar | Operator::NotEqualStar => {
+            *a.version().release() == *b.version().release() && a.version() == b.version()
+        }
+        // Versions are compared as strings.
+        Operator::ExactEqual => a.version().to_string() == b.version().to_string(),
+        Operator::Equal
+        | Operator::NotEqual
+        | Operator::LessThan
+        | Operator::LessThanEqual
+        | Operator::GreaterThan
+        | Operator::GreaterThanEqual => a.version() == b.version(),
+    }
+}
+
+fn urls_equal<T: Pep508Url>(a: &T, b: &T) -> bool {
+    let a = a.displayable_with_credentials().to_string();
+    let b = b.displayable_with_credentials().to_string();
+    if a == b {
+        return true;
+    }
+    match (Url::parse(&a), Url::parse(&b)) {
+        (Ok(a), Ok(b)) => {
+            a.scheme() == b.scheme()
+                && a.username() == b.username()
+                && a.password() == b.password()
+                && a.host() == b.host()
+                && a.port_or_known_default() == b.port_or_known_default()
+                && normalize_path(a.path()) == normalize_path(b.path())
+                && a.query().map(decode) == b.query().map(decode)
+                && a.fragment().map(decode) == b.fragment().map(decode)
+        }
+        _ => false,
+    }
+}
+
+/// Decode a URL path, and strip any trailing slash (except from the root).
+fn normalize_path(path: &str) -> Cow<'_, str> {
+    let path = path
+        .strip_suffix('/')
+        .filter(|path| !path.is_empty())
+        .unwrap_or(path);
+    decode(path)
+}
+
+fn decode(component: &str) -> Cow<'_, str> {
+    percent_decode_str(component).decode_utf8_lossy()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{Requirement, VerbatimUrl};
+
+    use super::requirements_equal;
+
+    fn equal(a: &str, b: &str) -> bool {
+        let a = Requirement::<VerbatimUrl>::from_str(a).unwrap();
+        let b = Requirement::<VerbatimUrl>::from_str(b).unwrap();
+        let equal = requirements_equal(&a, &b);
+        assert_eq!(
+            equal,
+            requirements_equal(&b, &a),
+            "{a} and {b} aren't symmetric"
+        );
+        equal
+    }
+
+    #[test]
+    fn names() {
+        assert!(equal("foo-bar", "foo-bar"));
+        assert!(equal("Foo_Bar", "foo-bar"));
+        assert!(equal("foo.bar>=1", "FOO--BAR >= 1"));
+        assert!(!equal("foo-bar", "foo-baz"));
+    }
+
+    #[test]
+    fn extras() {
+        assert!(equal("foo[a,b]", "foo[b,a]"));
+        assert!(equal("foo[Dev_Test]", "foo[dev-test]"));
+        assert!(equal("foo[a,a,b]", "foo[b,a]"));
+        assert!(!equal("foo[a]", "foo[a,b]"));
+        assert!(!equal("foo[a]", "foo"));
+    }
+
+    #[test]
+    fn specifiers() {
+        assert!(equal("foo>=2.0,<3", "foo<3,>=2.0"));
+        assert!(equal("foo >= 2.0, < 3", "foo>=2.0,<3"));
+        assert!(equal("foo==2.0.0", "foo==2.0"));
+        assert!(equal("foo==2", "foo==2.0.0"));
+        assert!(equal("foo>=2,>=2.0", "foo>=2"));
+        assert!(equal("foo!=1.0.0", "foo!=1"));
+        assert!(equal("foo==1.0a1", "foo==1.0.0alpha1"));
+        assert!(equal("foo==1.0.post1", "foo==1.0-1"));
+        assert!(equal("foo", "foo"));
+
+        assert!(!equal("foo>=2.0", "foo>2.0"));
+        assert!(!equal("foo>=2.0", "foo>=2.0,<3"));
+        assert!(!equal("foo>=2.0", "foo"));
+        assert!(!equal("foo==1.0", "foo==1.0+local"));
+        assert!(!equal("foo==1.0", "foo==1.0.dev0"));
+    }
+
+    /// Specifiers whose release segments are significant, or which are only logically equivalent,
+    /// are deliberately not equal.
+    #[test]
+    fn specifiers_not_equal() {
+        assert!(equal("foo~=2.1", "foo~=2.1"));
+        assert!(equal("foo==2.0.*", "foo==2.0.*"));
+        assert!(!equal("foo~=2.1", "foo~=2.1.0"));
+        assert!(!equal("foo~=2.1", "foo>=2.1,==2.*"));
+        assert!(!equal("foo==2.*", "foo==2.0.*"));
+        assert!(!equal("foo!=2.*", "foo!=2.0.*"));
+        assert!(!equal("foo==2.*", "foo>=2,<3"));
+        assert!(!equal("foo>=1.0,>1.0", "foo>1.0"));
+        assert!(!equal("foo==1.0", "foo>=1.0,<=1.0"));
+        assert!(!equal("foo===1.0", "foo==1.0"));
+        assert!(!equal("foo===1.0", "foo===1.0.0"));
+        assert!(equal("foo===1.0", "foo===1.0"));
+    }
+
+    #[test]
+    fn markers() {
+        assert!(equal(
+            "foo ; python_version > '3.8'",
+            r#"foo;python_version>"3.8""#
+        ));
+        assert!(equal(
+            "foo ; sys_platform == 'linux' and python_version >= '3.9'",
+            "foo ; python_version >= '3.9' and sys_platform == 'linux'"
+        ));
+        assert!(equal(
+            "foo ; extra == 'Dev_Test'",
+            "foo ; extra == 'dev-test'"
+        ));
+        assert!(!equal(
+            "foo ; python_version > '3.8'",
+            "foo ; python_version >= '3.8'"
+        ));
+        assert!(!equal("foo ; sys_platform == 'linux'", "foo"));
+    }
+
+    #[test]
+    fn urls() {
+        assert!(equal(
+            "foo @ https://example.com/foo.whl",
+            "foo@https://example.com/foo.whl"
+        ));
+        assert!(equal(
+            "foo @ https://example.com/foo/",
+            "foo @ https://example.com/foo"
+        ));
+        assert!(equal(
+            "foo @ https://example.com/foo%2Dbar/",
+            "foo @ https://example.com/foo-bar"
+        ));
+        assert!(equal(
+            "foo @ https://EXAMPLE.com:443/foo",
+            "foo @ https://example.com/foo"
+        ));
+        assert!(equal(
+            "foo @ git+https://example.com/foo@main#subdirectory=a%2Fb",
+            "foo @ git+https://example.com/foo@main#subdirectory=a/b"
+        ));
+
+        assert!(!equal(
+            "foo @ https://example.com/foo",
+            "foo @ http://example.com/foo"
+        ));
+        assert!(!equal(
+            "foo @ https://example.com/foo",
+            "foo @ https://example.com/Foo"
+        ));
+        assert!(!equal(
+            "foo @ git+https://example.com/foo@main",
+            "foo @ git+https://example.com/foo@v1"
+        ));
+        assert!(!equal(
+            "foo @ https://user@example.com/foo",
+            "foo @ https://example.com/foo"
+        ));
+        assert!(!equal(
+            "foo @ https://example.com/foo-1.0-py3-none-any.whl",
+            "foo==1.0"
+        ));
+    }
+}
@@ -31,12 +31,14 @@ use uv_cache_key::{CacheKey, CacheKeyHasher};
 use uv_normalize::{ExtraName, PackageName};
 
 use crate::cursor::Cursor;
+pub use crate::equivalence::requirements_equal;
 pub use crate::marker::{
     CanonicalMarkerValueExtra, CanonicalMarkerValueString, CanonicalMarkerValueVersion,
     ContainsMarkerTree, ExtraMarkerTree, ExtraOperator, InMarkerTree, MarkerEnvironment,
     MarkerEnvironmentBuilder, MarkerExpression, MarkerOperator, MarkerTree, MarkerTreeContents,
     MarkerTreeKind, MarkerValue, MarkerValueExtra, MarkerValueList, MarkerValueString,
-    MarkerValueVersion, MarkerWarningKind, StringMarkerTree, StringVersion, VersionMarkerTree,
+    MarkerValueVersion, MarkerWarningKind, PythonMarkerError, StringMarkerTree, StringVersion,
+    VersionMarkerTree, python_marker,
 };
 pub use crate::origin::RequirementOrigin;
 #[cfg(feature = "non-pep508-extensions")]
@@ -51,6 +53,7 @@ pub use uv_pep440;
 use uv_pep440::{VersionSpecifier, VersionSpecifiers};
 
 mod cursor;
+mod equivalence;
 pub mod marker;
 mod origin;
 #[cfg(feature = "non-pep508-extensions")]
@@ -13,6 +13,7 @@ mod algebra;
 mod environment;
 mod lowering;
 pub(crate) mod parse;
+mod python;
 mod simplify;
 mod tree;
 
@@ -20,6 +21,7 @@ pub use environment::{MarkerEnvironment, MarkerEnvironmentBuilder};
 pub use lowering::{
     CanonicalMarkerValueExtra, CanonicalMarkerValueString, CanonicalMarkerValueVersion,
 };
+pub use python::{PythonMarkerError, python_marker};
 pub use tree::{
     ContainsMarkerTree, ExtraMarkerTree, ExtraOperator, InMarkerTree, MarkerExpression,
     MarkerOperator, MarkerTree, MarkerTreeContents, MarkerTreeDebugGraph, MarkerTreeKind,
@@ -0,0 +1,220 @@
+//! Converting a set of Python version specifiers (as in `requires-python`) into a marker.
+
+use thiserror::Error;
+
+use uv_pep440::{Operator, VersionSpecifier};
+
+use crate::{MarkerExpression, MarkerTree, MarkerValueVersion};
+
+/// A specifier that can't be expressed as a Python version marker.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum PythonMarkerError {
+    /// The specifier uses arbitrary equality, e.g., `===3.9.1`.
+    #[error(
+        "`{0}` can't be expressed as a Python version marker, since arbitrary equality compares versions as strings"
+    )]
+    ArbitraryEquality(VersionSpecifier),
+    /// The version has segments other than the release, e.g., `>=3.13.0rc1`.
+    #[error(
+        "`{0}` can't be expressed as a Python version marker, since markers only compare the release segments of Python versions"
+    )]
+    NonRelease(VersionSpecifier),
+}
+
+/// Returns the marker that applies to exactly the Python versions matching all of the given
+/// specifiers, e.g., `python_version >= '3.9' and python_version < '3.12'` for `>=3.9,<3.12`.
+///
+/// With no specifiers, the returned marker is always true.
+///
+/// Each specifier is compared against `python_version` where that's equivalent, and against
+/// `python_full_version` otherwise. Since `python_version` only has a major and minor segment, it
+/// can be used for the operators that only depend on the major and minor segments when the
+/// specifier has no segments beyond them, i.e., `>=`, `<`, `~=`, `==` with a wildcard, and `!=`
+/// with a wildcard. For example, `<=3.9` includes `3.9.0`, but not `3.9.1`, so it's expressed as
+/// `python_full_version <= '3.9'`, rather than `python_version <= '3.9'`, which includes both.
+///
+/// Specifiers with pre-release, post-release, dev release or local segments, or an epoch, can't
+/// be expressed, as markers ignore those segments (e.g., `>=3.13.0rc1` would be treated as
+/// `>=3.13`, excluding `3.13.0rc1`). Neither can arbitrary equality (`===`).
+pub fn python_marker(specifiers: &[VersionSpecifier]) -> Result<MarkerTree, PythonMarkerError> {
+    let mut marker = MarkerTree::TRUE;
+    for specifier in specifiers {
+        let version = specifier.version();
+        if *specifier.operator() == Operator::ExactEqual {
+            return Err(PythonMarkerError::ArbitraryEquality(specifier.clone()));
+        }
+        if *version != version.only_release() || version.epoch() != 0 {
+            return Err(PythonMarkerError::NonRelease(specifier.clone()));
+        }
+        marker.and(MarkerTree::expression(MarkerExpression::Version {
+            key: python_key(specifier),
+            specifier: specifier.clone(),
+        }));
+    }
+    Ok(marker)
+}
+
+/// Returns the marker key that's equivalent to the specifier, preferring `python_version`.
+fn python_key(specifier: &VersionSpecifier) -> MarkerValueVersion {
+    let release = specifier.version().release().len();
+    let truncatable = match specifier.operator() {
+        Operator::GreaterThanEqual
+        | Operator::LessThan
+        | Operator::EqualStar
+        | Operator::NotEqualStar => release <= 2,
+        // `~=3` isn't a valid specifier, and `~=3.9` only depends on the major segment.
+        Operator::TildeEqual => release == 2,
+        Operator::Equal
+        | Operator::ExactEqual
+        | Operator::NotEqual
+        | Operator::GreaterThan
+        | Operator::LessThanEqual => false,
+    };
+    if truncatable {
+        MarkerValueVersion::PythonVersion
+    } else {
+        MarkerValueVersion::PythonFullVersion
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use uv_pep440::{Version, VersionSpecifiers};
+
+    use crate::{MarkerEnvironment, MarkerEnvironmentBuilder, MarkerTree, MarkerValueVersion};
+
+    use super::{python_key, python_marker};
+
+    fn keys(specifiers: &str) -> Vec<String> {
+        VersionSpecifiers::from_str(specifiers)
+            .unwrap()
+            .iter()
+            .map(|specifier| match python_key(specifier) {
+                MarkerValueVersion::PythonVersion => format!("python_version {specifier}"),
+                _ => format!("python_full_version {specifier}"),
+            })
+            .collect()
+    }
+
+    fn marker(specifiers: &str) -> MarkerTree {
+        python_marker(&VersionSpecifiers::from_str(specifiers).unwrap()).unwrap()
+    }
+
+    fn environment(python_full_version: &str) -> MarkerEnvironment {
+        let python_version = Version::from_str(python_full_version)
+            .unwrap()
+            .only_minor_release()
+            .to_string();
+        MarkerEnvironment::try_from(MarkerEnvironmentBuilder {
+            implementation_name: "cpython",
+            implementation_version: python_full_version,
+            os_name: "posix",
+            platform_machine: "x86_64",
+            platform_python_implementation: "CPython",
+            platform_release: "",
+            platform_system: "Linux",
+            platform_version: "",
+            python_full_version,
+            python_version: &python_version,
+            sys_platform: "linux",
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn key() {
+        assert_eq!(
+            keys(">=3.9,<3.12"),
+            ["python_version >=3.9", "python_version <3.12"]
+        );
+        assert_eq!(keys(">=3"), ["python_version >=3"]);
+        assert_eq!(keys("~=3.9"), ["python_version ~=3.9"]);
+        assert_eq!(keys("==3.9.*"), ["python_version ==3.9.*"]);
+        assert_eq!(keys("!=3.9.*"), ["python_version !=3.9.*"]);
+        // Micro versions.
+        assert_eq!(keys(">=3.9.2"), ["python_full_version >=3.9.2"]);
+        assert_eq!(keys("~=3.9.1"), ["python_full_version ~=3.9.1"]);
+        assert_eq!(keys("==3.9.1.*"), ["python_full_version ==3.9.1.*"]);
+        // Operators that depend on the micro version.
+        assert_eq!(keys("==3.9"), ["python_full_version ==3.9"]);
+        assert_eq!(keys("!=3.9"), ["python_full_version !=3.9"]);
+        assert_eq!(keys(">3.9"), ["python_full_version >3.9"]);
+        assert_eq!(keys("<=3.9"), ["python_full_version <=3.9"]);
+    }
+
+    #[test]
+    fn simplified() {
+        assert_eq!(marker("").try_to_string(), None);
+        assert_eq!(
+            marker("<3.11").try_to_string().unwrap(),
+            "python_full_version < '3.11'"
+        );
+        assert_eq!(
+            marker(">=3.9,<3.12").try_to_string().unwrap(),
+            "python_full_version >= '3.9' and python_full_version < '3.12'"
+        );
+        assert_eq!(
+            marker(">=3.9,!=3.10.*").try_to_string().unwrap(),
+            "python_full_version == '3.9.*' or python_full_version >= '3.11'"
+        );
+        assert!(marker(">=3.12,<3.9").is_false());
+    }
+
+    /// The marker matches a Python version if and only if the specifiers do.
+    #[test]
+    fn equivalent() {
+        let specifiers = [
+            ">=3.9,<3.12",
+            "<3.11",
+            "~=3.9",
+            "~=3.9.1",
+            "==3.9",
+            "==3.9.*",
+            "!=3.9",
+            "!=3.9.*",
+            ">3.9",
+            "<=3.9",
+            ">=3.9.2",
+            ">=3.9,!=3.9.1",
+        ];
+        let versions = [
+            "3.8.10", "3.9.0", "3.9.1", "3.9.2", "3.10.0", "3.11.9", "3.12.0", "3.13.0", "4.0.0",
+        ];
+        for specifiers in specifiers {
+            let marker = marker(specifiers);
+            let parsed = VersionSpecifiers::from_str(specifiers).unwrap();
+            for version in versions {
+                let expected = parsed.contains(&Version::from_str(version).unwrap());
+                assert_eq!(
+                    marker.evaluate(&environment(version), &[]),
+                    expected,
+                    "`{marker:?}` for `{specifiers}` on {version}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn unsupported() {
+        let error = |specifiers: &str| {
+            let specifiers = VersionSpecifiers::from_str(specifiers).unwrap();
+            python_marker(&specifiers).unwrap_err().to_string()
+        };
+        assert_eq!(
+            error("===3.9.1"),
+            "`===3.9.1` can't be expressed as a Python version marker, since arbitrary equality compares versions as strings"
+        );
+        assert_eq!(
+            error(">=3.9,<3.13.0rc1"),
+            "`<3.13.0rc1` can't be expressed as a Python version marker, since markers only compare the release segments of Python versions"
+        );
+        for specifiers in [">=3.9.post1", "<3.13.dev0", "==3.12+local", ">=1!3.9"] {
+            assert!(
+                error(specifiers).contains("only compare the release segments"),
+                "{specifiers}"
+            );
+        }
+    }
+}
@@ -264,9 +264,13 @@ impl ParsedDirectoryUrl {
 /// Explicit `lfs = true` or `--lfs` should be used to enable Git LFS support as
 /// we do not support implicit parsing of the `lfs=true` url fragments for now.
 ///
+/// Whether to check out submodules can be set with a `submodules=true` or `submodules=false`
+/// fragment.
+///
 /// Examples:
 /// * `git+https://git.example.com/MyProject.git`
 /// * `git+https://git.example.com/MyProject.git@v1.0#egg=pkg&subdirectory=pkg_dir`
+/// * `git+https://git.example.com/MyProject.git#submodules=false`
 #[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Hash, Ord)]
 pub struct ParsedGitUrl {
     pub url: GitUrl,
@@ -289,6 +293,7 @@ impl TryFrom<DisplaySafeUrl> for ParsedGitUrl {
     /// excluded, it's presumed to come from `tool.uv.sources`.
     fn try_from(url_in: DisplaySafeUrl) -> Result<Self, Self::Error> {
         let subdirectory = get_subdirectory(&url_in).map(PathBuf::into_boxed_path);
+        let submodules = get_submodules(&url_in);
 
         let url = url_in
             .as_str()
@@ -296,7 +301,7 @@ impl TryFrom<DisplaySafeUrl> for ParsedGitUrl {
             .unwrap_or(url_in.as_str());
         let url = DisplaySafeUrl::parse(url)
             .map_err(|err| ParsedUrlError::UrlParse(url.to_string(), err))?;
-        let url = GitUrl::try_from(url)?;
+        let url = GitUrl::try_from(url)?.with_submodules(submodules);
         Ok(Self { url, subdirectory })
     }
 }
@@ -368,6 +373,18 @@ fn get_subdirectory(url: &Url) -> Option<PathBuf> {
     Some(PathBuf::from(subdirectory))
 }
 
+/// Whether to check out submodules, from the `submodules` fragment of a Git URL, if set.
+fn get_submodules(url: &Url) -> Option<bool> {
+    let fragment = url.fragment()?;
+    fragment
+        .split('&')
+        .find_map(|fragment| match fragment.strip_prefix("submodules=")? {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        })
+}
+
 impl TryFrom<DisplaySafeUrl> for ParsedUrl {
     type Error = ParsedUrlError;
 
@@ -531,6 +548,7 @@ impl From<ParsedArchiveUrl> for DisplaySafeUrl {
 impl From<ParsedGitUrl> for DisplaySafeUrl {
     fn from(value: ParsedGitUrl) -> Self {
         let lfs = value.url.lfs().enabled();
+        let submodules = value.url.submodules();
         let mut url = Self::parse(&format!("{}{}", "git+", Self::from(value.url).as_str()))
             .expect("Git URL is invalid");
         let mut frags: Vec<String> = Vec::new();
@@ -541,6 +559,9 @@ impl From<ParsedGitUrl> for DisplaySafeUrl {
         if lfs {
             frags.push("lfs=true".to_string());
         }
+        if let Some(submodules) = submodules {
+            frags.push(format!("submodules={submodules}"));
+        }
         if !frags.is_empty() {
             url.set_fragment(Some(&frags.join("&")));
         }
@@ -583,6 +604,12 @@ mod tests {
         let actual = DisplaySafeUrl::from(ParsedUrl::try_from(expected.clone())?);
         assert_ne!(expected, actual);
 
+        let expected = DisplaySafeUrl::parse(
+            "git+https://github.com/pallets/flask.git@2.0.0#subdirectory=pkg_dir&submodules=false",
+        )?;
+        let actual = DisplaySafeUrl::from(ParsedUrl::try_from(expected.clone())?);
+        assert_eq!(expected, actual);
+
         // TODO(charlie): Preserve other fragments.
         let expected = DisplaySafeUrl::parse(
             "git+https://github.com/pallets/flask.git#egg=flask&subdirectory=pkg_dir",
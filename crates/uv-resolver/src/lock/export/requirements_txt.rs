@@ -93,7 +93,8 @@ impl std::fmt::Display for RequirementsTxtExport<'_> {
                         git.precise,
                         git.lfs,
                     )
-                    .expect("Internal Git URLs must have supported schemes");
+                    .expect("Internal Git URLs must have supported schemes")
+                    .with_submodules(git.submodules);
 
                     // Reconstruct the PEP 508-compatible URL from the `GitSource`.
                     let url = DisplaySafeUrl::from(ParsedGitUrl {
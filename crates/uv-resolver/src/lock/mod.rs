@@ -3017,7 +3017,8 @@ impl Package {
                     GitReference::from(git.kind.clone()),
                     git.precise,
                     git.lfs,
-                )?;
+                )?
+                .with_submodules(git.submodules);
 
                 // Reconstruct the PEP 508-compatible URL from the `GitSource`.
                 let url = DisplaySafeUrl::from(ParsedGitUrl {
@@ -3888,6 +3889,7 @@ impl Source {
                 }),
                 subdirectory: git_dist.subdirectory.clone(),
                 lfs: git_dist.git.lfs(),
+                submodules: git_dist.git.submodules(),
             },
         )
     }
@@ -4204,6 +4206,7 @@ struct GitSource {
     subdirectory: Option<Box<Path>>,
     kind: GitSourceKind,
     lfs: GitLfs,
+    submodules: Option<bool>,
 }
 
 /// An error that occurs when a source string could not be parsed.
@@ -4220,6 +4223,7 @@ impl GitSource {
         let mut kind = GitSourceKind::DefaultBranch;
         let mut subdirectory = None;
         let mut lfs = GitLfs::Disabled;
+        let mut submodules = None;
         for (key, val) in url.query_pairs() {
             match &*key {
                 "tag" => kind = GitSourceKind::Tag(val.into_owned()),
@@ -4227,6 +4231,7 @@ impl GitSource {
                 "rev" => kind = GitSourceKind::Rev(val.into_owned()),
                 "subdirectory" => subdirectory = Some(PortablePathBuf::from(val.as_ref()).into()),
                 "lfs" => lfs = GitLfs::from(val.eq_ignore_ascii_case("true")),
+                "submodules" => submodules = Some(val.eq_ignore_ascii_case("true")),
                 _ => {}
             }
         }
@@ -4239,6 +4244,7 @@ impl GitSource {
             subdirectory,
             kind,
             lfs,
+            submodules,
         })
     }
 }
@@ -4632,6 +4638,12 @@ fn locked_git_url(git_dist: &GitSourceDist) -> DisplaySafeUrl {
         url.query_pairs_mut().append_pair("lfs", "true");
     }
 
+    // Put the submodule setting in the query only when set for the repository specifically.
+    if let Some(submodules) = git_dist.git.submodules() {
+        url.query_pairs_mut()
+            .append_pair("submodules", if submodules { "true" } else { "false" });
+    }
+
     // Put the requested reference in the query.
     match git_dist.git.reference() {
         GitReference::Branch(branch) => {
@@ -5388,6 +5400,7 @@ fn normalize_requirement(
                     git.precise(),
                     git.lfs(),
                 )?
+                .with_submodules(git.submodules())
             };
 
             // Reconstruct the PEP 508 URL from the underlying data.
@@ -6573,6 +6586,7 @@ pub(crate) fn is_wheel_unreachable(
 
 #[cfg(test)]
 mod tests {
+    use uv_git_types::HashKind;
     use uv_warnings::anstream;
 
     use super::*;
@@ -6896,6 +6910,33 @@ source = { editable = "path/to/dir" }
         insta::assert_debug_snapshot!(result);
     }
 
+    /// Git sources pinned to a SHA-256 object ID should round-trip.
+    #[test]
+    fn source_git_sha256() {
+        let data = r#"
+version = 1
+requires-python = ">=3.12"
+
+[[package]]
+name = "anyio"
+version = "4.3.0"
+source = { git = "https://github.com/agronholm/anyio?rev=4.3.0#4a23745badf5bf5ef7928f1e346e9986bd696d824a23745badf5bf5ef7928f1e" }
+"#;
+        let lock: Lock = toml::from_str(data).unwrap();
+        let Source::Git(_, git) = &lock.packages[0].id.source else {
+            panic!("expected a Git source");
+        };
+        assert_eq!(git.precise.hash_kind(), HashKind::Sha256);
+
+        let serialized = lock.to_toml().unwrap();
+        assert!(
+            serialized
+                .contains("#4a23745badf5bf5ef7928f1e346e9986bd696d824a23745badf5bf5ef7928f1e")
+        );
+        let roundtrip: Lock = toml::from_str(&serialized).unwrap();
+        assert_eq!(roundtrip.packages, lock.packages);
+    }
+
     /// Windows drive letter paths like `C:/...` should be deserialized as local path registry
     /// sources, not as URLs. The `C:` prefix must not be misinterpreted as a URL scheme.
     #[test]
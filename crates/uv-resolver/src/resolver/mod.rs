@@ -2979,7 +2979,7 @@ impl ForkState {
 
             if let Some(name) = self.pubgrub.package_store[for_package]
                 .name_no_root()
-                .filter(|name| !workspace_members.contains(name))
+                .filter(|name| !workspace_members.contains(*name))
             {
                 debug!(
                     "Adding transitive dependency for {name}=={for_version}: {package}{version}"
@@ -907,6 +907,14 @@ impl EnvVars {
     #[attr_added_in("0.9.15")]
     pub const GIT_CONFIG_GLOBAL: &'static str = "GIT_CONFIG_GLOBAL";
 
+    /// The number of configuration entries that Git reads from `GIT_CONFIG_KEY_<n>` and
+    /// `GIT_CONFIG_VALUE_<n>`.
+    ///
+    /// Used to pass credentials for a submodule fetch to Git without persisting them.
+    #[attr_hidden]
+    #[attr_added_in("next release")]
+    pub const GIT_CONFIG_COUNT: &'static str = "GIT_CONFIG_COUNT";
+
     /// Used in tests for better git isolation.
     ///
     /// For example, we run some tests in ~/.local/share/uv/tests.
@@ -0,0 +1,24 @@
+//! Parse arbitrary bytes as a `pyproject.toml`, both as the typed [`PyProjectToml`] and as the
+//! editable [`PyProjectTomlMut`].
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use uv_workspace::pyproject::{DependencyType, PyProjectToml};
+use uv_workspace::pyproject_mut::{DependencyTarget, PyProjectTomlMut};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(contents) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let _ = PyProjectToml::from_string(contents.to_string(), "pyproject.toml");
+
+    let Ok(pyproject) = PyProjectTomlMut::from_toml(contents, DependencyTarget::PyProjectToml)
+    else {
+        return;
+    };
+    let _ = pyproject.pyproject_toml();
+    let _ = pyproject.dependencies(&DependencyType::Production);
+});
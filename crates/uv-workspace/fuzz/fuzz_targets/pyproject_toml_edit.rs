@@ -0,0 +1,117 @@
+//! Apply an arbitrary sequence of dependency additions and removals to a `pyproject.toml`, and
+//! check that the result is always valid TOML.
+
+#![no_main]
+
+use std::str::FromStr;
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use toml_edit::DocumentMut;
+
+use uv_normalize::{ExtraName, GroupName, PackageName};
+use uv_pep508::Requirement;
+use uv_workspace::pyproject_mut::{DependencyTarget, PyProjectTomlMut};
+
+/// The document to edit, if the input isn't a valid TOML document.
+const FALLBACK: &str = r#"[project]
+name = "albatross"
+version = "0.1.0"
+dependencies = [
+    "anyio>=4", # comment
+    "flask",
+]
+
+[project.optional-dependencies]
+cli = ["rich"]
+
+[dependency-groups]
+dev = [{ include-group = "lint" }, "pytest"]
+lint = ["ruff"]
+
+[tool.uv]
+dev-dependencies = []
+
+[tool.uv.sources]
+anyio = { path = "packages/anyio" }
+"#;
+
+#[derive(Debug, Arbitrary)]
+enum Section {
+    Production,
+    Dev,
+    Optional,
+    Group,
+}
+
+#[derive(Debug, Arbitrary)]
+enum Operation {
+    Add {
+        section: Section,
+        requirement: String,
+    },
+    Remove {
+        section: Section,
+        name: String,
+    },
+}
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    document: String,
+    operations: Vec<Operation>,
+}
+
+fuzz_target!(|input: Input| {
+    let mut pyproject =
+        match PyProjectTomlMut::from_toml(&input.document, DependencyTarget::PyProjectToml) {
+            Ok(pyproject) => pyproject,
+            Err(_) => PyProjectTomlMut::from_toml(FALLBACK, DependencyTarget::PyProjectToml)
+                .expect("fallback document is valid"),
+        };
+
+    let extra = ExtraName::from_str("cli").unwrap();
+    let group = GroupName::from_str("dev").unwrap();
+
+    for operation in input.operations {
+        match operation {
+            Operation::Add {
+                section,
+                requirement,
+            } => {
+                let Ok(requirement) = Requirement::from_str(&requirement) else {
+                    continue;
+                };
+                let _ = match section {
+                    Section::Production => pyproject.add_dependency(&requirement, None, false),
+                    Section::Dev => pyproject.add_dev_dependency(&requirement, None, false),
+                    Section::Optional => {
+                        pyproject.add_optional_dependency(&extra, &requirement, None, false)
+                    }
+                    Section::Group => pyproject.add_dependency_group_requirement(
+                        &group,
+                        &requirement,
+                        None,
+                        false,
+                    ),
+                };
+            }
+            Operation::Remove { section, name } => {
+                let Ok(name) = PackageName::from_str(&name) else {
+                    continue;
+                };
+                let _ = match section {
+                    Section::Production => pyproject.remove_dependency(&name),
+                    Section::Dev => pyproject.remove_dev_dependency(&name),
+                    Section::Optional => pyproject.remove_optional_dependency(&name, &extra),
+                    Section::Group => pyproject.remove_dependency_group_requirement(&name, &group),
+                };
+            }
+        }
+
+        let contents = pyproject.to_string();
+        if let Err(err) = contents.parse::<DocumentMut>() {
+            panic!("edited document is not valid TOML: {err}\n{contents}");
+        }
+    }
+});
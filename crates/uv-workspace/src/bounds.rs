@@ -0,0 +1,388 @@
+//! Linting dependencies that lack a meaningful lower bound.
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use uv_normalize::PackageName;
+use uv_pep440::{Operator, VersionSpecifier};
+use uv_pep508::{Requirement, VersionOrUrl};
+use uv_pypi_types::{DependencyGroupSpecifier, VerbatimParsedUrl};
+
+use crate::pyproject::{DependencyType, Source};
+use crate::pyproject_mut::section;
+use crate::{Workspace, WorkspaceMember};
+
+/// Which dependencies [`Workspace::check_bounds`] reports.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum BoundsStrictness {
+    /// Report dependencies without any version specifiers, or with only an upper bound.
+    #[default]
+    Default,
+    /// Additionally report dependencies that only exclude versions, e.g., `requests!=2.0`.
+    Strict,
+}
+
+/// The way in which a dependency lacks a lower bound.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MissingBound {
+    /// The dependency has no version specifiers, e.g., `requests` or `numpy>=0`.
+    Unbounded,
+    /// The dependency only has an upper bound, e.g., `requests<3`.
+    UpperOnly,
+    /// The dependency only excludes versions, e.g., `requests!=2.0`.
+    ExclusionsOnly,
+}
+
+/// A dependency of a workspace member that lacks a meaningful lower bound.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundsDiagnostic {
+    /// The workspace member declaring the dependency.
+    pub member: PackageName,
+    /// The section declaring the dependency.
+    pub dependency_type: DependencyType,
+    /// The index of the dependency in its section.
+    pub index: usize,
+    /// The requirement, as written.
+    pub requirement: String,
+    /// The name of the dependency.
+    pub name: PackageName,
+    /// The way in which the dependency lacks a lower bound.
+    pub kind: MissingBound,
+}
+
+impl BoundsDiagnostic {
+    /// Returns the section declaring the dependency, e.g., `project.dependencies`.
+    pub fn section(&self) -> String {
+        section(&self.dependency_type)
+    }
+}
+
+impl Display for BoundsDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let problem = match self.kind {
+            MissingBound::Unbounded => "has no lower bound",
+            MissingBound::UpperOnly => "only has an upper bound",
+            MissingBound::ExclusionsOnly => "only excludes versions",
+        };
+        write!(
+            f,
+            "`{}` in `{}` {problem}",
+            self.requirement,
+            self.section()
+        )
+    }
+}
+
+impl Workspace {
+    /// Find the dependencies of the workspace members that lack a meaningful lower bound.
+    ///
+    /// Dependencies on URLs, and dependencies with a Git, URL, path, or workspace source in
+    /// `tool.uv.sources`, are exempt. Entries that aren't valid requirements are skipped.
+    pub fn check_bounds(&self, strictness: BoundsStrictness) -> Vec<BoundsDiagnostic> {
+        let mut diagnostics = Vec::new();
+        for (name, member) in self.packages() {
+            for (dependency_type, index, requirement) in member_requirements(member) {
+                let Ok(parsed) = Requirement::<VerbatimParsedUrl>::from_str(&requirement) else {
+                    continue;
+                };
+                let specifiers: &[VersionSpecifier] = match &parsed.version_or_url {
+                    Some(VersionOrUrl::Url(_)) => continue,
+                    Some(VersionOrUrl::VersionSpecifier(specifiers)) => specifiers,
+                    None => &[],
+                };
+                let Some(kind) = missing_bound(specifiers) else {
+                    continue;
+                };
+                if kind == MissingBound::ExclusionsOnly && strictness != BoundsStrictness::Strict {
+                    continue;
+                }
+                if self.has_direct_source(member, &parsed.name) {
+                    continue;
+                }
+                diagnostics.push(BoundsDiagnostic {
+                    member: name.clone(),
+                    dependency_type,
+                    index,
+                    requirement,
+                    name: parsed.name,
+                    kind,
+                });
+            }
+        }
+        diagnostics
+    }
+
+    /// Returns `true` if the member declares the dependency with a source other than an index,
+    /// falling back to the sources of the workspace root.
+    fn has_direct_source(&self, member: &WorkspaceMember, dependency: &PackageName) -> bool {
+        let sources = member
+            .pyproject_toml()
+            .tool
+            .as_ref()
+            .and_then(|tool| tool.uv.as_ref())
+            .and_then(|uv| uv.sources.as_ref())
+            .and_then(|sources| sources.inner().get(dependency))
+            .or_else(|| self.sources().get(dependency));
+        sources.is_some_and(|sources| {
+            sources.iter().any(|source| {
+                !matches!(
+                    source,
+                    Source::Registry { .. }
+                        | Source::Workspace {
+                            workspace: false,
+                            ..
+                        }
+                )
+            })
+        })
+    }
+}
+
+/// Collect the requirement strings of a workspace member, along with their section and index.
+pub(crate) fn member_requirements(
+    member: &WorkspaceMember,
+) -> Vec<(DependencyType, usize, String)> {
+    let mut requirements = Vec::new();
+    let project = member.project();
+    for (index, requirement) in project.dependencies.iter().flatten().enumerate() {
+        requirements.push((DependencyType::Production, index, requirement.clone()));
+    }
+    for (extra, extra_requirements) in project.optional_dependencies.iter().flatten() {
+        for (index, requirement) in extra_requirements.iter().enumerate() {
+            requirements.push((
+                DependencyType::Optional(extra.clone()),
+                index,
+                requirement.clone(),
+            ));
+        }
+    }
+    for (group, specifiers) in member.pyproject_toml().dependency_groups.iter().flatten() {
+        for (index, specifier) in specifiers.iter().enumerate() {
+            if let DependencyGroupSpecifier::Requirement(requirement) = specifier {
+                requirements.push((
+                    DependencyType::Group(group.clone()),
+                    index,
+                    requirement.clone(),
+                ));
+            }
+        }
+    }
+    let dev_dependencies = member
+        .pyproject_toml()
+        .tool
+        .as_ref()
+        .and_then(|tool| tool.uv.as_ref())
+        .and_then(|uv| uv.dev_dependencies.as_ref());
+    for (index, requirement) in dev_dependencies.into_iter().flatten().enumerate() {
+        requirements.push((DependencyType::Dev, index, requirement.to_string()));
+    }
+    requirements
+}
+
+/// Returns the way in which the specifiers lack a lower bound, if they do.
+fn missing_bound(specifiers: &[VersionSpecifier]) -> Option<MissingBound> {
+    let mut upper = false;
+    let mut exclusions = false;
+    for specifier in specifiers {
+        if is_trivial_lower_bound(specifier) {
+            continue;
+        }
+        match specifier.operator() {
+            Operator::Equal
+            | Operator::EqualStar
+            | Operator::ExactEqual
+            | Operator::TildeEqual
+            | Operator::GreaterThan
+            | Operator::GreaterThanEqual => return None,
+            Operator::LessThan | Operator::LessThanEqual => upper = true,
+            Operator::NotEqual | Operator::NotEqualStar => exclusions = true,
+        }
+    }
+    if upper {
+        Some(MissingBound::UpperOnly)
+    } else if exclusions {
+        Some(MissingBound::ExclusionsOnly)
+    } else {
+        Some(MissingBound::Unbounded)
+    }
+}
+
+/// Returns `true` if the specifier is a lower bound that admits every stable version, e.g.,
+/// `>=0` or `>=0.0`.
+pub(crate) fn is_trivial_lower_bound(specifier: &VersionSpecifier) -> bool {
+    *specifier.operator() == Operator::GreaterThanEqual
+        && specifier.version().is_stable()
+        && !specifier.version().is_post()
+        && !specifier.version().is_local()
+        && specifier
+            .version()
+            .release()
+            .iter()
+            .all(|segment| *segment == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::str::FromStr;
+
+    use anyhow::Result;
+    use assert_fs::fixture::ChildPath;
+    use assert_fs::prelude::*;
+    use insta::assert_snapshot;
+
+    use uv_normalize::PackageName;
+    use uv_pep440::Version;
+
+    use crate::diagnostic::Diagnostic;
+    use crate::pyproject_mut::{DependencyTarget, PyProjectTomlMut};
+    use crate::{BoundsStrictness, DiscoveryOptions, Workspace, WorkspaceCache};
+
+    async fn workspace(root: &ChildPath, body: &str) -> Result<Workspace> {
+        root.child("pyproject.toml").write_str(&format!(
+            "[project]\nname = \"albatross\"\nversion = \"0.1.0\"\n{body}"
+        ))?;
+        Ok(Workspace::discover(
+            root.path(),
+            &DiscoveryOptions::default(),
+            &WorkspaceCache::default(),
+        )
+        .await?)
+    }
+
+    const BODY: &str = r#"
+dependencies = [
+    "requests",
+    "numpy>=0",
+    "anyio<5",
+    "tqdm>=4,<5",
+    "rich!=13.0",
+    "sniffio==1.3.*",
+    "attrs ; python_version < '3.12'",
+    "idna @ https://example.com/idna-3.7-py3-none-any.whl",
+    "bird",
+    "flask",
+    "torch",
+]
+
+[project.optional-dependencies]
+cli = ["click~=8.1", "typer<=0.12"]
+
+[dependency-groups]
+dev = ["pytest", { include-group = "lint" }]
+lint = ["ruff>=0.4"]
+
+[tool.uv.sources]
+bird = { path = "../bird" }
+flask = { git = "https://github.com/pallets/flask" }
+torch = { index = "pytorch" }
+"#;
+
+    #[tokio::test]
+    async fn check_bounds() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+        let workspace = workspace(&root, BODY).await?;
+
+        let diagnostics = workspace.check_bounds(BoundsStrictness::Default);
+        let rendered = diagnostics
+            .iter()
+            .map(|diagnostic| format!("{}[{}]: {diagnostic}", diagnostic.member, diagnostic.index))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_snapshot!(rendered, @r"
+        albatross[0]: `requests` in `project.dependencies` has no lower bound
+        albatross[1]: `numpy>=0` in `project.dependencies` has no lower bound
+        albatross[2]: `anyio<5` in `project.dependencies` only has an upper bound
+        albatross[6]: `attrs ; python_version < '3.12'` in `project.dependencies` has no lower bound
+        albatross[10]: `torch` in `project.dependencies` has no lower bound
+        albatross[1]: `typer<=0.12` in `project.optional-dependencies.cli` only has an upper bound
+        albatross[0]: `pytest` in `dependency-groups.dev` has no lower bound
+        ");
+
+        let diagnostic = Diagnostic::from(&diagnostics[2]);
+        assert_eq!(diagnostic.key.as_deref(), Some("project.dependencies[2]"));
+        assert_eq!(
+            diagnostic.help.as_deref(),
+            Some("Add a lower bound, e.g., `anyio>=<version>`")
+        );
+
+        // Exclusions are only reported when strict.
+        let strict = workspace.check_bounds(BoundsStrictness::Strict);
+        assert_eq!(strict.len(), diagnostics.len() + 1);
+        assert!(
+            strict
+                .iter()
+                .any(|diagnostic| diagnostic.requirement == "rich!=13.0")
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn fix_bounds() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+        let workspace = workspace(&root, BODY).await?;
+        let diagnostics = workspace.check_bounds(BoundsStrictness::Strict);
+
+        let versions = BTreeMap::from(
+            [
+                ("requests", "2.32.3"),
+                ("numpy", "2.0.0"),
+                ("anyio", "4.4.0"),
+                ("rich", "13.7.1"),
+                ("pytest", "8.2.2"),
+            ]
+            .map(|(name, version)| {
+                (
+                    PackageName::from_str(name).unwrap(),
+                    Version::from_str(version).unwrap(),
+                )
+            }),
+        );
+        let workspace_pyproject = root.child("pyproject.toml");
+        let mut pyproject = PyProjectTomlMut::from_toml(
+            &fs_err::read_to_string(&workspace_pyproject)?,
+            DependencyTarget::PyProjectToml,
+        )?;
+        assert_eq!(pyproject.fix_bounds(&diagnostics, &versions)?, 5);
+
+        let contents = pyproject.to_string();
+        for fixed in [
+            "requests>=2.32.3",
+            "numpy>=2.0.0",
+            "anyio>=4.4.0,<5",
+            "rich!=13.0,>=13.7.1",
+            "pytest>=8.2.2",
+            // Dependencies without a known version are left as-is.
+            "torch",
+            "typer<=0.12",
+        ] {
+            assert!(
+                contents.contains(&format!("\"{fixed}\"")),
+                "missing `{fixed}`"
+            );
+        }
+
+        // A fixed file has no remaining diagnostics for the fixed dependencies.
+        workspace_pyproject.write_str(&contents)?;
+        let workspace = Workspace::discover(
+            root.path(),
+            &DiscoveryOptions::default(),
+            &WorkspaceCache::default(),
+        )
+        .await?;
+        let remaining = workspace
+            .check_bounds(BoundsStrictness::Strict)
+            .into_iter()
+            .map(|diagnostic| diagnostic.requirement)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            remaining,
+            ["attrs ; python_version < '3.12'", "torch", "typer<=0.12"]
+        );
+
+        Ok(())
+    }
+}
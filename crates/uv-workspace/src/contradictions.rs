@@ -0,0 +1,292 @@
+//! Linting dependencies whose version specifiers can't be satisfied by any version.
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use itertools::Itertools;
+use version_ranges::Ranges;
+
+use uv_normalize::PackageName;
+use uv_pep440::{Operator, Version, VersionSpecifier};
+use uv_pep508::{Requirement, VersionOrUrl};
+use uv_pypi_types::VerbatimParsedUrl;
+
+use crate::Workspace;
+use crate::bounds::member_requirements;
+use crate::pyproject::DependencyType;
+use crate::pyproject_mut::section;
+
+/// The reason why a set of version specifiers can't be satisfied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Contradiction {
+    /// Two specifiers pin different versions, e.g., `==1.0` and `==2.0`.
+    ConflictingPins(VersionSpecifier, VersionSpecifier),
+    /// A specifier excludes the pinned version, e.g., `==1.0` and `>=2`, or `==1.0` and `!=1.0`.
+    ExcludedPin {
+        pin: VersionSpecifier,
+        other: VersionSpecifier,
+    },
+    /// A lower bound is at or above an upper bound, e.g., `>=2` and `<2`, or `>2` and `<=2`.
+    InvertedRange {
+        lower: VersionSpecifier,
+        upper: VersionSpecifier,
+    },
+    /// Two specifiers don't have any versions in common, e.g., `==1.*` and `==2.*`.
+    Disjoint(VersionSpecifier, VersionSpecifier),
+    /// Every pair of specifiers has versions in common, but all specifiers together don't,
+    /// e.g., `>=1`, `<2`, and `!=1.*`.
+    Combined(Vec<VersionSpecifier>),
+}
+
+impl Contradiction {
+    /// Returns the reason why the specifiers can't be satisfied, if they can't.
+    ///
+    /// The specifiers are interpreted with PEP 440 semantics, as in resolution, so a returned
+    /// contradiction is never spurious: no version, including pre-releases, post-releases, and
+    /// local versions, satisfies the specifiers.
+    pub fn find(specifiers: &[VersionSpecifier]) -> Option<Self> {
+        let ranges = specifiers
+            .iter()
+            .map(|specifier| Ranges::<Version>::from(specifier.clone()))
+            .collect::<Vec<_>>();
+        let combined = ranges.iter().fold(Ranges::full(), |combined, range| {
+            combined.intersection(range)
+        });
+        if !combined.is_empty() {
+            return None;
+        }
+
+        // Explain the contradiction with a pair of specifiers, if we can.
+        let pair = ranges
+            .iter()
+            .enumerate()
+            .tuple_combinations()
+            .find(|((_, a), (_, b))| a.intersection(b).is_empty())
+            .map(|((a, _), (b, _))| (&specifiers[a], &specifiers[b]));
+        let Some((a, b)) = pair else {
+            return Some(Self::Combined(specifiers.to_vec()));
+        };
+        let (a, b) = (a.clone(), b.clone());
+        Some(match (kind(*a.operator()), kind(*b.operator())) {
+            (Kind::Pin, Kind::Pin) => Self::ConflictingPins(a, b),
+            (Kind::Pin, _) => Self::ExcludedPin { pin: a, other: b },
+            (_, Kind::Pin) => Self::ExcludedPin { pin: b, other: a },
+            (Kind::Lower, Kind::Upper) => Self::InvertedRange { lower: a, upper: b },
+            (Kind::Upper, Kind::Lower) => Self::InvertedRange { lower: b, upper: a },
+            _ => Self::Disjoint(a, b),
+        })
+    }
+}
+
+impl Display for Contradiction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ConflictingPins(a, b) => write!(f, "`{a}` and `{b}` pin different versions"),
+            Self::ExcludedPin { pin, other } => {
+                write!(f, "the version pinned by `{pin}` is excluded by `{other}`")
+            }
+            Self::InvertedRange { lower, upper } => {
+                write!(
+                    f,
+                    "the lower bound `{lower}` isn't below the upper bound `{upper}`"
+                )
+            }
+            Self::Disjoint(a, b) => write!(f, "`{a}` and `{b}` have no versions in common"),
+            Self::Combined(specifiers) => write!(
+                f,
+                "no version satisfies all of `{}`",
+                specifiers.iter().join(", ")
+            ),
+        }
+    }
+}
+
+/// The role of a specifier in a [`Contradiction`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Kind {
+    /// A single version, e.g., `==1.0`.
+    Pin,
+    /// A lower bound, e.g., `>=1.0`.
+    Lower,
+    /// An upper bound, e.g., `<2.0`.
+    Upper,
+    /// Anything else, e.g., `~=1.0`, `==1.*`, or `!=1.0`.
+    Other,
+}
+
+fn kind(operator: Operator) -> Kind {
+    match operator {
+        Operator::Equal | Operator::ExactEqual => Kind::Pin,
+        Operator::GreaterThan | Operator::GreaterThanEqual => Kind::Lower,
+        Operator::LessThan | Operator::LessThanEqual => Kind::Upper,
+        Operator::TildeEqual
+        | Operator::EqualStar
+        | Operator::NotEqual
+        | Operator::NotEqualStar => Kind::Other,
+    }
+}
+
+/// A dependency of a workspace member whose version specifiers can't be satisfied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContradictionDiagnostic {
+    /// The workspace member declaring the dependency.
+    pub member: PackageName,
+    /// The section declaring the dependency.
+    pub dependency_type: DependencyType,
+    /// The index of the dependency in its section.
+    pub index: usize,
+    /// The requirement, as written.
+    pub requirement: String,
+    /// The name of the dependency.
+    pub name: PackageName,
+    /// The reason why the version specifiers can't be satisfied.
+    pub contradiction: Contradiction,
+}
+
+impl ContradictionDiagnostic {
+    /// Returns the section declaring the dependency, e.g., `project.dependencies`.
+    pub fn section(&self) -> String {
+        section(&self.dependency_type)
+    }
+}
+
+impl Display for ContradictionDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}` in `{}` can't be satisfied by any version: {}",
+            self.requirement,
+            self.section(),
+            self.contradiction
+        )
+    }
+}
+
+impl Workspace {
+    /// Find the dependencies of the workspace members whose version specifiers can't be
+    /// satisfied by any version, e.g., `foo>=2,<2` or `bar==1.0,==2.0`.
+    ///
+    /// The check is structural, rather than against the available versions, and may miss some
+    /// contradictions (e.g., between arbitrary equality and other specifiers), but never reports
+    /// a satisfiable requirement. Entries that aren't valid requirements are skipped.
+    pub fn check_contradictions(&self) -> Vec<ContradictionDiagnostic> {
+        let mut diagnostics = Vec::new();
+        for (name, member) in self.packages() {
+            for (dependency_type, index, requirement) in member_requirements(member) {
+                let Ok(parsed) = Requirement::<VerbatimParsedUrl>::from_str(&requirement) else {
+                    continue;
+                };
+                let Some(VersionOrUrl::VersionSpecifier(specifiers)) = &parsed.version_or_url
+                else {
+                    continue;
+                };
+                let Some(contradiction) = Contradiction::find(specifiers) else {
+                    continue;
+                };
+                diagnostics.push(ContradictionDiagnostic {
+                    member: name.clone(),
+                    dependency_type,
+                    index,
+                    requirement,
+                    name: parsed.name,
+                    contradiction,
+                });
+            }
+        }
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use anyhow::Result;
+    use assert_fs::fixture::ChildPath;
+    use assert_fs::prelude::*;
+    use insta::assert_snapshot;
+    use itertools::Itertools;
+
+    use uv_pep440::VersionSpecifiers;
+
+    use crate::diagnostic::Diagnostic;
+    use crate::{DiscoveryOptions, Workspace, WorkspaceCache};
+
+    use super::Contradiction;
+
+    fn find(specifiers: &str) -> Option<String> {
+        let specifiers = VersionSpecifiers::from_str(specifiers).unwrap();
+        Contradiction::find(&specifiers).map(|contradiction| contradiction.to_string())
+    }
+
+    #[test]
+    fn contradictions() {
+        assert_snapshot!(find(">=2,<2").unwrap(), @"the lower bound `>=2` isn't below the upper bound `<2`");
+        assert_snapshot!(find("<1,>3").unwrap(), @"the lower bound `>3` isn't below the upper bound `<1`");
+        assert_snapshot!(find(">2,<=2").unwrap(), @"the lower bound `>2` isn't below the upper bound `<=2`");
+        assert_snapshot!(find("==1.0,==2.0").unwrap(), @"`==1.0` and `==2.0` pin different versions");
+        assert_snapshot!(find("==1.0,>=2").unwrap(), @"the version pinned by `==1.0` is excluded by `>=2`");
+        assert_snapshot!(find("!=1.0,==1.0").unwrap(), @"the version pinned by `==1.0` is excluded by `!=1.0`");
+        // `>1.0` excludes local versions of `1.0`, e.g., `1.0+local`.
+        assert_snapshot!(find("==1.0,>1.0").unwrap(), @"the version pinned by `==1.0` is excluded by `>1.0`");
+        assert_snapshot!(find("==1.*,==2.*").unwrap(), @"`==1.*` and `==2.*` have no versions in common");
+        assert_snapshot!(find(">=1,<2,!=1.*").unwrap(), @"no version satisfies all of `>=1, !=1.*, <2`");
+    }
+
+    /// Specifiers that look contradictory, but admit some version, aren't reported.
+    #[test]
+    fn satisfiable() {
+        // Pre-releases, post-releases, and local versions.
+        assert_eq!(find(">=2.0a1,<2.0b1"), None);
+        assert_eq!(find(">2.0,<2.0.1"), None);
+        assert_eq!(find(">=2.0.post1,<=2.0.post1"), None);
+        assert_eq!(find("==1.0,<=1.0"), None);
+        // Different spellings of the same version.
+        assert_eq!(find("==1,==1.0.0"), None);
+        assert_eq!(find("===1.0,==1.0"), None);
+        assert_eq!(find("~=1.4,==1.*"), None);
+        assert_eq!(find(">=1,<=1"), None);
+        assert_eq!(find(""), None);
+    }
+
+    #[tokio::test]
+    async fn check_contradictions() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+        root.child("pyproject.toml").write_str(
+            r#"
+            [project]
+            name = "albatross"
+            version = "0.1.0"
+            dependencies = ["anyio>=4,<4", "requests>=2"]
+
+            [project.optional-dependencies]
+            cli = ["click==8.0,==8.1", "idna @ https://example.com/idna-3.7-py3-none-any.whl"]
+
+            [dependency-groups]
+            dev = ["pytest", "ruff==0.4.*,<0.4"]
+            "#,
+        )?;
+        let workspace = Workspace::discover(
+            root.path(),
+            &DiscoveryOptions::default(),
+            &WorkspaceCache::default(),
+        )
+        .await?;
+
+        let diagnostics = workspace.check_contradictions();
+        assert_snapshot!(diagnostics.iter().join("\n"), @r"
+        `anyio>=4,<4` in `project.dependencies` can't be satisfied by any version: the lower bound `>=4` isn't below the upper bound `<4`
+        `click==8.0,==8.1` in `project.optional-dependencies.cli` can't be satisfied by any version: `==8.0` and `==8.1` pin different versions
+        `ruff==0.4.*,<0.4` in `dependency-groups.dev` can't be satisfied by any version: `==0.4.*` and `<0.4` have no versions in common
+        ");
+
+        let diagnostic = Diagnostic::from(&diagnostics[1]);
+        assert_eq!(
+            diagnostic.key.as_deref(),
+            Some("project.optional-dependencies.cli[0]")
+        );
+
+        Ok(())
+    }
+}
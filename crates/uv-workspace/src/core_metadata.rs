@@ -0,0 +1,391 @@
+//! The core metadata (as in `PKG-INFO` and `METADATA`) that can be derived from the static
+//! `project` table of each workspace member, without invoking a build backend.
+//!
+//! See <https://packaging.python.org/en/latest/specifications/core-metadata/>.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use indexmap::IndexMap;
+use itertools::Itertools;
+use serde::Deserialize;
+
+use uv_normalize::{ExtraName, PackageName};
+use uv_pep508::{ExtraOperator, MarkerExpression, MarkerTree, MarkerValueExtra, Requirement};
+use uv_pypi_types::{Keywords, Metadata23, ProjectUrls, VerbatimParsedUrl};
+
+use crate::pyproject::PyprojectTomlError;
+use crate::{Workspace, WorkspaceError, WorkspaceMember};
+
+/// The core metadata fields that each `project` key in `project.dynamic` is written to.
+///
+/// The `readme` and `license-files` keys aren't derived statically, since they refer to files, but
+/// are still listed as `Dynamic` when declared as such.
+const DYNAMIC_FIELDS: &[(&str, &[&str])] = &[
+    ("description", &["Summary"]),
+    ("readme", &["Description", "Description-Content-Type"]),
+    ("requires-python", &["Requires-Python"]),
+    ("license", &["License", "License-Expression"]),
+    ("license-files", &["License-File"]),
+    ("authors", &["Author", "Author-email"]),
+    ("maintainers", &["Maintainer", "Maintainer-email"]),
+    ("keywords", &["Keywords"]),
+    ("classifiers", &["Classifier"]),
+    ("urls", &["Project-URL"]),
+    ("dependencies", &["Requires-Dist"]),
+    (
+        "optional-dependencies",
+        &["Requires-Dist", "Provides-Extra"],
+    ),
+];
+
+/// The `project` fields that are only needed for core metadata.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "kebab-case")]
+struct Project {
+    description: Option<String>,
+    license: Option<License>,
+    authors: Option<Vec<Contact>>,
+    maintainers: Option<Vec<Contact>>,
+    keywords: Option<Vec<String>>,
+    classifiers: Option<Vec<String>>,
+    urls: Option<IndexMap<String, String>>,
+    dynamic: Option<Vec<String>>,
+}
+
+/// The `project.license` key.
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum License {
+    /// An SPDX expression, as in PEP 639.
+    Spdx(String),
+    Text {
+        text: String,
+    },
+    /// A table referring to a license file, e.g., `{ file = "LICENSE" }`, which isn't read.
+    File(serde::de::IgnoredAny),
+}
+
+/// A `project.authors` or `project.maintainers` entry.
+#[derive(Deserialize, Debug)]
+#[serde(untagged, deny_unknown_fields)]
+enum Contact {
+    NameEmail { name: String, email: String },
+    Name { name: String },
+    Email { email: String },
+}
+
+impl Workspace {
+    /// Derive the core metadata of each workspace member from its static `project` table.
+    ///
+    /// `Name`, `Version`, `Summary`, `Requires-Python`, `Requires-Dist`, `Provides-Extra`, and the
+    /// `Author`, `Maintainer`, `License`, `Keywords`, `Classifier` and `Project-URL` fields are
+    /// derived from the `pyproject.toml`; fields declared in `project.dynamic` are listed as
+    /// `Dynamic` instead. The readme and the license files are omitted, since they need to be read
+    /// from disk. Use [`Metadata23::core_metadata_format`] to render the metadata as a `METADATA`
+    /// file.
+    ///
+    /// Returns an error if a member declares its version as dynamic, since core metadata requires
+    /// a static version.
+    pub fn to_core_metadata(&self) -> Result<BTreeMap<PackageName, Metadata23>, WorkspaceError> {
+        self.packages()
+            .iter()
+            .map(|(name, member)| Ok((name.clone(), core_metadata(name, member)?)))
+            .collect()
+    }
+}
+
+/// Derive the core metadata of a workspace member.
+fn core_metadata(
+    name: &PackageName,
+    member: &WorkspaceMember,
+) -> Result<Metadata23, WorkspaceError> {
+    #[derive(Deserialize)]
+    struct PyProjectToml {
+        #[serde(default)]
+        project: Project,
+    }
+
+    let pyproject_path = member.root().join("pyproject.toml");
+    let metadata = toml::from_str::<PyProjectToml>(&member.pyproject_toml().raw)
+        .map_err(|err| {
+            WorkspaceError::Toml(
+                pyproject_path.clone(),
+                Box::new(PyprojectTomlError::Toml(err)),
+            )
+        })?
+        .project;
+    let project = member.project();
+    let dynamic = metadata.dynamic.unwrap_or_default();
+    let is_static = |key: &str| !dynamic.iter().any(|field| field == key);
+
+    let Some(version) = project.version.as_ref().filter(|_| is_static("version")) else {
+        return Err(WorkspaceError::DynamicNotAllowed("project.version"));
+    };
+
+    let parse = |requirement: &str| {
+        Requirement::<VerbatimParsedUrl>::from_str(requirement).map_err(|err| {
+            WorkspaceError::DependencyParse(name.clone(), requirement.to_string(), Box::new(err))
+        })
+    };
+    let mut requires_dist = Vec::new();
+    if is_static("dependencies") {
+        for requirement in project.dependencies.iter().flatten() {
+            requires_dist.push(parse(requirement)?.to_string());
+        }
+    }
+    let mut provides_extra = Vec::new();
+    if is_static("optional-dependencies") {
+        for (extra, requirements) in project.optional_dependencies.iter().flatten() {
+            for requirement in requirements {
+                requires_dist.push(with_extra(parse(requirement)?, extra).to_string());
+            }
+            provides_extra.push(extra.to_string());
+        }
+    }
+
+    let (license, license_expression) = match metadata.license.filter(|_| is_static("license")) {
+        Some(License::Spdx(expression)) => (None, Some(expression)),
+        Some(License::Text { text }) => (Some(text), None),
+        Some(License::File { .. }) | None => (None, None),
+    };
+    let (author, author_email) =
+        contacts(metadata.authors.filter(|_| is_static("authors")).as_deref());
+    let (maintainer, maintainer_email) = contacts(
+        metadata
+            .maintainers
+            .filter(|_| is_static("maintainers"))
+            .as_deref(),
+    );
+
+    let dynamic = DYNAMIC_FIELDS
+        .iter()
+        .filter(|(key, _)| !is_static(key))
+        .flat_map(|(_, fields)| fields.iter())
+        .unique()
+        .map(ToString::to_string)
+        .collect();
+
+    Ok(Metadata23 {
+        // License expressions require metadata version 2.4 (PEP 639), and `Dynamic` requires 2.2.
+        metadata_version: if license_expression.is_some() {
+            "2.4"
+        } else {
+            "2.3"
+        }
+        .to_string(),
+        name: project.verbatim_name().as_verbatim().to_string(),
+        version: version.to_string(),
+        summary: metadata.description.filter(|_| is_static("description")),
+        keywords: metadata
+            .keywords
+            .filter(|_| is_static("keywords"))
+            .map(Keywords::new),
+        author,
+        author_email,
+        maintainer,
+        maintainer_email,
+        license,
+        license_expression,
+        classifiers: metadata
+            .classifiers
+            .filter(|_| is_static("classifiers"))
+            .unwrap_or_default(),
+        requires_dist,
+        requires_python: project
+            .requires_python
+            .as_ref()
+            .filter(|_| is_static("requires-python"))
+            .map(ToString::to_string),
+        project_urls: ProjectUrls::new(
+            metadata
+                .urls
+                .filter(|_| is_static("urls"))
+                .unwrap_or_default(),
+        ),
+        provides_extra,
+        dynamic,
+        ..Metadata23::default()
+    })
+}
+
+/// Gate an optional dependency on its extra, e.g., `click>=8 ; extra == 'cli'`.
+fn with_extra(
+    mut requirement: Requirement<VerbatimParsedUrl>,
+    extra: &ExtraName,
+) -> Requirement<VerbatimParsedUrl> {
+    requirement
+        .marker
+        .and(MarkerTree::expression(MarkerExpression::Extra {
+            operator: ExtraOperator::Equal,
+            name: MarkerValueExtra::Extra(extra.clone()),
+        }));
+    requirement
+}
+
+/// Split contacts into the name field (e.g., `Author`) and the email field (e.g.,
+/// `Author-email`), where the latter uses the `Name <email>` form for entries with both.
+fn contacts(contacts: Option<&[Contact]>) -> (Option<String>, Option<String>) {
+    let contacts = contacts.unwrap_or_default();
+    let names = contacts
+        .iter()
+        .filter_map(|contact| match contact {
+            Contact::Name { name } | Contact::NameEmail { name, .. } => Some(name.as_str()),
+            Contact::Email { .. } => None,
+        })
+        .join(", ");
+    let emails = contacts
+        .iter()
+        .filter_map(|contact| match contact {
+            Contact::Name { .. } => None,
+            Contact::Email { email } => Some(email.clone()),
+            Contact::NameEmail { name, email } => Some(format!("{name} <{email}>")),
+        })
+        .join(", ");
+    (
+        Some(names).filter(|names| !names.is_empty()),
+        Some(emails).filter(|emails| !emails.is_empty()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use std::str::FromStr;
+
+    use assert_fs::fixture::ChildPath;
+    use assert_fs::prelude::*;
+    use insta::assert_snapshot;
+
+    use uv_normalize::PackageName;
+
+    use crate::{DiscoveryOptions, Workspace, WorkspaceCache};
+
+    async fn workspace(root: &ChildPath) -> Result<Workspace> {
+        Ok(Workspace::discover(
+            root.path(),
+            &DiscoveryOptions::default(),
+            &WorkspaceCache::default(),
+        )
+        .await?)
+    }
+
+    #[tokio::test]
+    async fn core_metadata() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+        root.child("pyproject.toml").write_str(
+            r#"
+            [project]
+            name = "Albatross"
+            version = "0.1.0"
+            description = "A large seabird"
+            requires-python = ">=3.12"
+            license = "MIT OR Apache-2.0"
+            authors = [
+                { name = "Zoë Martínez", email = "zoe@example.com" },
+                { name = "Ferris" },
+                { email = "crab@example.com" },
+            ]
+            keywords = ["bird", "ocean"]
+            classifiers = ["Programming Language :: Python :: 3.12"]
+            dependencies = [
+                "bird-feeder",
+                "anyio[trio]>=4,<5",
+                "tomli ; python_version < '3.11'",
+            ]
+
+            [project.optional-dependencies]
+            cli = ["click>=8", "rich ; sys_platform != 'win32'"]
+            all = ["albatross[cli]"]
+
+            [project.urls]
+            Homepage = "https://example.com"
+            Repository = "https://github.com/example/albatross"
+
+            [tool.uv.workspace]
+            members = ["packages/*"]
+            "#,
+        )?;
+        root.child("packages")
+            .child("bird-feeder")
+            .child("pyproject.toml")
+            .write_str(
+                r#"
+                [project]
+                name = "bird-feeder"
+                version = "1.0.0"
+                license = { text = "Copyright (c) Ferris\nAll rights reserved." }
+                maintainers = [{ name = "Ferris", email = "ferris@example.com" }]
+                dynamic = ["dependencies", "optional-dependencies", "readme"]
+                "#,
+            )?;
+
+        let metadata = workspace(&root).await?.to_core_metadata()?;
+        assert_snapshot!(
+            metadata[&PackageName::from_str("albatross")?].core_metadata_format(),
+            @r"
+        Metadata-Version: 2.4
+        Name: Albatross
+        Version: 0.1.0
+        Summary: A large seabird
+        Keywords: bird,ocean
+        Author: Zoë Martínez, Ferris
+        Author-email: Zoë Martínez <zoe@example.com>, crab@example.com
+        License-Expression: MIT OR Apache-2.0
+        Classifier: Programming Language :: Python :: 3.12
+        Requires-Dist: bird-feeder
+        Requires-Dist: anyio[trio]>=4,<5
+        Requires-Dist: tomli ; python_full_version < '3.11'
+        Requires-Dist: albatross[cli] ; extra == 'all'
+        Requires-Dist: click>=8 ; extra == 'cli'
+        Requires-Dist: rich ; sys_platform != 'win32' and extra == 'cli'
+        Requires-Python: >=3.12
+        Project-URL: Homepage, https://example.com
+        Project-URL: Repository, https://github.com/example/albatross
+        Provides-Extra: all
+        Provides-Extra: cli
+        "
+        );
+        assert_snapshot!(
+            metadata[&PackageName::from_str("bird-feeder")?].core_metadata_format(),
+            @r"
+        Metadata-Version: 2.3
+        Name: bird-feeder
+        Version: 1.0.0
+        License: Copyright (c) Ferris
+                 All rights reserved.
+        Maintainer: Ferris
+        Maintainer-email: Ferris <ferris@example.com>
+        Dynamic: Description
+        Dynamic: Description-Content-Type
+        Dynamic: Requires-Dist
+        Dynamic: Provides-Extra
+        "
+        );
+
+        Ok(())
+    }
+
+    /// Core metadata requires a static version.
+    #[tokio::test]
+    async fn dynamic_version() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+        root.child("pyproject.toml").write_str(
+            r#"
+            [project]
+            name = "albatross"
+            dynamic = ["version"]
+            "#,
+        )?;
+
+        let err = workspace(&root).await?.to_core_metadata().unwrap_err();
+        assert_snapshot!(
+            err,
+            @"pyproject.toml section is declared as dynamic, but must be static: `project.version`"
+        );
+
+        Ok(())
+    }
+}
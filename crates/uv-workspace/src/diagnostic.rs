@@ -0,0 +1,387 @@
+//! Structured diagnostics for problems in a `pyproject.toml`, e.g., parse errors and validation
+//! findings, which can be rendered as a snippet of the source with the offending span underlined.
+
+use std::fmt::{Display, Formatter, Write};
+use std::ops::Range;
+use std::path::Path;
+
+use uv_fs::Simplified;
+use uv_pep508::{Pep508Error, Pep508Url};
+
+use crate::bounds::BoundsDiagnostic;
+use crate::contradictions::ContradictionDiagnostic;
+use crate::pyproject::PyprojectTomlError;
+use crate::pyproject_mut::RequirementParseError;
+
+/// The severity of a [`Diagnostic`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Error => write!(f, "error"),
+            Self::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A problem in a `pyproject.toml`, optionally pointing at the span of the source it applies to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    /// The byte range of the source that the diagnostic applies to.
+    pub span: Option<Range<usize>>,
+    /// The dotted path of the key that the diagnostic applies to, e.g., `project.dependencies`.
+    pub key: Option<String>,
+    /// A suggestion for how to resolve the problem.
+    pub help: Option<String>,
+}
+
+impl Diagnostic {
+    /// Create an error diagnostic with the given message.
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, message)
+    }
+
+    /// Create a warning diagnostic with the given message.
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, message)
+    }
+
+    fn new(severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+            span: None,
+            key: None,
+            help: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_span(mut self, span: Range<usize>) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    #[must_use]
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Render the diagnostic against the source it was produced from, e.g.:
+    ///
+    /// ```text
+    /// error: invalid string
+    ///  --> pyproject.toml:3:8
+    ///   |
+    /// 3 | name = albatross
+    ///   |        ^
+    /// ```
+    ///
+    /// Spans that extend over multiple lines are underlined up to the end of their first line.
+    pub fn render(&self, path: &Path, source: &str) -> String {
+        let mut output = format!("{}: {}\n", self.severity, self.message);
+
+        let location = self
+            .span
+            .as_ref()
+            .filter(|span| span.start <= source.len())
+            .map(|span| Location::new(source, span));
+
+        let path = path.user_display();
+        match (&location, &self.key) {
+            (Some(location), _) => {
+                let _ = writeln!(
+                    output,
+                    " --> {path}:{}:{}",
+                    location.line_number, location.column
+                );
+            }
+            (None, Some(key)) => {
+                let _ = writeln!(output, " --> {path} (at `{key}`)");
+            }
+            (None, None) => {
+                let _ = writeln!(output, " --> {path}");
+            }
+        }
+
+        if let Some(location) = &location {
+            let gutter = " ".repeat(location.line_number.to_string().len());
+            let _ = writeln!(output, "{gutter} |");
+            let _ = writeln!(output, "{} | {}", location.line_number, location.line);
+            let _ = writeln!(
+                output,
+                "{gutter} | {}{}",
+                " ".repeat(location.column - 1),
+                "^".repeat(location.width)
+            );
+        }
+
+        if let Some(help) = &self.help {
+            let _ = writeln!(output, "  = help: {help}");
+        }
+
+        output
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.severity, self.message)
+    }
+}
+
+/// The position of a span in the source, for rendering.
+struct Location<'a> {
+    /// The line containing the start of the span, without the trailing newline.
+    line: &'a str,
+    /// The one-based line number.
+    line_number: usize,
+    /// The one-based column of the start of the span, in characters.
+    column: usize,
+    /// The number of characters to underline.
+    width: usize,
+}
+
+impl<'a> Location<'a> {
+    fn new(source: &'a str, span: &Range<usize>) -> Self {
+        let start = floor_char_boundary(source, span.start);
+        let line_start = source[..start].rfind('\n').map_or(0, |index| index + 1);
+        let line_end = source[start..]
+            .find('\n')
+            .map_or(source.len(), |index| start + index);
+        let line = source[line_start..line_end].trim_end_matches('\r');
+        let end = floor_char_boundary(source, span.end.clamp(start, line_start + line.len()));
+        Self {
+            line,
+            line_number: source[..line_start].matches('\n').count() + 1,
+            column: source[line_start..start].chars().count() + 1,
+            width: source[start..end].chars().count().max(1),
+        }
+    }
+}
+
+/// Returns the largest character boundary in `source` that's at most `index`.
+fn floor_char_boundary(source: &str, index: usize) -> usize {
+    let mut index = index.min(source.len());
+    while !source.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+impl From<&toml_edit::TomlError> for Diagnostic {
+    fn from(err: &toml_edit::TomlError) -> Self {
+        let diagnostic = Self::error(err.message().trim_end());
+        match err.span() {
+            Some(span) => diagnostic.with_span(span),
+            None => diagnostic,
+        }
+    }
+}
+
+impl From<&toml::de::Error> for Diagnostic {
+    fn from(err: &toml::de::Error) -> Self {
+        let diagnostic = Self::error(err.message().trim_end());
+        match err.span() {
+            Some(span) => diagnostic.with_span(span),
+            None => diagnostic,
+        }
+    }
+}
+
+impl From<&PyprojectTomlError> for Diagnostic {
+    fn from(err: &PyprojectTomlError) -> Self {
+        match err {
+            PyprojectTomlError::Toml(err) => Self::from(err),
+            PyprojectTomlError::MissingName => {
+                Self::error(err.to_string()).with_key("project.name")
+            }
+            PyprojectTomlError::MissingVersion => Self::error(err.to_string())
+                .with_key("project.version")
+                .with_help("Set `project.version`, or add `version` to `project.dynamic`"),
+        }
+    }
+}
+
+impl<T: Pep508Url> From<&Pep508Error<T>> for Diagnostic {
+    /// Convert a requirement parse error, with a span into the requirement string.
+    fn from(err: &Pep508Error<T>) -> Self {
+        Self::error(err.message.to_string()).with_span(err.start..err.start + err.len)
+    }
+}
+
+impl From<&RequirementParseError> for Diagnostic {
+    /// Convert an invalid dependency entry, with a span into the file if it's known.
+    fn from(err: &RequirementParseError) -> Self {
+        let diagnostic = Self::error(format!(
+            "Failed to parse `{}`: {}",
+            err.requirement, err.err.message
+        ))
+        .with_key(format!("{}[{}]", err.section, err.index));
+        match &err.span {
+            Some(span) => diagnostic.with_span(span.clone()),
+            None => diagnostic,
+        }
+    }
+}
+
+impl From<&BoundsDiagnostic> for Diagnostic {
+    /// Convert a dependency that lacks a lower bound into a warning.
+    fn from(diagnostic: &BoundsDiagnostic) -> Self {
+        Self::warning(diagnostic.to_string())
+            .with_key(format!("{}[{}]", diagnostic.section(), diagnostic.index))
+            .with_help(format!(
+                "Add a lower bound, e.g., `{}>=<version>`",
+                diagnostic.name
+            ))
+    }
+}
+
+impl From<&ContradictionDiagnostic> for Diagnostic {
+    /// Convert a dependency whose version specifiers can't be satisfied into an error.
+    fn from(diagnostic: &ContradictionDiagnostic) -> Self {
+        Self::error(diagnostic.to_string()).with_key(format!(
+            "{}[{}]",
+            diagnostic.section(),
+            diagnostic.index
+        ))
+    }
+}
+
+#[cfg(feature = "miette")]
+impl Diagnostic {
+    /// Convert the diagnostic into a [`miette::Report`] for the given source.
+    pub fn into_miette(self, path: &Path, source: String) -> miette::Report {
+        let mut diagnostic =
+            miette::MietteDiagnostic::new(self.message).with_severity(match self.severity {
+                Severity::Error => miette::Severity::Error,
+                Severity::Warning => miette::Severity::Warning,
+            });
+        if let Some(span) = self.span {
+            diagnostic = diagnostic.with_label(miette::LabeledSpan::underline(span));
+        }
+        if let Some(help) = self.help {
+            diagnostic = diagnostic.with_help(help);
+        }
+        miette::Report::new(diagnostic).with_source_code(miette::NamedSource::new(
+            path.simplified_display().to_string(),
+            source,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::str::FromStr;
+
+    use insta::assert_snapshot;
+
+    use uv_pep508::{Requirement, VerbatimUrl};
+
+    use crate::diagnostic::{Diagnostic, Severity};
+    use crate::pyproject::{DependencyType, PyProjectToml, PyprojectTomlError};
+    use crate::pyproject_mut::{DependencyTarget, Error, PyProjectTomlMut};
+
+    #[test]
+    fn render_parse_error() {
+        let source = "[project]\nname = \"albatross\"\nversion = 0.1.0\n";
+        let Err(Error::Parse(err)) = PyProjectTomlMut::from_toml(source, DependencyTarget::Script)
+        else {
+            panic!("expected a parse error");
+        };
+        assert_snapshot!(Diagnostic::from(err.as_ref()).render(Path::new("pyproject.toml"), source), @r"
+        error: invalid float, expected nothing
+         --> pyproject.toml:3:14
+          |
+        3 | version = 0.1.0
+          |              ^^
+        ");
+    }
+
+    #[test]
+    fn render_deserialize_error() {
+        let source = "[project]\nname = \"albatross\"\n";
+        let err = PyProjectToml::from_string(source.to_string(), "pyproject.toml").unwrap_err();
+        assert_snapshot!(Diagnostic::from(&err).render(Path::new("pyproject.toml"), source), @r"
+        error: `pyproject.toml` is using the `[project]` table, but the required `project.version` field is neither set nor present in the `project.dynamic` list
+         --> pyproject.toml:1:1
+          |
+        1 | [project]
+          | ^^^^^^^^^
+        ");
+    }
+
+    #[test]
+    fn render_key() {
+        let diagnostic = Diagnostic::from(&PyprojectTomlError::MissingVersion);
+        assert_snapshot!(diagnostic.render(Path::new("pyproject.toml"), ""), @r"
+        error: `pyproject.toml` is using the `[project]` table, but the required `project.version` field is neither set nor present in the `project.dynamic` list
+         --> pyproject.toml (at `project.version`)
+          = help: Set `project.version`, or add `version` to `project.dynamic`
+        ");
+    }
+
+    #[test]
+    fn render_requirement() {
+        let source = "flask >=1.0,<";
+        let err = Requirement::<VerbatimUrl>::from_str(source).unwrap_err();
+        let diagnostic = Diagnostic::from(&err);
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_snapshot!(diagnostic.render(Path::new("pyproject.toml"), source), @r"
+        error: Unexpected end of version specifier, expected version
+         --> pyproject.toml:1:13
+          |
+        1 | flask >=1.0,<
+          |             ^
+        ");
+    }
+
+    #[test]
+    fn render_dependency() -> anyhow::Result<()> {
+        let source = "[project]\nname = \"albatross\"\ndependencies = [\n    \"anyio>=4\",\n    \"flask >=1.0,<\",\n]\n";
+        let pyproject = PyProjectTomlMut::from_toml(source, DependencyTarget::PyProjectToml)?;
+        let Err(Error::RequirementParse(err)) = pyproject.dependencies(&DependencyType::Production)
+        else {
+            panic!("expected a requirement parse error");
+        };
+        assert_snapshot!(Diagnostic::from(err.as_ref()).render(Path::new("pyproject.toml"), source), @r#"
+        error: Failed to parse `flask >=1.0,<`: Unexpected end of version specifier, expected version
+         --> pyproject.toml:5:18
+          |
+        5 |     "flask >=1.0,<",
+          |                  ^
+        "#);
+        Ok(())
+    }
+
+    #[test]
+    fn render_multiline_span() {
+        let source = "[project]\ndependencies = [\n  \"flask\",\n]\n";
+        let diagnostic = Diagnostic::warning("`project.dependencies` is unsorted")
+            .with_span(25..source.len())
+            .with_help("Sort the dependencies");
+        assert_snapshot!(diagnostic.render(Path::new("pyproject.toml"), source), @r"
+        warning: `project.dependencies` is unsorted
+         --> pyproject.toml:2:16
+          |
+        2 | dependencies = [
+          |                ^
+          = help: Sort the dependencies
+        ");
+    }
+}
@@ -0,0 +1,351 @@
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use tracing::debug;
+
+use uv_fs::{PortablePath, Simplified};
+use uv_normalize::PackageName;
+use uv_pep508::Requirement;
+
+use crate::pyproject_mut::{self, ArrayEdit, DependencyTarget, PyProjectTomlMut};
+use crate::{Workspace, WorkspaceMember};
+
+#[derive(thiserror::Error, Debug)]
+pub enum EditError {
+    #[error("Failed to read: `{}`", _0.user_display())]
+    Read(PathBuf, #[source] std::io::Error),
+    #[error("Failed to edit: `{}`", _0.user_display())]
+    Edit(PathBuf, #[source] pyproject_mut::Error),
+    #[error("Failed to write: `{}`", _0.user_display())]
+    Write(PathBuf, #[source] std::io::Error),
+}
+
+/// Selects a subset of the members of a [`Workspace`].
+#[derive(Debug, Clone)]
+pub enum MemberFilter {
+    /// All workspace members, including the workspace root.
+    All,
+    /// The workspace members with the given names.
+    Names(Vec<PackageName>),
+    /// The workspace members whose path, relative to the workspace root, matches the glob.
+    Glob(glob::Pattern),
+}
+
+impl MemberFilter {
+    /// Returns `true` if the workspace member is selected by the filter.
+    fn matches(&self, name: &PackageName, member: &WorkspaceMember, install_path: &Path) -> bool {
+        match self {
+            Self::All => true,
+            Self::Names(names) => names.contains(name),
+            Self::Glob(pattern) => {
+                let Ok(relative) = member.root().strip_prefix(install_path) else {
+                    return false;
+                };
+                pattern.matches(&PortablePath::from(relative).to_string())
+            }
+        }
+    }
+}
+
+/// The reason a workspace member was skipped when applying an edit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkipReason {
+    /// The dependency refers to the member itself.
+    SelfDependency,
+    /// The member already declares the dependency as a `{ workspace = true }` source.
+    WorkspaceSource,
+    /// The member doesn't depend on the package.
+    MissingDependency,
+}
+
+impl Display for SkipReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SelfDependency => write!(f, "the dependency refers to the member itself"),
+            Self::WorkspaceSource => write!(
+                f,
+                "the dependency is already provided by a `workspace = true` source"
+            ),
+            Self::MissingDependency => write!(f, "the member doesn't depend on the package"),
+        }
+    }
+}
+
+/// An edited, but not yet written, `pyproject.toml` of a workspace member.
+pub struct MemberEdit {
+    /// The path to the member's `pyproject.toml`.
+    pub path: PathBuf,
+    /// The edit to the `project.dependencies` array.
+    pub edit: ArrayEdit,
+    /// The edited `pyproject.toml`.
+    pub pyproject: PyProjectTomlMut,
+}
+
+/// The result of adding a dependency to a single workspace member.
+pub enum AddResult {
+    /// The dependency was added to, or updated in, the member's `pyproject.toml`.
+    Edited(Box<MemberEdit>),
+    /// The member was skipped.
+    Skipped(SkipReason),
+    /// The member could not be edited.
+    Failed(EditError),
+}
+
+impl Workspace {
+    /// Add a dependency to `project.dependencies` of every workspace member matching the filter.
+    ///
+    /// Each member is edited independently, such that a failure in one member is recorded in its
+    /// result without aborting the others. The edits are held in memory; use [`save_all`] to write
+    /// them.
+    pub fn add_dependency_to_members(
+        &self,
+        requirement: &Requirement,
+        filter: &MemberFilter,
+    ) -> Vec<(PackageName, AddResult)> {
+        self.packages()
+            .iter()
+            .filter(|(name, member)| filter.matches(name, member, self.install_path()))
+            .map(|(name, member)| {
+                let result = add_dependency_to_member(name, member, requirement);
+                (name.clone(), result)
+            })
+            .collect()
+    }
+}
+
+/// Add a dependency to `project.dependencies` of a single workspace member.
+fn add_dependency_to_member(
+    name: &PackageName,
+    member: &WorkspaceMember,
+    requirement: &Requirement,
+) -> AddResult {
+    if *name == requirement.name {
+        return AddResult::Skipped(SkipReason::SelfDependency);
+    }
+
+    if member.has_workspace_source(&requirement.name, None) {
+        debug!(
+            "Skipping `{name}`, which declares `{}` as a workspace source",
+            requirement.name
+        );
+        return AddResult::Skipped(SkipReason::WorkspaceSource);
+    }
+
+    let path = member.root().join("pyproject.toml");
+    let mut pyproject = match PyProjectTomlMut::load(&path, DependencyTarget::PyProjectToml) {
+        Ok(pyproject) => pyproject,
+        Err(pyproject_mut::Error::Read(path, err)) => {
+            return AddResult::Failed(EditError::Read(path, err));
+        }
+        Err(err) => return AddResult::Failed(EditError::Edit(path, err)),
+    };
+    match pyproject.add_dependency(requirement, None, false) {
+        Ok(edit) => AddResult::Edited(Box::new(MemberEdit {
+            path,
+            edit,
+            pyproject,
+        })),
+        Err(err) => AddResult::Failed(EditError::Edit(path, err)),
+    }
+}
+
+/// Write the edited `pyproject.toml` files, atomically per file.
+///
+/// A failure to write one file doesn't prevent the remaining files from being written; the
+/// failures are returned alongside the name of the affected member.
+pub fn save_all(results: &mut [(PackageName, AddResult)]) -> Vec<(PackageName, EditError)> {
+    let mut errors = Vec::new();
+    for (name, result) in results {
+        let AddResult::Edited(edit) = result else {
+            continue;
+        };
+        if let Err(err) = edit.pyproject.save() {
+            let err = match err {
+                pyproject_mut::Error::Read(path, err) => EditError::Read(path, err),
+                pyproject_mut::Error::Write(path, err) => EditError::Write(path, err),
+                err => EditError::Edit(edit.path.clone(), err),
+            };
+            errors.push((name.clone(), err));
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use anyhow::Result;
+    use assert_fs::fixture::ChildPath;
+    use assert_fs::prelude::*;
+
+    use uv_normalize::PackageName;
+    use uv_pep508::Requirement;
+
+    use crate::edit::{AddResult, MemberFilter, save_all};
+    use crate::{DiscoveryOptions, Workspace, WorkspaceCache};
+
+    /// Create a workspace with a root (`albatross`) and two members (`bird-feeder`, `seeds`),
+    /// where `bird-feeder` depends on `seeds` via a workspace source.
+    fn workspace_fixture(root: &ChildPath) -> Result<()> {
+        root.child("pyproject.toml").write_str(
+            r#"
+            [project]
+            name = "albatross"
+            version = "0.1.0"
+            requires-python = ">=3.12"
+            dependencies = []
+
+            [tool.uv.workspace]
+            members = ["packages/*"]
+            "#,
+        )?;
+        root.child("packages")
+            .child("bird-feeder")
+            .child("pyproject.toml")
+            .write_str(
+                r#"
+            [project]
+            name = "bird-feeder"
+            version = "1.0.0"
+            requires-python = ">=3.12"
+            dependencies = ["seeds"]
+
+            [tool.uv.sources]
+            seeds = { workspace = true }
+            "#,
+            )?;
+        root.child("packages")
+            .child("seeds")
+            .child("pyproject.toml")
+            .write_str(
+                r#"
+            [project]
+            name = "seeds"
+            version = "1.0.0"
+            requires-python = ">=3.12"
+            "#,
+            )?;
+        Ok(())
+    }
+
+    async fn discover(root: &ChildPath) -> Result<Workspace> {
+        Ok(Workspace::discover(
+            root.path(),
+            &DiscoveryOptions::default(),
+            &WorkspaceCache::default(),
+        )
+        .await?)
+    }
+
+    #[tokio::test]
+    async fn add_dependency_to_all_members() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+        workspace_fixture(&root)?;
+        let workspace = discover(&root).await?;
+
+        let requirement = Requirement::from_str("seeds>=1")?;
+        let mut results = workspace.add_dependency_to_members(&requirement, &MemberFilter::All);
+
+        let summary = results
+            .iter()
+            .map(|(name, result)| {
+                let outcome = match result {
+                    AddResult::Edited(_) => "edited".to_string(),
+                    AddResult::Skipped(reason) => format!("skipped: {reason}"),
+                    AddResult::Failed(err) => format!("failed: {err}"),
+                };
+                format!("{name}: {outcome}")
+            })
+            .collect::<Vec<_>>();
+        insta::assert_debug_snapshot!(summary, @r#"
+        [
+            "albatross: edited",
+            "bird-feeder: skipped: the dependency is already provided by a `workspace = true` source",
+            "seeds: skipped: the dependency refers to the member itself",
+        ]
+        "#);
+
+        // Nothing is written until the edits are saved.
+        assert!(
+            fs_err::read_to_string(root.child("pyproject.toml"))?.contains("dependencies = []")
+        );
+
+        assert!(save_all(&mut results).is_empty());
+        assert!(fs_err::read_to_string(root.child("pyproject.toml"))?.contains(r#""seeds>=1","#));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn add_dependency_filtered() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+        workspace_fixture(&root)?;
+        let workspace = discover(&root).await?;
+        let requirement = Requirement::from_str("tqdm>=4")?;
+
+        let names = |results: Vec<(PackageName, AddResult)>| {
+            results
+                .into_iter()
+                .map(|(name, _)| name.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let filter = MemberFilter::Names(vec![PackageName::from_str("seeds")?]);
+        assert_eq!(
+            names(workspace.add_dependency_to_members(&requirement, &filter)),
+            vec!["seeds"]
+        );
+
+        let filter = MemberFilter::Glob(glob::Pattern::new("packages/*")?);
+        assert_eq!(
+            names(workspace.add_dependency_to_members(&requirement, &filter)),
+            vec!["bird-feeder", "seeds"]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn add_dependency_partial_failure() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+        workspace_fixture(&root)?;
+        let workspace = discover(&root).await?;
+
+        // Make one member's dependencies malformed after discovery.
+        root.child("packages")
+            .child("seeds")
+            .child("pyproject.toml")
+            .write_str(
+                r#"
+            [project]
+            name = "seeds"
+            version = "1.0.0"
+            requires-python = ">=3.12"
+            dependencies = "tqdm"
+            "#,
+            )?;
+
+        let requirement = Requirement::from_str("tqdm>=4")?;
+        let mut results = workspace.add_dependency_to_members(&requirement, &MemberFilter::All);
+        let failed = results
+            .iter()
+            .filter(|(_, result)| matches!(result, AddResult::Failed(_)))
+            .map(|(name, _)| name.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(failed, vec!["seeds"]);
+
+        // The remaining members are still written.
+        assert!(save_all(&mut results).is_empty());
+        let bird_feeder = root
+            .child("packages")
+            .child("bird-feeder")
+            .child("pyproject.toml");
+        assert!(fs_err::read_to_string(bird_feeder)?.contains("tqdm>=4"));
+
+        Ok(())
+    }
+}
@@ -1,10 +1,30 @@
+pub use bounds::{BoundsDiagnostic, BoundsStrictness, MissingBound};
+pub use contradictions::{Contradiction, ContradictionDiagnostic};
+pub use edit::{AddResult, EditError, MemberEdit, MemberFilter, SkipReason, save_all};
+pub use member_graph::{MemberCycle, MemberEdgeKind, MemberGraph, MissingWorkspaceSource};
+pub use metadata::{
+    DependencyMetadata, MemberMetadata, WORKSPACE_METADATA_VERSION, WorkspaceMetadata,
+};
+pub use plan::{PlanError, PlanOperation, PlanOutcome, PlanReport, WorkspacePlan};
+pub use portable::PortablePathError;
+pub use summary::{DependencySummary, SummaryRequirement};
 pub use workspace::{
-    DiscoveryOptions, Editability, MemberDiscovery, ProjectDiscovery, ProjectWorkspace,
-    RequiresPythonSources, VirtualProject, Workspace, WorkspaceCache, WorkspaceError,
-    WorkspaceMember,
+    DiscoveryOptions, DiscoveryWarning, Editability, MemberDiscovery, ProjectDiscovery,
+    ProjectWorkspace, RequiresPythonSources, VirtualProject, Workspace, WorkspaceCache,
+    WorkspaceError, WorkspaceMember, WorkspaceSnapshot,
 };
 
+mod bounds;
+mod contradictions;
+mod core_metadata;
 pub mod dependency_groups;
+pub mod diagnostic;
+mod edit;
+mod member_graph;
+mod metadata;
+mod plan;
+mod portable;
 pub mod pyproject;
 pub mod pyproject_mut;
+mod summary;
 mod workspace;
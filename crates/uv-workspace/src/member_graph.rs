@@ -0,0 +1,454 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use itertools::Itertools;
+use petgraph::Direction;
+use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::{EdgeFiltered, EdgeRef, Walker};
+use rustc_hash::FxHashSet;
+
+use uv_normalize::{ExtraName, GroupName, PackageName};
+use uv_pypi_types::VerbatimParsedUrl;
+
+use crate::dependency_groups::FlatDependencyGroups;
+use crate::{Workspace, WorkspaceError, WorkspaceMember};
+
+/// The kind of dependency from one workspace member on another.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MemberEdgeKind {
+    /// A dependency in `project.dependencies`.
+    Runtime,
+    /// A dependency in `project.optional-dependencies`, for the given extra.
+    Optional(ExtraName),
+    /// A dependency in a dependency group (including `tool.uv.dev-dependencies`).
+    Group(GroupName),
+}
+
+impl MemberEdgeKind {
+    /// Returns `true` if the dependency is part of the member's published metadata, i.e., it's a
+    /// runtime or optional dependency, as opposed to a development dependency.
+    pub fn is_metadata(&self) -> bool {
+        matches!(self, Self::Runtime | Self::Optional(_))
+    }
+}
+
+/// A dependency on a workspace member that isn't declared with a `{ workspace = true }` source,
+/// and so would be resolved from the index, rather than from the workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingWorkspaceSource {
+    /// The member declaring the dependency.
+    pub member: PackageName,
+    /// The workspace member that is depended on.
+    pub dependency: PackageName,
+    /// The kind of dependency.
+    pub kind: MemberEdgeKind,
+}
+
+/// A cycle between workspace members.
+#[derive(Debug)]
+pub struct MemberCycle(Vec<PackageName>);
+
+/// Display a cycle, e.g., `a -> b -> c -> a`.
+impl std::fmt::Display for MemberCycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [first, rest @ ..] = self.0.as_slice() else {
+            return Ok(());
+        };
+        write!(f, "`{first}`")?;
+        for name in rest {
+            write!(f, " -> `{name}`")?;
+        }
+        write!(f, " -> `{first}`")?;
+        Ok(())
+    }
+}
+
+/// The graph of dependencies between the members of a [`Workspace`].
+///
+/// An edge from `a` to `b` indicates that `a` depends on `b` via a `{ workspace = true }` source.
+#[derive(Debug)]
+pub struct MemberGraph {
+    graph: DiGraph<PackageName, MemberEdgeKind>,
+    indices: BTreeMap<PackageName, NodeIndex>,
+    missing_sources: Vec<MissingWorkspaceSource>,
+}
+
+impl Workspace {
+    /// Build the graph of dependencies between the workspace members.
+    pub fn member_graph(&self) -> Result<MemberGraph, WorkspaceError> {
+        let mut graph = DiGraph::new();
+        let mut indices = BTreeMap::new();
+        for name in self.packages().keys() {
+            indices.insert(name.clone(), graph.add_node(name.clone()));
+        }
+
+        let mut missing_sources = Vec::new();
+        for (name, member) in self.packages() {
+            for (dependency, kind) in member_dependencies(name, member)? {
+                // Ignore dependencies outside the workspace, and self-dependencies (e.g., extras
+                // that activate other extras).
+                if dependency == *name {
+                    continue;
+                }
+                let Some(target) = indices.get(&dependency) else {
+                    continue;
+                };
+
+                if member.has_workspace_source(&dependency, Some(self.sources())) {
+                    graph.add_edge(indices[name], *target, kind);
+                } else {
+                    missing_sources.push(MissingWorkspaceSource {
+                        member: name.clone(),
+                        dependency,
+                        kind,
+                    });
+                }
+            }
+        }
+
+        Ok(MemberGraph {
+            graph,
+            indices,
+            missing_sources,
+        })
+    }
+}
+
+/// Collect the names of all dependencies of a workspace member, along with their kind.
+fn member_dependencies(
+    name: &PackageName,
+    member: &WorkspaceMember,
+) -> Result<Vec<(PackageName, MemberEdgeKind)>, WorkspaceError> {
+    let parse = |requirement: &str| {
+        uv_pep508::Requirement::<VerbatimParsedUrl>::from_str(requirement)
+            .map(|requirement| requirement.name)
+            .map_err(|err| {
+                WorkspaceError::DependencyParse(
+                    name.clone(),
+                    requirement.to_string(),
+                    Box::new(err),
+                )
+            })
+    };
+
+    let mut dependencies = Vec::new();
+    let project = member.project();
+    for requirement in project.dependencies.iter().flatten() {
+        dependencies.push((parse(requirement)?, MemberEdgeKind::Runtime));
+    }
+    for (extra, requirements) in project.optional_dependencies.iter().flatten() {
+        for requirement in requirements {
+            dependencies.push((parse(requirement)?, MemberEdgeKind::Optional(extra.clone())));
+        }
+    }
+    let groups = FlatDependencyGroups::from_pyproject_toml(member.root(), member.pyproject_toml())?;
+    for (group, flat_group) in groups {
+        for requirement in flat_group.requirements {
+            dependencies.push((requirement.name, MemberEdgeKind::Group(group.clone())));
+        }
+    }
+    Ok(dependencies)
+}
+
+impl MemberGraph {
+    /// Returns the workspace members that the given member directly depends on.
+    pub fn dependencies<'a>(
+        &'a self,
+        name: &PackageName,
+    ) -> impl Iterator<Item = (&'a PackageName, &'a MemberEdgeKind)> + 'a {
+        self.edges(name, Direction::Outgoing)
+    }
+
+    /// Returns the workspace members that directly depend on the given member.
+    pub fn dependents<'a>(
+        &'a self,
+        name: &PackageName,
+    ) -> impl Iterator<Item = (&'a PackageName, &'a MemberEdgeKind)> + 'a {
+        self.edges(name, Direction::Incoming)
+    }
+
+    fn edges<'a>(
+        &'a self,
+        name: &PackageName,
+        direction: Direction,
+    ) -> impl Iterator<Item = (&'a PackageName, &'a MemberEdgeKind)> + 'a {
+        self.indices
+            .get(name)
+            .into_iter()
+            .flat_map(move |index| self.graph.edges_directed(*index, direction))
+            .map(move |edge| {
+                let other = match direction {
+                    Direction::Outgoing => edge.target(),
+                    Direction::Incoming => edge.source(),
+                };
+                (&self.graph[other], edge.weight())
+            })
+    }
+
+    /// Returns the workspace members that are affected by a change to the given member, i.e., the
+    /// members that transitively depend on it, via any kind of dependency.
+    ///
+    /// The given member itself is not included.
+    pub fn affected_by(&self, name: &PackageName) -> Vec<&PackageName> {
+        let Some(start) = self.indices.get(name) else {
+            return Vec::new();
+        };
+        let reversed = petgraph::visit::Reversed(&self.graph);
+        let mut affected = petgraph::visit::Bfs::new(reversed, *start)
+            .iter(reversed)
+            .filter(|index| index != start)
+            .map(|index| &self.graph[index])
+            .collect::<Vec<_>>();
+        affected.sort();
+        affected
+    }
+
+    /// Returns the workspace members in dependency order, such that each member appears after
+    /// all the members it depends on.
+    ///
+    /// Only runtime and optional dependencies are considered, since dependency groups aren't
+    /// required to build a member, and cycles through development dependencies are common.
+    pub fn topological_order(&self) -> Result<Vec<&PackageName>, WorkspaceError> {
+        let filtered = EdgeFiltered::from_fn(&self.graph, |edge| edge.weight().is_metadata());
+        match petgraph::algo::toposort(&filtered, None) {
+            Ok(order) => Ok(order
+                .into_iter()
+                .rev()
+                .map(|index| &self.graph[index])
+                .collect()),
+            Err(cycle) => Err(WorkspaceError::MemberCycle(
+                self.find_cycle(cycle.node_id()),
+            )),
+        }
+    }
+
+    /// Returns a cycle between the workspace members, if one exists.
+    ///
+    /// As with [`MemberGraph::topological_order`], dependency groups are not considered.
+    pub fn cycle(&self) -> Option<MemberCycle> {
+        self.topological_order().err().and_then(|err| match err {
+            WorkspaceError::MemberCycle(cycle) => Some(cycle),
+            _ => None,
+        })
+    }
+
+    /// Find the path of a cycle through the given node, which is known to be part of a cycle.
+    fn find_cycle(&self, start: NodeIndex) -> MemberCycle {
+        let mut path = vec![start];
+        let mut visited = FxHashSet::default();
+        if !self.find_path_to(start, start, &mut path, &mut visited) {
+            path.truncate(1);
+        }
+        let mut names = path
+            .into_iter()
+            .map(|index| self.graph[index].clone())
+            .collect::<Vec<_>>();
+        // Start the cycle at the lexicographically smallest member, for stable output.
+        if let Some(position) = names.iter().position_min() {
+            names.rotate_left(position);
+        }
+        MemberCycle(names)
+    }
+
+    /// Depth-first search for a path from `current` back to `target`, appending to `path`.
+    fn find_path_to(
+        &self,
+        current: NodeIndex,
+        target: NodeIndex,
+        path: &mut Vec<NodeIndex>,
+        visited: &mut FxHashSet<NodeIndex>,
+    ) -> bool {
+        for edge in self.graph.edges(current) {
+            if !edge.weight().is_metadata() {
+                continue;
+            }
+            let next = edge.target();
+            if next == target {
+                return true;
+            }
+            if !visited.insert(next) {
+                continue;
+            }
+            path.push(next);
+            if self.find_path_to(next, target, path, visited) {
+                return true;
+            }
+            path.pop();
+        }
+        false
+    }
+
+    /// Returns the dependencies on workspace members that lack a `{ workspace = true }` source.
+    ///
+    /// These dependencies are likely mistakes, as they would be resolved from the index, rather
+    /// than from the workspace; they are not included as edges in the graph.
+    pub fn missing_workspace_sources(&self) -> &[MissingWorkspaceSource] {
+        &self.missing_sources
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use anyhow::Result;
+    use assert_fs::fixture::ChildPath;
+    use assert_fs::prelude::*;
+    use insta::assert_snapshot;
+
+    use uv_normalize::PackageName;
+
+    use crate::{DiscoveryOptions, Workspace, WorkspaceCache};
+
+    /// Create a workspace with the given members, as `(name, body)` pairs.
+    async fn workspace(root: &ChildPath, members: &[(&str, &str)]) -> Result<Workspace> {
+        root.child("pyproject.toml").write_str(
+            r#"
+            [tool.uv.workspace]
+            members = ["packages/*"]
+            "#,
+        )?;
+        for (name, body) in members {
+            root.child("packages")
+                .child(name)
+                .child("pyproject.toml")
+                .write_str(&format!(
+                    "[project]\nname = \"{name}\"\nversion = \"0.1.0\"\n{body}"
+                ))?;
+        }
+        Ok(Workspace::discover(
+            root.path(),
+            &DiscoveryOptions::default(),
+            &WorkspaceCache::default(),
+        )
+        .await?)
+    }
+
+    fn names<'a>(names: impl IntoIterator<Item = &'a PackageName>) -> Vec<String> {
+        names.into_iter().map(ToString::to_string).collect()
+    }
+
+    #[tokio::test]
+    async fn topological_order() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+        let workspace = workspace(
+            &root,
+            &[
+                (
+                    "a",
+                    r#"
+                    dependencies = ["b", "c[extra]>=1"]
+                    [dependency-groups]
+                    dev = ["d"]
+                    [tool.uv.sources]
+                    b = { workspace = true }
+                    c = { workspace = true }
+                    d = { workspace = true }
+                    "#,
+                ),
+                (
+                    "b",
+                    r#"
+                    dependencies = ["c", "tqdm"]
+                    [tool.uv.sources]
+                    c = { workspace = true }
+                    "#,
+                ),
+                ("c", ""),
+                (
+                    "d",
+                    r#"
+                    [project.optional-dependencies]
+                    test = ["a"]
+                    [tool.uv.sources]
+                    a = { workspace = true }
+                    "#,
+                ),
+            ],
+        )
+        .await?;
+
+        let graph = workspace.member_graph()?;
+        assert_eq!(names(graph.topological_order()?), vec!["c", "b", "a", "d"]);
+        assert_eq!(
+            names(
+                graph
+                    .dependencies(&PackageName::from_str("a")?)
+                    .map(|(name, _)| name)
+            ),
+            vec!["d", "c", "b"]
+        );
+        assert_eq!(
+            names(graph.affected_by(&PackageName::from_str("c")?)),
+            vec!["a", "b", "d"]
+        );
+        assert!(graph.missing_workspace_sources().is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn cycle() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+        let workspace = workspace(
+            &root,
+            &[
+                (
+                    "a",
+                    r#"
+                    dependencies = ["b"]
+                    [tool.uv.sources]
+                    b = { workspace = true }
+                    "#,
+                ),
+                (
+                    "b",
+                    r#"
+                    dependencies = ["c"]
+                    [tool.uv.sources]
+                    c = { workspace = true }
+                    "#,
+                ),
+                (
+                    "c",
+                    r#"
+                    dependencies = ["a"]
+                    [tool.uv.sources]
+                    a = { workspace = true }
+                    "#,
+                ),
+            ],
+        )
+        .await?;
+
+        let graph = workspace.member_graph()?;
+        assert_snapshot!(
+            graph.topological_order().unwrap_err(),
+            @"Detected a cycle between workspace members: `a` -> `b` -> `c` -> `a`"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn missing_workspace_source() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+        let workspace = workspace(&root, &[("a", r#"dependencies = ["b"]"#), ("b", "")]).await?;
+
+        let graph = workspace.member_graph()?;
+        assert_eq!(graph.dependencies(&PackageName::from_str("a")?).count(), 0);
+        assert_eq!(
+            graph
+                .missing_workspace_sources()
+                .iter()
+                .map(|missing| format!("{} -> {}", missing.member, missing.dependency))
+                .collect::<Vec<_>>(),
+            vec!["a -> b"]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,404 @@
+//! A stable, serializable description of a workspace, for tools that don't link against uv
+//! (e.g., editor plugins).
+//!
+//! The schema is checked in as `workspace-metadata.schema.json`.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use uv_fs::PortablePathBuf;
+use uv_normalize::{ExtraName, GroupName, PackageName};
+use uv_pep440::{Version, VersionSpecifiers};
+use uv_pep508::{Requirement, VersionOrUrl};
+use uv_pypi_types::VerbatimParsedUrl;
+
+use crate::dependency_groups::FlatDependencyGroups;
+use crate::pyproject::Source;
+use crate::{Workspace, WorkspaceError, WorkspaceMember};
+
+/// The version of the [`WorkspaceMetadata`] schema, which is incremented on incompatible changes.
+pub const WORKSPACE_METADATA_VERSION: u32 = 1;
+
+/// A description of a workspace and its members.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct WorkspaceMetadata {
+    /// The version of the schema, i.e., [`WORKSPACE_METADATA_VERSION`].
+    pub version: u32,
+    /// The absolute path to the workspace root.
+    pub root: PortablePathBuf,
+    /// The members of the workspace, including the root project, if any.
+    pub members: Vec<MemberMetadata>,
+}
+
+/// A description of a workspace member.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct MemberMetadata {
+    /// The name of the project.
+    pub name: PackageName,
+    /// The path to the project, relative to the workspace root.
+    pub path: PortablePathBuf,
+    /// The version of the project, or `None` if the version is dynamic.
+    pub version: Option<Version>,
+    /// The Python versions the project is compatible with.
+    pub requires_python: Option<VersionSpecifiers>,
+    /// The build backend in `build-system.build-backend`, if any.
+    pub build_backend: Option<String>,
+    /// The requirements in `project.dependencies`.
+    pub dependencies: Vec<DependencyMetadata>,
+    /// The requirements in `project.optional-dependencies`, by extra.
+    pub optional_dependencies: BTreeMap<ExtraName, Vec<DependencyMetadata>>,
+    /// The requirements in each dependency group, including `tool.uv.dev-dependencies` (as
+    /// `dev`), with any `include-group` entries resolved.
+    pub dependency_groups: BTreeMap<GroupName, Vec<DependencyMetadata>>,
+}
+
+/// A description of a requirement.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DependencyMetadata {
+    /// The requirement, as written.
+    pub requirement: String,
+    /// The name of the dependency.
+    pub name: PackageName,
+    /// The extras of the dependency.
+    pub extras: Vec<ExtraName>,
+    /// The version specifiers, if any, e.g., `>=1.0, <2`.
+    pub specifiers: Option<VersionSpecifiers>,
+    /// The URL, for a direct URL dependency.
+    pub url: Option<String>,
+    /// The markers that gate the dependency, if any, in normalized form.
+    pub marker: Option<String>,
+    /// The sources for the dependency in `tool.uv.sources`, falling back to those of the
+    /// workspace root.
+    pub sources: Vec<Source>,
+}
+
+impl Workspace {
+    /// Describe the workspace and its members, e.g., to serialize as JSON.
+    pub fn metadata(&self) -> Result<WorkspaceMetadata, WorkspaceError> {
+        let members = self
+            .packages()
+            .iter()
+            .map(|(name, member)| self.member_metadata(name, member))
+            .collect::<Result<_, _>>()?;
+        Ok(WorkspaceMetadata {
+            version: WORKSPACE_METADATA_VERSION,
+            root: PortablePathBuf::from(self.install_path().as_path()),
+            members,
+        })
+    }
+
+    fn member_metadata(
+        &self,
+        name: &PackageName,
+        member: &WorkspaceMember,
+    ) -> Result<MemberMetadata, WorkspaceError> {
+        let parse = |requirement: &str| {
+            Requirement::<VerbatimParsedUrl>::from_str(requirement)
+                .map(|parsed| self.dependency_metadata(member, requirement.to_string(), parsed))
+                .map_err(|err| {
+                    WorkspaceError::DependencyParse(
+                        name.clone(),
+                        requirement.to_string(),
+                        Box::new(err),
+                    )
+                })
+        };
+
+        let project = member.project();
+        let dependencies = project
+            .dependencies
+            .iter()
+            .flatten()
+            .map(|requirement| parse(requirement))
+            .collect::<Result<_, _>>()?;
+        let optional_dependencies = project
+            .optional_dependencies
+            .iter()
+            .flatten()
+            .map(|(extra, requirements)| {
+                let requirements = requirements
+                    .iter()
+                    .map(|requirement| parse(requirement))
+                    .collect::<Result<_, _>>()?;
+                Ok((extra.clone(), requirements))
+            })
+            .collect::<Result<_, WorkspaceError>>()?;
+        let dependency_groups =
+            FlatDependencyGroups::from_pyproject_toml(member.root(), member.pyproject_toml())?
+                .into_iter()
+                .map(|(group, flat_group)| {
+                    let requirements = flat_group
+                        .requirements
+                        .into_iter()
+                        .map(|requirement| {
+                            self.dependency_metadata(member, requirement.to_string(), requirement)
+                        })
+                        .collect();
+                    (group, requirements)
+                })
+                .collect();
+
+        Ok(MemberMetadata {
+            name: name.clone(),
+            path: PortablePathBuf::from(
+                member
+                    .root()
+                    .strip_prefix(self.install_path())
+                    .unwrap_or(member.root()),
+            ),
+            version: project.version.clone(),
+            requires_python: project.requires_python.clone(),
+            build_backend: build_backend(&member.pyproject_toml().raw),
+            dependencies,
+            optional_dependencies,
+            dependency_groups,
+        })
+    }
+
+    fn dependency_metadata(
+        &self,
+        member: &WorkspaceMember,
+        requirement: String,
+        parsed: Requirement<VerbatimParsedUrl>,
+    ) -> DependencyMetadata {
+        let sources = member
+            .pyproject_toml()
+            .tool
+            .as_ref()
+            .and_then(|tool| tool.uv.as_ref())
+            .and_then(|uv| uv.sources.as_ref())
+            .and_then(|sources| sources.inner().get(&parsed.name))
+            .or_else(|| self.sources().get(&parsed.name))
+            .map(|sources| sources.iter().cloned().collect())
+            .unwrap_or_default();
+        let (specifiers, url) = match parsed.version_or_url {
+            Some(VersionOrUrl::VersionSpecifier(specifiers)) => (Some(specifiers), None),
+            Some(VersionOrUrl::Url(url)) => (None, Some(url.to_string())),
+            None => (None, None),
+        };
+        DependencyMetadata {
+            requirement,
+            name: parsed.name,
+            extras: parsed.extras.into_vec(),
+            specifiers,
+            url,
+            marker: parsed.marker.try_to_string(),
+            sources,
+        }
+    }
+}
+
+/// Read `build-system.build-backend` from a `pyproject.toml`.
+fn build_backend(raw: &str) -> Option<String> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    struct PyProjectToml {
+        build_system: Option<BuildSystem>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    struct BuildSystem {
+        build_backend: Option<String>,
+    }
+
+    toml::from_str::<PyProjectToml>(raw)
+        .ok()?
+        .build_system?
+        .build_backend
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use assert_fs::fixture::ChildPath;
+    use assert_fs::prelude::*;
+    use serde_json::Value;
+
+    use crate::{DiscoveryOptions, Workspace, WorkspaceCache};
+
+    /// The checked-in schema for [`super::WorkspaceMetadata`].
+    const SCHEMA: &str = include_str!("../workspace-metadata.schema.json");
+
+    /// Validate a JSON value against the subset of JSON Schema used by [`SCHEMA`], returning the
+    /// path to each violation.
+    fn validate(value: &Value, schema: &Value, root: &Value, path: &str, errors: &mut Vec<String>) {
+        if let Some(reference) = schema["$ref"].as_str() {
+            let name = reference.trim_start_matches("#/definitions/");
+            validate(value, &root["definitions"][name], root, path, errors);
+            return;
+        }
+        if let Some(options) = schema["anyOf"].as_array() {
+            let matches = options.iter().any(|option| {
+                let mut option_errors = Vec::new();
+                validate(value, option, root, path, &mut option_errors);
+                option_errors.is_empty()
+            });
+            if !matches {
+                errors.push(format!("{path}: matches none of `anyOf`"));
+            }
+            return;
+        }
+        if let Some(expected) = schema.get("const")
+            && value != expected
+        {
+            errors.push(format!("{path}: expected {expected}, found {value}"));
+        }
+        let types = match &schema["type"] {
+            Value::String(kind) => vec![kind.as_str()],
+            Value::Array(kinds) => kinds.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() {
+            let kind = match value {
+                Value::Null => "null",
+                Value::Bool(_) => "boolean",
+                Value::Number(number) if number.is_u64() || number.is_i64() => "integer",
+                Value::Number(_) => "number",
+                Value::String(_) => "string",
+                Value::Array(_) => "array",
+                Value::Object(_) => "object",
+            };
+            if !types.contains(&kind) && (kind != "integer" || !types.contains(&"number")) {
+                errors.push(format!("{path}: expected {types:?}, found {kind}"));
+                return;
+            }
+        }
+        match value {
+            Value::Object(object) => {
+                for required in schema["required"].as_array().into_iter().flatten() {
+                    if let Some(required) = required.as_str()
+                        && !object.contains_key(required)
+                    {
+                        errors.push(format!("{path}: missing `{required}`"));
+                    }
+                }
+                for (key, value) in object {
+                    let path = format!("{path}.{key}");
+                    if let Some(property) = schema["properties"].get(key) {
+                        validate(value, property, root, &path, errors);
+                    } else {
+                        match &schema["additionalProperties"] {
+                            Value::Bool(false) => errors.push(format!("{path}: unexpected key")),
+                            Value::Object(_) => validate(
+                                value,
+                                &schema["additionalProperties"],
+                                root,
+                                &path,
+                                errors,
+                            ),
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            Value::Array(items) => {
+                if schema["items"].is_object() {
+                    for (index, item) in items.iter().enumerate() {
+                        validate(
+                            item,
+                            &schema["items"],
+                            root,
+                            &format!("{path}[{index}]"),
+                            errors,
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[tokio::test]
+    async fn metadata_matches_schema() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+        root.child("pyproject.toml").write_str(
+            r#"
+            [project]
+            name = "albatross"
+            version = "0.1.0"
+            requires-python = ">=3.12"
+            dependencies = [
+                "bird-feeder",
+                "anyio[trio]>=4,<5",
+                "tomli ; python_version < '3.11'",
+                "iniconfig @ https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl",
+            ]
+
+            [project.optional-dependencies]
+            cli = ["click>=8"]
+
+            [dependency-groups]
+            lint = ["ruff"]
+            dev = ["pytest", { include-group = "lint" }]
+
+            [tool.uv.workspace]
+            members = ["packages/*"]
+
+            [tool.uv.sources]
+            bird-feeder = { workspace = true }
+            anyio = { git = "https://github.com/agronholm/anyio", tag = "4.6.0" }
+
+            [build-system]
+            requires = ["hatchling"]
+            build-backend = "hatchling.build"
+            "#,
+        )?;
+        root.child("packages")
+            .child("bird-feeder")
+            .child("pyproject.toml")
+            .write_str(
+                r#"
+                [project]
+                name = "bird-feeder"
+                dynamic = ["version"]
+                dependencies = ["anyio"]
+                "#,
+            )?;
+        let workspace = Workspace::discover(
+            root.path(),
+            &DiscoveryOptions::default(),
+            &WorkspaceCache::default(),
+        )
+        .await?;
+
+        let metadata = serde_json::to_value(workspace.metadata()?)?;
+        assert_eq!(metadata["version"], 1);
+        assert_eq!(metadata["members"][1]["path"], "packages/bird-feeder");
+        assert_eq!(metadata["members"][1]["version"], Value::Null);
+        assert_eq!(
+            metadata["members"][0]["dependencies"][1]["sources"][0]["tag"],
+            "4.6.0"
+        );
+        assert_eq!(
+            metadata["members"][0]["dependency-groups"]["dev"][1]["name"],
+            "ruff"
+        );
+        let schema = serde_json::from_str::<Value>(SCHEMA)?;
+        let mut errors = Vec::new();
+        validate(&metadata, &schema, &schema, "$", &mut errors);
+        assert!(errors.is_empty(), "{errors:#?}");
+
+        // Incompatible output is rejected.
+        let mut incompatible = metadata.clone();
+        incompatible["version"] = Value::from(2);
+        incompatible["members"][0]["dependencies"][0]["name"] = Value::Null;
+        let mut errors = Vec::new();
+        validate(&incompatible, &schema, &schema, "$", &mut errors);
+        assert_eq!(
+            errors,
+            [
+                "$.members[0].dependencies[0].name: expected [\"string\"], found null",
+                "$.version: expected 1, found 2",
+            ]
+        );
+
+        Ok(())
+    }
+}
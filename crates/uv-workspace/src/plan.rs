@@ -0,0 +1,911 @@
+//! Applying a declarative plan of edits (e.g., from a JSON or TOML file) to the members of a
+//! workspace.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::de::{Error as _, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use thiserror::Error;
+use tracing::debug;
+
+use uv_distribution_types::{Index, IndexName, IndexNameError, IndexUrl, IndexUrlError};
+use uv_normalize::PackageName;
+use uv_pep440::{Version, VersionParseError, VersionSpecifiers, VersionSpecifiersParseError};
+use uv_pep508::{MarkerTree, Pep508Error, Requirement};
+
+use crate::edit::SkipReason;
+use crate::pyproject::{DependencyType, Source};
+use crate::pyproject_mut::{
+    self, DependencyTarget, PyProjectEdit, PyProjectTomlMut, dependency_type,
+};
+use crate::{Workspace, WorkspaceError};
+
+/// A list of edits to apply to the members of a [`Workspace`], e.g., as written by a bot or a
+/// migration script.
+///
+/// The operations share their vocabulary with [`PyProjectEdit`], such that the edits recorded on
+/// one `pyproject.toml` (see [`WorkspacePlan::from_edits`]) can be replayed on others. For
+/// example, in JSON:
+///
+/// ```json
+/// {
+///   "operations": [
+///     { "kind": "add-dependency", "location": "project.dependencies", "requirement": "anyio>=4" },
+///     { "kind": "set-requires-python", "specifiers": ">=3.10" }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct WorkspacePlan {
+    /// The members to apply the plan to, or all members if omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub members: Option<Vec<PackageName>>,
+    /// The operations to apply to each member, in order.
+    #[serde(deserialize_with = "deserialize_operations")]
+    pub operations: Vec<PlanOperation>,
+}
+
+/// An operation in a [`WorkspacePlan`].
+///
+/// Requirements, markers, specifiers and sources are written as they would be in a
+/// `pyproject.toml`, e.g., `anyio>=4` or `{ index = "pytorch" }`. Sections are written as their
+/// dotted path, e.g., `project.dependencies`, `project.optional-dependencies.cli`,
+/// `dependency-groups.dev`, or `tool.uv.dev-dependencies`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum PlanOperation {
+    /// Add a requirement to a section, replacing any requirement for the same package with the
+    /// same marker.
+    AddDependency {
+        location: String,
+        requirement: String,
+        /// The source to add to `tool.uv.sources`, if any.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        source: Option<String>,
+    },
+    /// Remove the requirements for the given packages from a section.
+    RemoveDependency {
+        location: String,
+        /// The packages to remove, as names or requirements.
+        removed: Vec<String>,
+    },
+    /// Create an empty section, e.g., `dependency-groups.dev`.
+    CreateSection { location: String },
+    /// Add a member to `tool.uv.workspace.members`.
+    AddWorkspaceMember { member: String },
+    /// Add an index to `tool.uv.index`, or move it to the top.
+    AddIndex {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+        url: String,
+    },
+    /// Set a key, i.e., `project.version` or `project.requires-python`.
+    SetKey {
+        /// The dotted path of the key.
+        path: String,
+        new: Option<String>,
+    },
+    /// Replace the version specifiers of the requirements for a package.
+    Upgrade {
+        package: PackageName,
+        specifiers: String,
+        /// The section to upgrade the package in, or all sections if omitted.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        location: Option<String>,
+    },
+    /// Replace the marker of the requirements for a package.
+    SetMarker {
+        package: PackageName,
+        marker: String,
+        /// The section to update the package in, or all sections if omitted.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        location: Option<String>,
+    },
+    /// Pin a package to the index with the given name, via `tool.uv.sources`.
+    PinToIndex { package: PackageName, index: String },
+    /// Add a source for a package to `tool.uv.sources`.
+    AddSource {
+        package: PackageName,
+        source: String,
+    },
+    /// Set `project.requires-python`.
+    SetRequiresPython { specifiers: String },
+}
+
+impl From<&PyProjectEdit> for PlanOperation {
+    fn from(edit: &PyProjectEdit) -> Self {
+        match edit {
+            PyProjectEdit::AddDependency {
+                location,
+                requirement,
+                source,
+                ..
+            } => Self::AddDependency {
+                location: location.clone(),
+                requirement: requirement.clone(),
+                source: source.clone(),
+            },
+            PyProjectEdit::RemoveDependency {
+                location, removed, ..
+            } => Self::RemoveDependency {
+                location: location.clone(),
+                removed: removed.clone(),
+            },
+            PyProjectEdit::CreateSection { location } => Self::CreateSection {
+                location: location.clone(),
+            },
+            PyProjectEdit::AddWorkspaceMember { member } => Self::AddWorkspaceMember {
+                member: member.clone(),
+            },
+            PyProjectEdit::AddIndex { name, url } => Self::AddIndex {
+                name: name.clone(),
+                url: url.clone(),
+            },
+            PyProjectEdit::SetKey { path, new, .. } => Self::SetKey {
+                path: path.clone(),
+                new: new.clone(),
+            },
+        }
+    }
+}
+
+impl WorkspacePlan {
+    /// Create a plan that replays the given edits, e.g., the [`PyProjectTomlMut::edits`] of a
+    /// `pyproject.toml`, on all members.
+    ///
+    /// The edits are replayed as recorded, so any credentials redacted in the log are written as
+    /// redacted.
+    pub fn from_edits(edits: &[PyProjectEdit]) -> Self {
+        Self {
+            members: None,
+            operations: edits.iter().map(PlanOperation::from).collect(),
+        }
+    }
+}
+
+/// Deserialize the operations of a [`WorkspacePlan`], reporting the index of an invalid
+/// operation (e.g., one with an unknown `kind`).
+fn deserialize_operations<'de, D>(deserializer: D) -> Result<Vec<PlanOperation>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct OperationsVisitor;
+
+    impl<'de> Visitor<'de> for OperationsVisitor {
+        type Value = Vec<PlanOperation>;
+
+        fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+            formatter.write_str("a list of operations")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut operations = Vec::new();
+            loop {
+                match seq.next_element::<PlanOperation>() {
+                    Ok(Some(operation)) => operations.push(operation),
+                    Ok(None) => return Ok(operations),
+                    Err(err) => {
+                        return Err(A::Error::custom(format!(
+                            "invalid operation at index {}: {err}",
+                            operations.len()
+                        )));
+                    }
+                }
+            }
+        }
+    }
+
+    deserializer.deserialize_seq(OperationsVisitor)
+}
+
+/// An operation in a [`WorkspacePlan`] that can't be applied.
+#[derive(Debug, Error)]
+pub enum PlanError {
+    #[error(
+        "Unknown section `{0}`; expected `project.dependencies`, `project.optional-dependencies.<extra>`, `dependency-groups.<group>`, or `tool.uv.dev-dependencies`"
+    )]
+    UnknownSection(String),
+    #[error("Section `{0}` can't be created; only optional dependencies and dependency groups can")]
+    CreateSection(String),
+    #[error(
+        "Setting `{0}` isn't supported; expected `project.version` or `project.requires-python`"
+    )]
+    UnknownKey(String),
+    #[error("Removing `{0}` isn't supported")]
+    RemoveKey(String),
+    #[error("Failed to parse requirement: `{0}`")]
+    Requirement(String, #[source] Box<Pep508Error>),
+    #[error("Failed to parse marker: `{0}`")]
+    Marker(String, #[source] Box<Pep508Error>),
+    #[error("Failed to parse version specifiers: `{0}`")]
+    Specifiers(String, #[source] VersionSpecifiersParseError),
+    #[error("Failed to parse version: `{0}`")]
+    Version(String, #[source] VersionParseError),
+    #[error("Failed to parse source: `{0}`")]
+    Source(String, #[source] Box<toml::de::Error>),
+    #[error("Invalid index name: `{0}`")]
+    IndexName(String, #[source] IndexNameError),
+    #[error("Invalid index URL: `{0}`")]
+    IndexUrl(String, #[source] IndexUrlError),
+}
+
+/// A validated [`PlanOperation`], ready to be applied.
+#[derive(Debug, Clone)]
+enum Step {
+    AddDependency {
+        dependency_type: DependencyType,
+        requirement: Box<Requirement>,
+        source: Option<Box<Source>>,
+    },
+    RemoveDependency {
+        dependency_type: DependencyType,
+        names: Vec<PackageName>,
+    },
+    CreateSection(DependencyType),
+    AddWorkspaceMember(String),
+    AddIndex(Box<Index>),
+    SetVersion(Version),
+    SetRequiresPython(VersionSpecifiers),
+    Upgrade {
+        package: PackageName,
+        specifiers: VersionSpecifiers,
+        dependency_type: Option<DependencyType>,
+    },
+    SetMarker {
+        package: PackageName,
+        marker: MarkerTree,
+        dependency_type: Option<DependencyType>,
+    },
+    AddSource {
+        package: PackageName,
+        source: Box<Source>,
+    },
+}
+
+impl PlanOperation {
+    /// Parse the parameters of the operation.
+    fn validate(&self) -> Result<Step, PlanError> {
+        match self {
+            Self::AddDependency {
+                location,
+                requirement,
+                source,
+            } => Ok(Step::AddDependency {
+                dependency_type: parse_section(location)?,
+                requirement: Box::new(parse_requirement(requirement)?),
+                source: source
+                    .as_deref()
+                    .map(parse_source)
+                    .transpose()?
+                    .map(Box::new),
+            }),
+            Self::RemoveDependency { location, removed } => Ok(Step::RemoveDependency {
+                dependency_type: parse_section(location)?,
+                names: removed
+                    .iter()
+                    .map(|removed| parse_requirement(removed).map(|requirement| requirement.name))
+                    .collect::<Result<_, _>>()?,
+            }),
+            Self::CreateSection { location } => match parse_section(location)? {
+                dependency_type @ (DependencyType::Optional(_) | DependencyType::Group(_)) => {
+                    Ok(Step::CreateSection(dependency_type))
+                }
+                DependencyType::Production | DependencyType::Dev => {
+                    Err(PlanError::CreateSection(location.clone()))
+                }
+            },
+            Self::AddWorkspaceMember { member } => Ok(Step::AddWorkspaceMember(member.clone())),
+            Self::AddIndex { name, url } => {
+                let name = name
+                    .as_deref()
+                    .map(|name| {
+                        IndexName::from_str(name)
+                            .map_err(|err| PlanError::IndexName(name.to_string(), err))
+                    })
+                    .transpose()?;
+                let url =
+                    IndexUrl::from_str(url).map_err(|err| PlanError::IndexUrl(url.clone(), err))?;
+                Ok(Step::AddIndex(Box::new(Index {
+                    name,
+                    ..Index::from_extra_index_url(url)
+                })))
+            }
+            Self::SetKey { path, new: None } => Err(PlanError::RemoveKey(path.clone())),
+            Self::SetKey {
+                path,
+                new: Some(new),
+            } => match path.as_str() {
+                "project.version" => Version::from_str(new)
+                    .map(Step::SetVersion)
+                    .map_err(|err| PlanError::Version(new.clone(), err)),
+                "project.requires-python" => parse_specifiers(new).map(Step::SetRequiresPython),
+                _ => Err(PlanError::UnknownKey(path.clone())),
+            },
+            Self::Upgrade {
+                package,
+                specifiers,
+                location,
+            } => Ok(Step::Upgrade {
+                package: package.clone(),
+                specifiers: parse_specifiers(specifiers)?,
+                dependency_type: location.as_deref().map(parse_section).transpose()?,
+            }),
+            Self::SetMarker {
+                package,
+                marker,
+                location,
+            } => Ok(Step::SetMarker {
+                package: package.clone(),
+                marker: MarkerTree::from_str(marker)
+                    .map_err(|err| PlanError::Marker(marker.clone(), Box::new(err)))?,
+                dependency_type: location.as_deref().map(parse_section).transpose()?,
+            }),
+            Self::PinToIndex { package, index } => Ok(Step::AddSource {
+                package: package.clone(),
+                source: Box::new(Source::Registry {
+                    index: IndexName::from_str(index)
+                        .map_err(|err| PlanError::IndexName(index.clone(), err))?,
+                    marker: MarkerTree::TRUE,
+                    extra: None,
+                    group: None,
+                }),
+            }),
+            Self::AddSource { package, source } => Ok(Step::AddSource {
+                package: package.clone(),
+                source: Box::new(parse_source(source)?),
+            }),
+            Self::SetRequiresPython { specifiers } => {
+                parse_specifiers(specifiers).map(Step::SetRequiresPython)
+            }
+        }
+    }
+}
+
+fn parse_section(location: &str) -> Result<DependencyType, PlanError> {
+    dependency_type(location).ok_or_else(|| PlanError::UnknownSection(location.to_string()))
+}
+
+fn parse_requirement(requirement: &str) -> Result<Requirement, PlanError> {
+    Requirement::from_str(requirement)
+        .map_err(|err| PlanError::Requirement(requirement.to_string(), Box::new(err)))
+}
+
+fn parse_specifiers(specifiers: &str) -> Result<VersionSpecifiers, PlanError> {
+    VersionSpecifiers::from_str(specifiers)
+        .map_err(|err| PlanError::Specifiers(specifiers.to_string(), err))
+}
+
+/// Parse a source, written as a TOML inline table, e.g., `{ index = "pytorch" }`.
+fn parse_source(source: &str) -> Result<Source, PlanError> {
+    #[derive(Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct Wrapper {
+        source: Source,
+    }
+
+    toml::from_str::<Wrapper>(&format!("source = {source}"))
+        .map(|wrapper| wrapper.source)
+        .map_err(|err| PlanError::Source(source.to_string(), Box::new(err)))
+}
+
+/// The outcome of applying an operation to a workspace member.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanOutcome {
+    /// The operation modified the member's `pyproject.toml`.
+    Changed,
+    /// The member's `pyproject.toml` already reflected the operation.
+    Unchanged,
+    /// The operation didn't apply to the member.
+    Skipped(SkipReason),
+}
+
+impl Display for PlanOutcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Changed => write!(f, "changed"),
+            Self::Unchanged => write!(f, "unchanged"),
+            Self::Skipped(reason) => write!(f, "skipped: {reason}"),
+        }
+    }
+}
+
+/// The result of applying a [`WorkspacePlan`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlanReport {
+    /// For each operation in the plan, in order, its outcome for each member it was applied to.
+    pub operations: Vec<BTreeMap<PackageName, PlanOutcome>>,
+    /// The members whose `pyproject.toml` was written.
+    pub written: Vec<PackageName>,
+}
+
+impl Workspace {
+    /// Apply the operations of a plan to the selected workspace members.
+    ///
+    /// The plan is applied transactionally, as far as the filesystem allows: every operation is
+    /// validated, and then applied to every member in memory, before any `pyproject.toml` is
+    /// written. If an operation is invalid, or fails for any member, nothing is written. Once
+    /// written, the workspace is updated to reflect the edited members.
+    pub fn apply_plan(&mut self, plan: &WorkspacePlan) -> Result<PlanReport, WorkspaceError> {
+        let steps = plan
+            .operations
+            .iter()
+            .enumerate()
+            .map(|(index, operation)| {
+                operation
+                    .validate()
+                    .map_err(|err| WorkspaceError::InvalidPlan(index, err))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let names = match &plan.members {
+            Some(names) => {
+                if let Some(name) = names
+                    .iter()
+                    .find(|name| !self.packages().contains_key(*name))
+                {
+                    return Err(WorkspaceError::NoSuchMember(
+                        name.clone(),
+                        self.install_path().clone(),
+                    ));
+                }
+                names.clone()
+            }
+            None => self.packages().keys().cloned().collect(),
+        };
+
+        let mut report = PlanReport {
+            operations: vec![BTreeMap::new(); steps.len()],
+            written: Vec::new(),
+        };
+        let mut edited = Vec::new();
+        for name in names {
+            let path = self.packages()[&name].root().join("pyproject.toml");
+            let mut pyproject = PyProjectTomlMut::load(&path, DependencyTarget::PyProjectToml)
+                .map_err(|err| WorkspaceError::EditMember(name.clone(), Box::new(err)))?;
+            for (index, step) in steps.iter().enumerate() {
+                let outcome = apply(&mut pyproject, &name, step)
+                    .map_err(|err| WorkspaceError::ApplyPlan(index, name.clone(), Box::new(err)))?;
+                report.operations[index].insert(name.clone(), outcome);
+            }
+            if pyproject.is_modified() {
+                edited.push((name, path, pyproject));
+            }
+        }
+
+        for (name, path, mut pyproject) in edited {
+            pyproject
+                .save()
+                .map_err(|err| WorkspaceError::EditMember(name.clone(), Box::new(err)))?;
+            debug!("Applied plan to `{name}`");
+            let pyproject_toml = pyproject
+                .pyproject_toml()
+                .map_err(|err| WorkspaceError::Toml(path, Box::new(err)))?;
+            if let Some(workspace) = self.clone().update_member(&name, pyproject_toml)? {
+                *self = workspace;
+            }
+            report.written.push(name);
+        }
+
+        Ok(report)
+    }
+}
+
+/// Apply a validated operation to the `pyproject.toml` of the given member.
+fn apply(
+    pyproject: &mut PyProjectTomlMut,
+    member: &PackageName,
+    step: &Step,
+) -> Result<PlanOutcome, pyproject_mut::Error> {
+    let changed = match step {
+        Step::AddDependency {
+            dependency_type,
+            requirement,
+            source,
+        } => {
+            if requirement.name == *member {
+                return Ok(PlanOutcome::Skipped(SkipReason::SelfDependency));
+            }
+            pyproject
+                .add_requirement(dependency_type, requirement, source.as_deref(), false)?
+                .is_changed()
+        }
+        Step::RemoveDependency {
+            dependency_type,
+            names,
+        } => {
+            let mut changed = false;
+            for name in names {
+                changed |= !pyproject
+                    .remove_requirement(dependency_type, name)?
+                    .is_empty();
+            }
+            changed
+        }
+        Step::CreateSection(DependencyType::Optional(extra)) => {
+            pyproject.ensure_optional_dependency(extra)?
+        }
+        Step::CreateSection(DependencyType::Group(group)) => {
+            pyproject.ensure_dependency_group(group)?
+        }
+        Step::CreateSection(DependencyType::Production | DependencyType::Dev) => false,
+        Step::AddWorkspaceMember(member) => pyproject.add_workspace(member)?,
+        Step::AddIndex(index) => pyproject.add_index(index)?,
+        Step::SetVersion(version) => pyproject.set_version(version)?,
+        Step::SetRequiresPython(specifiers) => pyproject.set_requires_python(specifiers)?,
+        Step::Upgrade {
+            package,
+            specifiers,
+            dependency_type,
+        } => {
+            let Some(entries) = entries(pyproject, package, dependency_type.as_ref()) else {
+                return Ok(PlanOutcome::Skipped(SkipReason::MissingDependency));
+            };
+            let mut changed = false;
+            for (dependency_type, index, _) in entries {
+                changed |= pyproject.set_dependency_specifiers(
+                    &dependency_type,
+                    index,
+                    specifiers.clone(),
+                )?;
+            }
+            changed
+        }
+        Step::SetMarker {
+            package,
+            marker,
+            dependency_type,
+        } => {
+            let Some(entries) = entries(pyproject, package, dependency_type.as_ref()) else {
+                return Ok(PlanOutcome::Skipped(SkipReason::MissingDependency));
+            };
+            let mut changed = false;
+            for (dependency_type, index, _) in entries {
+                changed |= pyproject.set_dependency_marker(&dependency_type, index, *marker)?;
+            }
+            changed
+        }
+        Step::AddSource { package, source } => {
+            let Some(entries) = entries(pyproject, package, None) else {
+                return Ok(PlanOutcome::Skipped(SkipReason::MissingDependency));
+            };
+            let mut changed = false;
+            for (dependency_type, _, requirement) in entries {
+                changed |= pyproject
+                    .add_requirement(&dependency_type, &requirement, Some(source), false)?
+                    .is_changed();
+            }
+            changed
+        }
+    };
+    Ok(if changed {
+        PlanOutcome::Changed
+    } else {
+        PlanOutcome::Unchanged
+    })
+}
+
+/// Returns the requirements for the given package, in the given section or in all sections, or
+/// `None` if there are none.
+fn entries(
+    pyproject: &PyProjectTomlMut,
+    package: &PackageName,
+    dependency_type: Option<&DependencyType>,
+) -> Option<Vec<(DependencyType, usize, Requirement)>> {
+    let dependency_types = match dependency_type {
+        Some(dependency_type) => vec![dependency_type.clone()],
+        None => pyproject.find_dependency(package, None),
+    };
+    let entries = dependency_types
+        .into_iter()
+        .flat_map(|dependency_type| {
+            pyproject
+                .find_requirements(&dependency_type, package)
+                .into_iter()
+                .map(move |(index, requirement)| (dependency_type.clone(), index, requirement))
+        })
+        .collect::<Vec<_>>();
+    if entries.is_empty() {
+        None
+    } else {
+        Some(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use anyhow::Result;
+    use assert_fs::fixture::ChildPath;
+    use assert_fs::prelude::*;
+    use insta::{assert_debug_snapshot, assert_snapshot};
+
+    use uv_normalize::PackageName;
+    use uv_pep440::Version;
+    use uv_pep508::Requirement;
+
+    use crate::pyproject_mut::{DependencyTarget, PyProjectTomlMut};
+    use crate::{
+        DiscoveryOptions, PlanReport, Workspace, WorkspaceCache, WorkspaceError, WorkspacePlan,
+    };
+
+    /// Create a workspace with a root (`albatross`) and a member (`seeds`).
+    fn workspace_fixture(root: &ChildPath) -> Result<()> {
+        root.child("pyproject.toml").write_str(
+            r#"[project]
+name = "albatross"
+version = "0.1.0"
+requires-python = ">=3.9"
+dependencies = ["anyio>=3", "seeds"]
+
+[tool.uv.sources]
+seeds = { workspace = true }
+
+[tool.uv.workspace]
+members = ["packages/*"]
+"#,
+        )?;
+        root.child("packages")
+            .child("seeds")
+            .child("pyproject.toml")
+            .write_str(
+                r#"[project]
+name = "seeds"
+version = "1.0.0"
+requires-python = ">=3.9"
+dependencies = ["anyio>=3", "tomli"]
+"#,
+            )?;
+        Ok(())
+    }
+
+    async fn discover(root: &ChildPath) -> Result<Workspace> {
+        Ok(Workspace::discover(
+            root.path(),
+            &DiscoveryOptions::default(),
+            &WorkspaceCache::default(),
+        )
+        .await?)
+    }
+
+    /// Summarize the outcomes of each operation.
+    fn outcomes(report: &PlanReport) -> Vec<String> {
+        report
+            .operations
+            .iter()
+            .enumerate()
+            .flat_map(|(index, outcomes)| {
+                outcomes
+                    .iter()
+                    .map(move |(name, outcome)| format!("{index} {name}: {outcome}"))
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn apply_plan() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+        workspace_fixture(&root)?;
+        let mut workspace = discover(&root).await?;
+
+        let plan: WorkspacePlan = serde_json::from_str(
+            r#"{
+                "operations": [
+                    { "kind": "upgrade", "package": "anyio", "specifiers": ">=4" },
+                    { "kind": "add-dependency", "location": "dependency-groups.dev", "requirement": "pytest>=8" },
+                    { "kind": "set-marker", "package": "tomli", "marker": "python_version < '3.11'" },
+                    { "kind": "add-index", "name": "internal", "url": "https://pypi.example.com/simple" },
+                    { "kind": "pin-to-index", "package": "tomli", "index": "internal" },
+                    { "kind": "add-dependency", "location": "project.dependencies", "requirement": "seeds>=1" },
+                    { "kind": "remove-dependency", "location": "project.dependencies", "removed": ["rich"] },
+                    { "kind": "set-requires-python", "specifiers": ">=3.10" }
+                ]
+            }"#,
+        )?;
+        let report = workspace.apply_plan(&plan)?;
+        assert_debug_snapshot!(outcomes(&report), @r#"
+        [
+            "0 albatross: changed",
+            "0 seeds: changed",
+            "1 albatross: changed",
+            "1 seeds: changed",
+            "2 albatross: skipped: the member doesn't depend on the package",
+            "2 seeds: changed",
+            "3 albatross: changed",
+            "3 seeds: changed",
+            "4 albatross: skipped: the member doesn't depend on the package",
+            "4 seeds: changed",
+            "5 albatross: changed",
+            "5 seeds: skipped: the dependency refers to the member itself",
+            "6 albatross: unchanged",
+            "6 seeds: unchanged",
+            "7 albatross: changed",
+            "7 seeds: changed",
+        ]
+        "#);
+        assert_eq!(report.written.len(), 2);
+
+        assert_snapshot!(fs_err::read_to_string(root.child("pyproject.toml"))?, @r#"
+        [project]
+        name = "albatross"
+        version = "0.1.0"
+        requires-python = ">=3.10"
+        dependencies = [
+            "anyio>=4",
+            "seeds>=1",
+        ]
+
+        [tool.uv.sources]
+        seeds = { workspace = true }
+
+        [tool.uv.workspace]
+        members = ["packages/*"]
+
+        [[tool.uv.index]]
+        name = "internal"
+        url = "https://pypi.example.com/simple"
+
+        [dependency-groups]
+        dev = [
+            "pytest>=8",
+        ]
+        "#);
+        assert_snapshot!(
+            fs_err::read_to_string(root.join("packages/seeds/pyproject.toml"))?,
+            @r#"
+        [project]
+        name = "seeds"
+        version = "1.0.0"
+        requires-python = ">=3.10"
+        dependencies = ["anyio>=4", "tomli ; python_full_version < '3.11'"]
+
+        [dependency-groups]
+        dev = [
+            "pytest>=8",
+        ]
+
+        [[tool.uv.index]]
+        name = "internal"
+        url = "https://pypi.example.com/simple"
+
+        [tool.uv.sources]
+        tomli = { index = "internal" }
+        "#
+        );
+
+        // The workspace reflects the edited members.
+        let requires_python = workspace.packages()[&PackageName::from_str("seeds")?]
+            .pyproject_toml()
+            .project
+            .as_ref()
+            .and_then(|project| project.requires_python.as_ref())
+            .map(ToString::to_string);
+        assert_eq!(requires_python.as_deref(), Some(">=3.10"));
+
+        Ok(())
+    }
+
+    /// Edits recorded on one `pyproject.toml` can be replayed on the members of a workspace.
+    #[tokio::test]
+    async fn replay_edits() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+        workspace_fixture(&root)?;
+        let mut workspace = discover(&root).await?;
+
+        let mut pyproject = PyProjectTomlMut::from_toml(
+            "[project]\nname = \"template\"\nversion = \"0.1.0\"\ndependencies = [\"anyio>=3\"]\n",
+            DependencyTarget::PyProjectToml,
+        )?;
+        pyproject.add_dependency(&Requirement::from_str("anyio>=4")?, None, false)?;
+        pyproject.remove_dependency(&PackageName::from_str("tomli")?)?;
+        pyproject.set_version(&Version::from_str("0.2.0")?)?;
+
+        // The log can be replayed directly, or serialized as the operations of a plan.
+        let plan = WorkspacePlan::from_edits(pyproject.edits());
+        let json = format!(
+            r#"{{ "operations": {} }}"#,
+            serde_json::to_string(pyproject.edits())?
+        );
+        assert_eq!(serde_json::from_str::<WorkspacePlan>(&json)?, plan);
+
+        let report = workspace.apply_plan(&plan)?;
+        assert_debug_snapshot!(outcomes(&report), @r#"
+        [
+            "0 albatross: changed",
+            "0 seeds: changed",
+            "1 albatross: changed",
+            "1 seeds: changed",
+        ]
+        "#);
+        assert_snapshot!(
+            fs_err::read_to_string(root.join("packages/seeds/pyproject.toml"))?,
+            @r#"
+        [project]
+        name = "seeds"
+        version = "0.2.0"
+        requires-python = ">=3.9"
+        dependencies = [
+            "anyio>=4",
+            "tomli",
+        ]
+        "#
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_kind() {
+        let err = serde_json::from_str::<WorkspacePlan>(
+            r#"{ "operations": [
+                { "kind": "set-requires-python", "specifiers": ">=3.10" },
+                { "kind": "frobnicate" }
+            ] }"#,
+        )
+        .unwrap_err();
+        assert_snapshot!(err, @"invalid operation at index 1: unknown variant `frobnicate`, expected one of `add-dependency`, `remove-dependency`, `create-section`, `add-workspace-member`, `add-index`, `set-key`, `upgrade`, `set-marker`, `pin-to-index`, `add-source`, `set-requires-python` at line 3 column 38");
+
+        let err =
+            toml::from_str::<WorkspacePlan>("[[operations]]\nkind = \"frobnicate\"\n").unwrap_err();
+        assert_snapshot!(err, @r"
+        TOML parse error at line 1, column 1
+          |
+        1 | [[operations]]
+          | ^^^^^^^^^^^^^^
+        invalid operation at index 0: unknown variant `frobnicate`, expected one of `add-dependency`, `remove-dependency`, `create-section`, `add-workspace-member`, `add-index`, `set-key`, `upgrade`, `set-marker`, `pin-to-index`, `add-source`, `set-requires-python`
+        in `kind`
+        ");
+    }
+
+    /// Nothing is written if any operation is invalid, or fails for any member.
+    #[tokio::test]
+    async fn apply_plan_transactional() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+        workspace_fixture(&root)?;
+        let mut workspace = discover(&root).await?;
+        let before = fs_err::read_to_string(root.child("pyproject.toml"))?;
+
+        let plan: WorkspacePlan = serde_json::from_str(
+            r#"{ "operations": [
+                { "kind": "add-dependency", "location": "project.dependencies", "requirement": "rich" },
+                { "kind": "add-dependency", "location": "project.extras", "requirement": "rich" }
+            ] }"#,
+        )?;
+        let err = workspace.apply_plan(&plan).unwrap_err();
+        assert!(matches!(err, WorkspaceError::InvalidPlan(1, _)));
+        assert_snapshot!(
+            format!("{err}: {}", std::error::Error::source(&err).unwrap()),
+            @"Invalid operation at index 1 of the plan: Unknown section `project.extras`; expected `project.dependencies`, `project.optional-dependencies.<extra>`, `dependency-groups.<group>`, or `tool.uv.dev-dependencies`"
+        );
+
+        // Make one member's dependencies malformed after discovery.
+        root.child("packages")
+            .child("seeds")
+            .child("pyproject.toml")
+            .write_str(
+                "[project]\nname = \"seeds\"\nversion = \"1.0.0\"\ndependencies = \"tomli\"\n",
+            )?;
+        let plan: WorkspacePlan = serde_json::from_str(
+            r#"{ "operations": [
+                { "kind": "add-dependency", "location": "project.dependencies", "requirement": "rich" }
+            ] }"#,
+        )?;
+        let err = workspace.apply_plan(&plan).unwrap_err();
+        assert_snapshot!(err, @"Failed to apply operation at index 0 of the plan to `seeds`");
+        assert_eq!(
+            fs_err::read_to_string(root.child("pyproject.toml"))?,
+            before
+        );
+
+        Ok(())
+    }
+}
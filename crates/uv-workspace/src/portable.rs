@@ -0,0 +1,155 @@
+//! Normalization of the paths that appear in a `pyproject.toml`, e.g., in `tool.uv.sources` and
+//! `tool.uv.workspace.members`.
+//!
+//! These paths are shared across platforms, so they're always written with forward slashes, and
+//! read with either separator. The normalization operates on strings, rather than on [`Path`]s,
+//! such that it behaves identically on all platforms.
+//!
+//! [`Path`]: std::path::Path
+
+use std::borrow::Cow;
+
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PortablePathError {
+    #[error(
+        "UNC paths can't be written to a `pyproject.toml`, since they aren't portable across platforms: `{0}`"
+    )]
+    Unc(String),
+}
+
+/// Convert Windows-style backslashes to forward slashes.
+pub(crate) fn to_forward_slashes(path: &str) -> Cow<'_, str> {
+    if path.contains('\\') {
+        Cow::Owned(path.replace('\\', "/"))
+    } else {
+        Cow::Borrowed(path)
+    }
+}
+
+/// Normalize a path before writing it to a `pyproject.toml`.
+///
+/// Backslashes are converted to forward slashes, and UNC paths (e.g., `\\server\share` or
+/// `\\?\C:\project`) are rejected. The path is otherwise written as given; in particular, relative
+/// paths remain relative.
+pub(crate) fn normalize_for_write(path: &str) -> Result<Cow<'_, str>, PortablePathError> {
+    let normalized = to_forward_slashes(path);
+    if normalized.starts_with("//") {
+        return Err(PortablePathError::Unc(path.to_string()));
+    }
+    Ok(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use anyhow::Result;
+    use insta::assert_snapshot;
+
+    use crate::portable::{PortablePathError, normalize_for_write, to_forward_slashes};
+    use crate::pyproject::{PyProjectToml, Source};
+    use crate::pyproject_mut::{DependencyTarget, PyProjectTomlMut};
+
+    #[test]
+    fn forward_slashes() {
+        assert_eq!(to_forward_slashes("packages/core"), "packages/core");
+        assert_eq!(to_forward_slashes(r"packages\core"), "packages/core");
+        assert_eq!(to_forward_slashes(r"..\libs\*"), "../libs/*");
+        assert_eq!(
+            to_forward_slashes(r".\packages/mixed\core"),
+            "./packages/mixed/core"
+        );
+        assert_eq!(to_forward_slashes(r"C:\Users\ferris"), "C:/Users/ferris");
+    }
+
+    #[test]
+    fn write_relative() {
+        assert_eq!(
+            normalize_for_write(r"packages\core").unwrap(),
+            "packages/core"
+        );
+        assert_eq!(normalize_for_write(r"..\sibling").unwrap(), "../sibling");
+        assert_eq!(normalize_for_write(".").unwrap(), ".");
+    }
+
+    #[test]
+    fn write_unc() {
+        for path in [
+            r"\\server\share\project",
+            r"\\?\C:\project",
+            r"\\.\pipe\project",
+            "//server/share/project",
+        ] {
+            assert_eq!(
+                normalize_for_write(path),
+                Err(PortablePathError::Unc(path.to_string()))
+            );
+        }
+        assert_snapshot!(
+            normalize_for_write(r"\\server\share").unwrap_err(),
+            @r"UNC paths can't be written to a `pyproject.toml`, since they aren't portable across platforms: `\\server\share`"
+        );
+    }
+
+    #[test]
+    fn add_workspace_member() -> Result<()> {
+        let mut pyproject = PyProjectTomlMut::from_toml(
+            "[project]\nname = \"albatross\"\n",
+            DependencyTarget::PyProjectToml,
+        )?;
+        pyproject.add_workspace(Path::new(r"packages\core"))?;
+        assert_snapshot!(pyproject.to_string(), @r#"
+        [project]
+        name = "albatross"
+
+        [tool.uv.workspace]
+        members = [
+            "packages/core",
+        ]
+        "#);
+
+        let err = pyproject
+            .add_workspace(Path::new(r"\\server\share\core"))
+            .unwrap_err();
+        assert_snapshot!(err, @r"UNC paths can't be written to a `pyproject.toml`, since they aren't portable across platforms: `\\server\share\core`");
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_path_source() -> Result<()> {
+        let pyproject = PyProjectToml::from_string(
+            r#"
+            [project]
+            name = "albatross"
+            version = "0.1.0"
+
+            [tool.uv.sources]
+            core = { path = 'packages\core', editable = true }
+            "#
+            .to_string(),
+            "pyproject.toml",
+        )?;
+        let sources = pyproject
+            .tool
+            .and_then(|tool| tool.uv)
+            .and_then(|uv| uv.sources)
+            .unwrap();
+        let source = sources
+            .inner()
+            .values()
+            .next()
+            .unwrap()
+            .iter()
+            .next()
+            .unwrap();
+        let Source::Path { path, .. } = source else {
+            panic!("expected a path source, found: {source:?}");
+        };
+        assert_eq!(path.to_string(), "packages/core");
+
+        Ok(())
+    }
+}
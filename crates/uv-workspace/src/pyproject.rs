@@ -20,6 +20,7 @@ use rustc_hash::{FxBuildHasher, FxHashSet};
 use serde::de::SeqAccess;
 use serde::{Deserialize, Deserializer, Serialize};
 use thiserror::Error;
+use toml_edit::DocumentMut;
 use tracing::instrument;
 use uv_build_backend::BuildBackendSettings;
 use uv_configuration::GitLfsSetting;
@@ -27,7 +28,7 @@ use uv_distribution_types::{Index, IndexName, RequirementSource};
 use uv_fs::{PortablePathBuf, relative_to};
 use uv_git_types::GitReference;
 use uv_macros::OptionsMetadata;
-use uv_normalize::{DefaultGroups, ExtraName, GroupName, PackageName};
+use uv_normalize::{DefaultGroups, ExtraName, GroupName, PackageName, VerbatimName};
 use uv_options_metadata::{OptionSet, OptionsMetadata, Visit};
 use uv_pep440::{Version, VersionSpecifiers};
 use uv_pep508::MarkerTree;
@@ -36,6 +37,8 @@ use uv_pypi_types::{
 };
 use uv_redacted::DisplaySafeUrl;
 
+use crate::portable;
+
 #[derive(Error, Debug)]
 pub enum PyprojectTomlError {
     #[error(transparent)]
@@ -127,6 +130,20 @@ impl PyProjectToml {
         Ok(Self { raw, ..pyproject })
     }
 
+    /// Deserialize a `PyProjectToml` from an already-parsed TOML document, without parsing the
+    /// raw TOML again.
+    ///
+    /// If the document fails to deserialize, the raw TOML is parsed instead, such that the error
+    /// retains its position in the file.
+    #[instrument("toml_edit::de::from_document workspace", skip_all)]
+    pub fn from_document(document: &DocumentMut) -> Result<Self, PyprojectTomlError> {
+        let raw = document.to_string();
+        match toml_edit::de::from_document::<Self>(document.clone()) {
+            Ok(pyproject) => Ok(Self { raw, ..pyproject }),
+            Err(_) => Self::from_string(raw, "pyproject.toml"),
+        }
+    }
+
     /// Returns `true` if the project should be considered a Python package, as opposed to a
     /// non-package ("virtual") project.
     pub fn is_package(&self, require_build_system: bool) -> bool {
@@ -215,6 +232,9 @@ pub struct Project {
     /// The optional dependencies of the project.
     pub optional_dependencies: Option<BTreeMap<ExtraName, Vec<String>>>,
 
+    /// The name of the project, as written in the `pyproject.toml`.
+    #[serde(skip_serializing)]
+    pub(crate) verbatim_name: VerbatimName,
     /// Used to determine whether a `gui-scripts` section is present.
     #[serde(default, skip_serializing)]
     pub(crate) gui_scripts: Option<serde::de::IgnoredAny>,
@@ -223,10 +243,17 @@ pub struct Project {
     pub(crate) scripts: Option<serde::de::IgnoredAny>,
 }
 
+impl Project {
+    /// Returns the name of the project as written, e.g., `My_Project` rather than `my-project`.
+    pub fn verbatim_name(&self) -> &VerbatimName {
+        &self.verbatim_name
+    }
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "kebab-case")]
 struct ProjectWire {
-    name: Option<PackageName>,
+    name: Option<VerbatimName>,
     version: Option<Version>,
     dynamic: Option<Vec<String>>,
     requires_python: Option<VersionSpecifiers>,
@@ -241,7 +268,7 @@ impl TryFrom<ProjectWire> for Project {
 
     fn try_from(value: ProjectWire) -> Result<Self, Self::Error> {
         // If `[project.name]` is not present, show a dedicated error message.
-        let name = value.name.ok_or(PyprojectTomlError::MissingName)?;
+        let verbatim_name = value.name.ok_or(PyprojectTomlError::MissingName)?;
 
         // If `[project.version]` is not present (or listed in `[project.dynamic]`), show a dedicated error message.
         if value.version.is_none()
@@ -254,11 +281,12 @@ impl TryFrom<ProjectWire> for Project {
         }
 
         Ok(Self {
-            name,
+            name: verbatim_name.as_normalized().clone(),
             version: value.version,
             requires_python: value.requires_python,
             dependencies: value.dependencies,
             optional_dependencies: value.optional_dependencies,
+            verbatim_name,
             gui_scripts: value.gui_scripts,
             scripts: value.scripts,
         })
@@ -1134,6 +1162,8 @@ pub enum Source {
         branch: Option<String>,
         /// Whether to use Git LFS when cloning the repository.
         lfs: Option<bool>,
+        /// Whether to check out the repository's submodules (the default) when cloning it.
+        submodules: Option<bool>,
         #[serde(
             skip_serializing_if = "uv_pep508::marker::ser::is_empty",
             serialize_with = "uv_pep508::marker::ser::serialize",
@@ -1233,6 +1263,7 @@ impl<'de> Deserialize<'de> for Source {
             tag: Option<String>,
             branch: Option<String>,
             lfs: Option<bool>,
+            submodules: Option<bool>,
             url: Option<DisplaySafeUrl>,
             path: Option<PortablePathBuf>,
             editable: Option<bool>,
@@ -1257,6 +1288,7 @@ impl<'de> Deserialize<'de> for Source {
             tag,
             branch,
             lfs,
+            submodules,
             url,
             path,
             editable,
@@ -1335,6 +1367,7 @@ impl<'de> Deserialize<'de> for Source {
                 tag,
                 branch,
                 lfs,
+                submodules,
                 marker,
                 extra,
                 group,
@@ -1443,6 +1476,9 @@ impl<'de> Deserialize<'de> for Source {
                 ));
             }
 
+            // Accept Windows-style separators on all platforms.
+            let path = PortablePathBuf::from(&*portable::to_forward_slashes(&path.to_string()));
+
             return Ok(Self::Path {
                 path,
                 editable,
@@ -1640,6 +1676,7 @@ impl Source {
                         if let Self::Git {
                             git,
                             subdirectory,
+                            submodules,
                             marker,
                             extra,
                             group,
@@ -1653,6 +1690,7 @@ impl Source {
                                 tag,
                                 branch,
                                 lfs: lfs.into(),
+                                submodules: *submodules,
                                 marker: *marker,
                                 extra: extra.clone(),
                                 group: group.clone(),
@@ -1782,6 +1820,7 @@ impl Source {
                         tag,
                         branch,
                         lfs: lfs.into(),
+                        submodules: git.submodules(),
                         git: git.repository().clone(),
                         subdirectory: subdirectory.map(PortablePathBuf::from),
                         marker: MarkerTree::TRUE,
@@ -1794,6 +1833,7 @@ impl Source {
                         tag,
                         branch,
                         lfs: lfs.into(),
+                        submodules: git.submodules(),
                         git: git.repository().clone(),
                         subdirectory: subdirectory.map(PortablePathBuf::from),
                         marker: MarkerTree::TRUE,
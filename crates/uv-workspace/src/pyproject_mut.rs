@@ -1,5 +1,8 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
-use std::path::Path;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::{fmt, iter, mem};
 
@@ -7,18 +10,36 @@ use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use toml_edit::{
-    Array, ArrayOfTables, DocumentMut, Formatted, Item, RawString, Table, TomlError, Value,
+    Array, ArrayOfTables, DocumentMut, Formatted, Item, RawString, Table, TableLike, TomlError,
+    Value,
 };
+use tracing::debug;
 
 use uv_cache_key::CanonicalUrl;
 use uv_distribution_types::Index;
-use uv_fs::PortablePath;
+use uv_fs::{LockedFileError, PortablePath, PortablePathBuf, Simplified};
 use uv_normalize::{ExtraName, GroupName, PackageName};
 use uv_pep440::{Version, VersionParseError, VersionSpecifier, VersionSpecifiers};
-use uv_pep508::{MarkerTree, Requirement, VersionOrUrl};
+use uv_pep508::{
+    MarkerEnvironment, MarkerTree, Pep508Error, PythonMarkerError, Requirement, VersionOrUrl,
+    python_marker, requirements_equal,
+};
 use uv_redacted::DisplaySafeUrl;
 
-use crate::pyproject::{DependencyType, Source};
+pub use fs::{BackupStyle, LockedPyProjectTomlMut, SaveOptions, SaveOutcome};
+pub use history::PyProjectEdit;
+pub use merge::{MergeConflict, MergeReport};
+pub use observer::{CollectingObserver, NoopObserver, PyProjectObserver};
+
+use crate::bounds::{BoundsDiagnostic, is_trivial_lower_bound};
+use crate::portable::{self, PortablePathError};
+use crate::pyproject::{DependencyType, PyProjectToml, PyprojectTomlError, Source};
+use crate::pyproject_mut::fs::{Origin, read_only_message};
+
+mod fs;
+mod history;
+mod merge;
+mod observer;
 
 /// Raw and mutable representation of a `pyproject.toml`.
 ///
@@ -27,6 +48,64 @@ use crate::pyproject::{DependencyType, Source};
 pub struct PyProjectTomlMut {
     doc: DocumentMut,
     target: DependencyTarget,
+    /// Whether the document may have been modified since it was loaded or last saved.
+    dirty: bool,
+    /// Whether a mutating method changed the document since it was loaded or last saved.
+    modified: bool,
+    /// The file the document was read from, if any.
+    origin: Option<Origin>,
+    /// The document as it was loaded or last saved.
+    original: DocumentMut,
+    /// The contents of the document as it was loaded or last saved.
+    contents: String,
+    /// The edits applied since the document was loaded or last saved, for replaying.
+    recorded: Vec<RecordedEdit>,
+    /// The edits that changed the document since it was loaded or last saved.
+    log: Vec<PyProjectEdit>,
+    /// Whether to remember the state of the document before each edit, to revert it with
+    /// [`PyProjectTomlMut::revert_last`].
+    history: bool,
+    /// For each entry in the log, the state of the document before the edit.
+    undo: Vec<Checkpoint>,
+    /// The state of the document before the current edit, if any.
+    checkpoint: Option<Checkpoint>,
+    /// Whether requirements are being added in a batch, in which case the document is
+    /// checkpointed, and the edited array reformatted, once for the whole batch.
+    batch: bool,
+    /// The observer to report each edit that changed the document to.
+    observer: Box<dyn PyProjectObserver>,
+}
+
+/// An edit applied to a [`PyProjectTomlMut`], recorded such that it can be replayed onto another
+/// version of the document.
+#[derive(Debug, Clone)]
+enum RecordedEdit {
+    AddWorkspace(PathBuf),
+    AddDependency {
+        dependency_type: DependencyType,
+        requirement: Box<Requirement>,
+        source: Option<Box<Source>>,
+        raw: bool,
+    },
+    RemoveDependency {
+        dependency_type: DependencyType,
+        name: PackageName,
+    },
+    AddIndex(Box<Index>),
+    EnsureOptionalDependency(ExtraName),
+    EnsureDependencyGroup(GroupName),
+    SetVersion(Version),
+    SetRequiresPython(VersionSpecifiers),
+}
+
+/// The state of a [`PyProjectTomlMut`] before an edit, to which the edit can be reverted.
+#[derive(Debug, Clone)]
+struct Checkpoint {
+    doc: DocumentMut,
+    /// The number of [`RecordedEdit`]s before the edit.
+    recorded: usize,
+    /// The number of [`PyProjectEdit`]s before the edit.
+    log: usize,
 }
 
 #[derive(Error, Debug)]
@@ -54,6 +133,76 @@ pub enum Error {
     },
     #[error("Unknown bound king {0}")]
     UnknownBoundKind(String),
+    #[error(transparent)]
+    PortablePath(#[from] PortablePathError),
+    #[error("Failed to read: `{}`", _0.user_display())]
+    Read(PathBuf, #[source] std::io::Error),
+    #[error("Failed to write: `{}`", _0.user_display())]
+    Write(PathBuf, #[source] std::io::Error),
+    #[error("Failed to write `pyproject.toml`")]
+    WriteTo(#[source] std::io::Error),
+    #[error(transparent)]
+    Lock(#[from] LockedFileError),
+    #[error("Another process is editing `{}`", _0.user_display())]
+    Locked(PathBuf),
+    #[error("`{}` was modified since it was read", path.user_display())]
+    ConcurrentModification { path: PathBuf },
+    #[error("The `pyproject.toml` wasn't read from a file")]
+    NotLoaded,
+    #[error("{}", read_only_message(path, *is_dir))]
+    ReadOnly { path: PathBuf, is_dir: bool },
+    #[error("Permission denied when reading `{}`; check that the file is readable by the current user", _0.user_display())]
+    Unreadable(PathBuf),
+    #[error(transparent)]
+    RequirementParse(Box<RequirementParseError>),
+    #[error(transparent)]
+    PythonMarker(#[from] PythonMarkerError),
+}
+
+impl Error {
+    /// Returns the path of the file or directory involved in the error, if any.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::Read(path, _)
+            | Self::Write(path, _)
+            | Self::Locked(path)
+            | Self::ConcurrentModification { path }
+            | Self::ReadOnly { path, .. }
+            | Self::Unreadable(path) => Some(path),
+            Self::Parse(_)
+            | Self::Serialize(_)
+            | Self::Deserialize(_)
+            | Self::MalformedDependencies
+            | Self::MalformedSources
+            | Self::MalformedWorkspace
+            | Self::MissingDependency(_)
+            | Self::VersionParse(_)
+            | Self::Ambiguous { .. }
+            | Self::UnknownBoundKind(_)
+            | Self::PortablePath(_)
+            | Self::RequirementParse(_)
+            | Self::PythonMarker(_) => None,
+            Self::WriteTo(_) | Self::Lock(_) | Self::NotLoaded => None,
+        }
+    }
+}
+
+/// An entry in a dependency array that isn't a valid requirement.
+#[derive(Error, Debug)]
+#[error("Failed to parse entry {index} of `{section}`: `{requirement}`")]
+pub struct RequirementParseError {
+    /// The section containing the entry, e.g., `project.dependencies`.
+    pub section: String,
+    /// The index of the entry in the section.
+    pub index: usize,
+    /// The entry, as written.
+    pub requirement: String,
+    /// The character offset in the entry at which parsing failed.
+    pub offset: usize,
+    /// The byte range in the file at which parsing failed, if known.
+    pub span: Option<Range<usize>>,
+    #[source]
+    pub err: Pep508Error,
 }
 
 /// The result of editing an array in a TOML document.
@@ -63,6 +212,8 @@ pub enum ArrayEdit {
     Update(usize),
     /// A new entry was added at the given index (typically, the end of the array).
     Add(usize),
+    /// An existing entry (at the given index) already matched the requirement, and was left as-is.
+    Unchanged(usize),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -82,9 +233,14 @@ struct Comment {
 impl ArrayEdit {
     pub fn index(&self) -> usize {
         match self {
-            Self::Update(i) | Self::Add(i) => *i,
+            Self::Update(i) | Self::Add(i) | Self::Unchanged(i) => *i,
         }
     }
+
+    /// Returns `true` if the edit modified the array.
+    pub fn is_changed(&self) -> bool {
+        !matches!(self, Self::Unchanged(_))
+    }
 }
 
 /// The default version specifier when adding a dependency.
@@ -262,14 +418,132 @@ pub enum DependencyTarget {
 impl PyProjectTomlMut {
     /// Initialize a [`PyProjectTomlMut`] from a [`str`].
     pub fn from_toml(raw: &str, target: DependencyTarget) -> Result<Self, Error> {
+        let doc: DocumentMut = raw.parse().map_err(Box::new)?;
         Ok(Self {
-            doc: raw.parse().map_err(Box::new)?,
+            original: doc.clone(),
+            contents: raw.to_string(),
+            doc,
             target,
+            dirty: false,
+            modified: false,
+            origin: None,
+            recorded: Vec::new(),
+            log: Vec::new(),
+            history: false,
+            undo: Vec::new(),
+            checkpoint: None,
+            batch: false,
+            observer: Box::new(NoopObserver),
         })
     }
 
+    /// Deserialize the document into a [`PyProjectToml`], reusing the parsed document rather than
+    /// parsing its contents again.
+    pub fn pyproject_toml(&self) -> Result<PyProjectToml, PyprojectTomlError> {
+        PyProjectToml::from_document(&self.doc)
+    }
+
+    /// Record an edit to the document, before it's applied.
+    fn record(&mut self, edit: RecordedEdit) {
+        if self.batch {
+            self.dirty = true;
+        } else {
+            self.checkpoint();
+        }
+        self.recorded.push(edit);
+    }
+
+    /// Mark the document as dirty, and, if history is enabled, remember its state before the
+    /// current edit.
+    fn checkpoint(&mut self) {
+        self.dirty = true;
+        if self.history {
+            self.checkpoint = Some(Checkpoint {
+                doc: self.doc.clone(),
+                recorded: self.recorded.len(),
+                log: self.log.len(),
+            });
+        }
+    }
+
+    /// Add an edit that changed the document to the log, and report it to the observer.
+    fn log(&mut self, edit: PyProjectEdit) {
+        self.modified = true;
+        if let Some(checkpoint) = self.checkpoint.take() {
+            self.undo.push(checkpoint);
+        }
+        self.observer.on_edit(&edit);
+        self.log.push(edit);
+    }
+
+    /// Forget the edits applied since the document was loaded or last saved.
+    fn clear_edits(&mut self) {
+        self.recorded.clear();
+        self.log.clear();
+        self.undo.clear();
+        self.checkpoint = None;
+    }
+
+    /// Returns `true` if the document may have been modified since it was loaded or last saved.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Returns `true` if a mutating method changed the document since it was loaded or last
+    /// saved.
+    ///
+    /// Unlike [`PyProjectTomlMut::is_dirty`], edits that leave the document as-is (e.g., adding a
+    /// requirement that's already present) don't mark the document as modified.
+    pub fn is_modified(&self) -> bool {
+        self.modified
+    }
+
+    /// Serialize the document to bytes, as written by [`PyProjectTomlMut::save`].
+    ///
+    /// To serialize to a [`String`], use [`ToString::to_string`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_string().into_bytes()
+    }
+
+    /// Render a unified diff between the document as it was loaded or last saved, and the
+    /// document as it would be written by [`PyProjectTomlMut::save`], with three lines of context.
+    ///
+    /// Returns `None` if the document wasn't modified, or if saving it wouldn't change the file.
+    pub fn diff(&self) -> Option<String> {
+        self.diff_with_context(3)
+    }
+
+    /// Render a unified diff of the pending changes, as in [`PyProjectTomlMut::diff`], with the
+    /// given number of lines of context.
+    pub fn diff_with_context(&self, context: usize) -> Option<String> {
+        // An unmodified document isn't written, even if serializing it would change the contents
+        // (e.g., by adding a trailing newline).
+        if !self.dirty {
+            return None;
+        }
+        let new = self.to_string();
+        if self.contents == new {
+            return None;
+        }
+
+        let path = self.path().map_or_else(
+            || "pyproject.toml".to_string(),
+            |path| path.user_display().to_string(),
+        );
+        let diff = similar::TextDiff::from_lines(&self.contents, &new)
+            .unified_diff()
+            .context_radius(context)
+            .header(&path, &path)
+            .to_string();
+        Some(diff)
+    }
+
     /// Adds a project to the workspace.
-    pub fn add_workspace(&mut self, path: impl AsRef<Path>) -> Result<(), Error> {
+    ///
+    /// Returns `true` if the document was modified, i.e., if the project wasn't already a member.
+    pub fn add_workspace(&mut self, path: impl AsRef<Path>) -> Result<bool, Error> {
+        self.record(RecordedEdit::AddWorkspace(path.as_ref().to_path_buf()));
+
         // Get or create `tool.uv.workspace.members`.
         let members = self
             .doc
@@ -290,12 +564,22 @@ impl PyProjectTomlMut {
             .as_array_mut()
             .ok_or(Error::MalformedWorkspace)?;
 
-        // Add the path to the workspace.
-        members.push(PortablePath::from(path.as_ref()).to_string());
+        // Add the path to the workspace, using forward slashes such that the member is portable.
+        let path = PortablePath::from(path.as_ref()).to_string();
+        let member = portable::normalize_for_write(&path)?.into_owned();
+        if members
+            .iter()
+            .any(|existing| existing.as_str() == Some(&member))
+        {
+            return Ok(false);
+        }
+        debug!(member = %member, "Added workspace member");
+        members.push(member.as_str());
 
         reformat_array_multiline(members);
 
-        Ok(())
+        self.log(PyProjectEdit::AddWorkspaceMember { member });
+        Ok(true)
     }
 
     /// Retrieves a mutable reference to the `project` [`Table`] of the TOML document, creating the
@@ -333,13 +617,23 @@ impl PyProjectTomlMut {
 
     /// Adds a dependency to `project.dependencies`.
     ///
-    /// Returns `true` if the dependency was added, `false` if it was updated.
+    /// Returns the [`ArrayEdit`] applied to the section; [`ArrayEdit::Unchanged`] if an equivalent
+    /// requirement (and source) is already present.
     pub fn add_dependency(
         &mut self,
         req: &Requirement,
         source: Option<&Source>,
         raw: bool,
     ) -> Result<ArrayEdit, Error> {
+        self.record(RecordedEdit::AddDependency {
+            dependency_type: DependencyType::Production,
+            requirement: Box::new(req.clone()),
+            source: source.cloned().map(Box::new),
+            raw,
+        });
+
+        let reformat = !self.batch;
+
         // Get or create `project.dependencies`.
         let dependencies = self
             .project()?
@@ -348,24 +642,58 @@ impl PyProjectTomlMut {
             .as_array_mut()
             .ok_or(Error::MalformedDependencies)?;
 
-        let edit = add_dependency(req, dependencies, source.is_some(), raw)?;
-
-        if let Some(source) = source {
-            self.add_source(&req.name, source)?;
-        }
+        let (edit, replaced) =
+            insert_dependency(req, dependencies, source.is_some(), raw, reformat)?;
+        let edit = self.add_dependency_source(
+            &DependencyType::Production,
+            req,
+            source,
+            edit,
+            replaced.as_deref(),
+        )?;
 
         Ok(edit)
     }
 
+    /// Adds a dependency to `project.dependencies` that only applies to the given Python versions,
+    /// e.g., `tomli` for `<3.11`.
+    ///
+    /// The specifiers are converted into the equivalent marker (see [`python_marker`]), which is
+    /// conjoined with any marker already on the requirement, e.g., `foo ; sys_platform == 'linux'`
+    /// for `>=3.12` is added as `foo ; python_full_version >= '3.12' and sys_platform == 'linux'`.
+    ///
+    /// Returns the [`ArrayEdit`] applied to the section, as in [`PyProjectTomlMut::add_dependency`].
+    pub fn add_dependency_for_python(
+        &mut self,
+        req: &Requirement,
+        python: &VersionSpecifiers,
+        source: Option<&Source>,
+        raw: bool,
+    ) -> Result<ArrayEdit, Error> {
+        let mut req = req.clone();
+        req.marker.and(python_marker(python)?);
+        self.add_dependency(&req, source, raw)
+    }
+
     /// Adds a development dependency to `tool.uv.dev-dependencies`.
     ///
-    /// Returns `true` if the dependency was added, `false` if it was updated.
+    /// Returns the [`ArrayEdit`] applied to the section; [`ArrayEdit::Unchanged`] if an equivalent
+    /// requirement (and source) is already present.
     pub fn add_dev_dependency(
         &mut self,
         req: &Requirement,
         source: Option<&Source>,
         raw: bool,
     ) -> Result<ArrayEdit, Error> {
+        self.record(RecordedEdit::AddDependency {
+            dependency_type: DependencyType::Dev,
+            requirement: Box::new(req.clone()),
+            source: source.cloned().map(Box::new),
+            raw,
+        });
+
+        let reformat = !self.batch;
+
         // Get or create `tool.uv.dev-dependencies`.
         let dev_dependencies = self
             .doc
@@ -382,17 +710,87 @@ impl PyProjectTomlMut {
             .as_array_mut()
             .ok_or(Error::MalformedDependencies)?;
 
-        let edit = add_dependency(req, dev_dependencies, source.is_some(), raw)?;
+        let (edit, replaced) =
+            insert_dependency(req, dev_dependencies, source.is_some(), raw, reformat)?;
+        let edit = self.add_dependency_source(
+            &DependencyType::Dev,
+            req,
+            source,
+            edit,
+            replaced.as_deref(),
+        )?;
+
+        Ok(edit)
+    }
 
-        if let Some(source) = source {
-            self.add_source(&req.name, source)?;
+    /// Adds a dependency to the given section.
+    pub fn add_requirement(
+        &mut self,
+        dependency_type: &DependencyType,
+        req: &Requirement,
+        source: Option<&Source>,
+        raw: bool,
+    ) -> Result<ArrayEdit, Error> {
+        match dependency_type {
+            DependencyType::Production => self.add_dependency(req, source, raw),
+            DependencyType::Dev => self.add_dev_dependency(req, source, raw),
+            DependencyType::Optional(extra) => {
+                self.add_optional_dependency(extra, req, source, raw)
+            }
+            DependencyType::Group(group) => {
+                self.add_dependency_group_requirement(group, req, source, raw)
+            }
         }
+    }
 
-        Ok(edit)
+    /// Adds dependencies to the given section, as a single edit.
+    ///
+    /// Equivalent to adding each requirement in turn with [`PyProjectTomlMut::add_requirement`],
+    /// except that the section is only reformatted once, after all requirements are added, and
+    /// that [`PyProjectTomlMut::revert_last`] reverts all of them at once.
+    ///
+    /// Returns the [`ArrayEdit`] applied for each requirement, in order. If adding a requirement
+    /// fails, the requirements before it remain added.
+    pub fn add_dependencies<'a>(
+        &mut self,
+        dependency_type: &DependencyType,
+        requirements: impl IntoIterator<Item = (&'a Requirement, Option<&'a Source>)>,
+        raw: bool,
+    ) -> Result<Vec<ArrayEdit>, Error> {
+        self.checkpoint();
+        self.batch = true;
+
+        let mut edits = Vec::new();
+        let mut result = Ok(());
+        for (req, source) in requirements {
+            match self.add_requirement(dependency_type, req, source, raw) {
+                Ok(edit) => edits.push(edit),
+                Err(err) => {
+                    result = Err(err);
+                    break;
+                }
+            }
+        }
+        self.batch = false;
+
+        if edits.iter().any(ArrayEdit::is_changed) {
+            reformat_array_multiline(self.dependency_array_mut(dependency_type)?);
+        }
+
+        result.map(|()| edits)
     }
 
     /// Add an [`Index`] to `tool.uv.index`.
-    pub fn add_index(&mut self, index: &Index) -> Result<(), Error> {
+    ///
+    /// Returns `true` if the document was modified, i.e., if the index wasn't already present as
+    /// the first index.
+    pub fn add_index(&mut self, index: &Index) -> Result<bool, Error> {
+        self.record(RecordedEdit::AddIndex(Box::new(index.clone())));
+
+        // Reusing an existing index reorders and reformats the tables in place, so compare the
+        // serialized document instead.
+        let before = self.doc.to_string();
+
         let size = self.doc.len();
         let existing = self
             .doc
@@ -561,12 +959,21 @@ impl PyProjectTomlMut {
         // Push the item to the table.
         existing.push(table);
 
-        Ok(())
+        if self.doc.to_string() == before {
+            return Ok(false);
+        }
+        debug!(url = %index.url, "Added index to `tool.uv.index`");
+        self.log(PyProjectEdit::AddIndex {
+            name: index.name.as_ref().map(ToString::to_string),
+            url: index.url.without_credentials().to_string(),
+        });
+        Ok(true)
     }
 
     /// Adds a dependency to `project.optional-dependencies`.
     ///
-    /// Returns `true` if the dependency was added, `false` if it was updated.
+    /// Returns the [`ArrayEdit`] applied to the section; [`ArrayEdit::Unchanged`] if an equivalent
+    /// requirement (and source) is already present.
     pub fn add_optional_dependency(
         &mut self,
         group: &ExtraName,
@@ -574,6 +981,16 @@ impl PyProjectTomlMut {
         source: Option<&Source>,
         raw: bool,
     ) -> Result<ArrayEdit, Error> {
+        let dependency_type = DependencyType::Optional(group.clone());
+        self.record(RecordedEdit::AddDependency {
+            dependency_type: dependency_type.clone(),
+            requirement: Box::new(req.clone()),
+            source: source.cloned().map(Box::new),
+            raw,
+        });
+
+        let reformat = !self.batch;
+
         // Get or create `project.optional-dependencies`.
         let optional_dependencies = self
             .project()?
@@ -601,29 +1018,33 @@ impl PyProjectTomlMut {
         .as_array_mut()
         .ok_or(Error::MalformedDependencies)?;
 
-        let added = add_dependency(req, group, source.is_some(), raw)?;
+        let (added, replaced) = insert_dependency(req, group, source.is_some(), raw, reformat)?;
 
         // If `project.optional-dependencies` is an inline table, reformat it.
         //
         // Reformatting can drop comments between keys, but you can't put comments
         // between items in an inline table anyway.
-        if let Some(optional_dependencies) = self
-            .project()?
-            .get_mut("optional-dependencies")
-            .and_then(Item::as_inline_table_mut)
+        if added.is_changed()
+            && let Some(optional_dependencies) = self
+                .project()?
+                .get_mut("optional-dependencies")
+                .and_then(Item::as_inline_table_mut)
         {
             optional_dependencies.fmt();
         }
 
-        if let Some(source) = source {
-            self.add_source(&req.name, source)?;
-        }
+        let added =
+            self.add_dependency_source(&dependency_type, req, source, added, replaced.as_deref())?;
 
         Ok(added)
     }
 
     /// Ensure that an optional dependency group exists, creating an empty group if it doesn't.
-    pub fn ensure_optional_dependency(&mut self, extra: &ExtraName) -> Result<(), Error> {
+    ///
+    /// Returns `true` if the document was modified, i.e., if the group was created.
+    pub fn ensure_optional_dependency(&mut self, extra: &ExtraName) -> Result<bool, Error> {
+        self.record(RecordedEdit::EnsureOptionalDependency(extra.clone()));
+
         // Get or create `project.optional-dependencies`.
         let optional_dependencies = self
             .project()?
@@ -637,11 +1058,13 @@ impl PyProjectTomlMut {
             .iter()
             .any(|(key, _value)| ExtraName::from_str(key).is_ok_and(|e| e == *extra));
 
-        // If the extra doesn't exist, create it.
-        if !extra_exists {
-            optional_dependencies.insert(extra.as_ref(), Item::Value(Value::Array(Array::new())));
+        if extra_exists {
+            return Ok(false);
         }
 
+        // If the extra doesn't exist, create it.
+        optional_dependencies.insert(extra.as_ref(), Item::Value(Value::Array(Array::new())));
+
         // If `project.optional-dependencies` is an inline table, reformat it.
         //
         // Reformatting can drop comments between keys, but you can't put comments
@@ -654,12 +1077,16 @@ impl PyProjectTomlMut {
             optional_dependencies.fmt();
         }
 
-        Ok(())
+        self.log(PyProjectEdit::CreateSection {
+            location: section(&DependencyType::Optional(extra.clone())),
+        });
+        Ok(true)
     }
 
     /// Adds a dependency to `dependency-groups`.
     ///
-    /// Returns `true` if the dependency was added, `false` if it was updated.
+    /// Returns the [`ArrayEdit`] applied to the section; [`ArrayEdit::Unchanged`] if an equivalent
+    /// requirement (and source) is already present.
     pub fn add_dependency_group_requirement(
         &mut self,
         group: &GroupName,
@@ -667,6 +1094,16 @@ impl PyProjectTomlMut {
         source: Option<&Source>,
         raw: bool,
     ) -> Result<ArrayEdit, Error> {
+        let dependency_type = DependencyType::Group(group.clone());
+        self.record(RecordedEdit::AddDependency {
+            dependency_type: dependency_type.clone(),
+            requirement: Box::new(req.clone()),
+            source: source.cloned().map(Box::new),
+            raw,
+        });
+
+        let reformat = !self.batch;
+
         // Get or create `dependency-groups`.
         let dependency_groups = self
             .doc
@@ -701,35 +1138,40 @@ impl PyProjectTomlMut {
         .as_array_mut()
         .ok_or(Error::MalformedDependencies)?;
 
-        let added = add_dependency(req, group, source.is_some(), raw)?;
+        let (added, replaced) = insert_dependency(req, group, source.is_some(), raw, reformat)?;
 
-        // To avoid churn in pyproject.toml, we only sort new group keys if the
-        // existing keys were sorted.
-        if was_sorted {
-            dependency_groups.sort_values();
-        }
+        if added.is_changed() {
+            // To avoid churn in pyproject.toml, we only sort new group keys if the
+            // existing keys were sorted.
+            if was_sorted {
+                dependency_groups.sort_values();
+            }
 
-        // If `dependency-groups` is an inline table, reformat it.
-        //
-        // Reformatting can drop comments between keys, but you can't put comments
-        // between items in an inline table anyway.
-        if let Some(dependency_groups) = self
-            .doc
-            .get_mut("dependency-groups")
-            .and_then(Item::as_inline_table_mut)
-        {
-            dependency_groups.fmt();
+            // If `dependency-groups` is an inline table, reformat it.
+            //
+            // Reformatting can drop comments between keys, but you can't put comments
+            // between items in an inline table anyway.
+            if let Some(dependency_groups) = self
+                .doc
+                .get_mut("dependency-groups")
+                .and_then(Item::as_inline_table_mut)
+            {
+                dependency_groups.fmt();
+            }
         }
 
-        if let Some(source) = source {
-            self.add_source(&req.name, source)?;
-        }
+        let added =
+            self.add_dependency_source(&dependency_type, req, source, added, replaced.as_deref())?;
 
         Ok(added)
     }
 
     /// Ensure that a dependency group exists, creating an empty group if it doesn't.
-    pub fn ensure_dependency_group(&mut self, group: &GroupName) -> Result<(), Error> {
+    ///
+    /// Returns `true` if the document was modified, i.e., if the group was created.
+    pub fn ensure_dependency_group(&mut self, group: &GroupName) -> Result<bool, Error> {
+        self.record(RecordedEdit::EnsureDependencyGroup(group.clone()));
+
         // Get or create `dependency-groups`.
         let dependency_groups = self
             .doc
@@ -750,15 +1192,17 @@ impl PyProjectTomlMut {
             .iter()
             .any(|(key, _value)| GroupName::from_str(key).is_ok_and(|g| g == *group));
 
+        if group_exists {
+            return Ok(false);
+        }
+
         // If the group doesn't exist, create it.
-        if !group_exists {
-            dependency_groups.insert(group.as_ref(), Item::Value(Value::Array(Array::new())));
+        dependency_groups.insert(group.as_ref(), Item::Value(Value::Array(Array::new())));
 
-            // To avoid churn in pyproject.toml, we only sort new group keys if the
-            // existing keys were sorted.
-            if was_sorted {
-                dependency_groups.sort_values();
-            }
+        // To avoid churn in pyproject.toml, we only sort new group keys if the
+        // existing keys were sorted.
+        if was_sorted {
+            dependency_groups.sort_values();
         }
 
         // If `dependency-groups` is an inline table, reformat it.
@@ -773,38 +1217,175 @@ impl PyProjectTomlMut {
             dependency_groups.fmt();
         }
 
-        Ok(())
+        self.log(PyProjectEdit::CreateSection {
+            location: section(&DependencyType::Group(group.clone())),
+        });
+        Ok(true)
     }
 
     /// Set the constraint for a requirement for an existing dependency.
+    ///
+    /// Returns `true` if the document was modified, i.e., if the requirement didn't already have
+    /// the constraint.
     pub fn set_dependency_bound(
         &mut self,
         dependency_type: &DependencyType,
         index: usize,
         version: Version,
         bound_kind: AddBoundsKind,
-    ) -> Result<(), Error> {
-        let group = match dependency_type {
-            DependencyType::Production => self.dependencies_array()?,
-            DependencyType::Dev => self.dev_dependencies_array()?,
-            DependencyType::Optional(extra) => self.optional_dependencies_array(extra)?,
-            DependencyType::Group(group) => self.dependency_groups_array(group)?,
-        };
+    ) -> Result<bool, Error> {
+        self.set_dependency_specifiers(dependency_type, index, bound_kind.specifiers(version))
+    }
+
+    /// Add a lower bound to the dependencies reported by [`Workspace::check_bounds`], using the
+    /// given versions (e.g., the versions in the lockfile).
+    ///
+    /// The diagnostics must have been produced for this `pyproject.toml`. Each dependency gets a
+    /// `>=` bound on its version, in addition to any upper bounds or exclusions; dependencies
+    /// without a version, and dependencies whose entries have changed since the diagnostics were
+    /// produced, are left as-is.
+    ///
+    /// Returns the number of dependencies that were modified.
+    ///
+    /// [`Workspace::check_bounds`]: crate::Workspace::check_bounds
+    pub fn fix_bounds(
+        &mut self,
+        diagnostics: &[BoundsDiagnostic],
+        versions: &BTreeMap<PackageName, Version>,
+    ) -> Result<usize, Error> {
+        let mut fixed = 0;
+        for diagnostic in diagnostics {
+            let Some(version) = versions.get(&diagnostic.name) else {
+                continue;
+            };
+            let Some(requirement) = dependency_array(
+                self.doc.as_table(),
+                self.target,
+                &diagnostic.dependency_type,
+            )
+            .and_then(|array| array.get(diagnostic.index))
+            .and_then(Value::as_str)
+            .and_then(try_parse_requirement)
+            .filter(|requirement| requirement.name == diagnostic.name) else {
+                continue;
+            };
+            let existing: &[VersionSpecifier] = match &requirement.version_or_url {
+                Some(VersionOrUrl::Url(_)) => continue,
+                Some(VersionOrUrl::VersionSpecifier(specifiers)) => specifiers,
+                None => &[],
+            };
+            let specifiers = iter::once(VersionSpecifier::greater_than_equal_version(
+                version.clone(),
+            ))
+            .chain(
+                existing
+                    .iter()
+                    .filter(|specifier| !is_trivial_lower_bound(specifier))
+                    .cloned(),
+            )
+            .collect();
+            if self.set_dependency_specifiers(
+                &diagnostic.dependency_type,
+                diagnostic.index,
+                specifiers,
+            )? {
+                fixed += 1;
+            }
+        }
+        Ok(fixed)
+    }
+
+    /// Replace the version specifiers of an existing dependency.
+    ///
+    /// Returns `true` if the document was modified, i.e., if the requirement didn't already have
+    /// the specifiers.
+    pub fn set_dependency_specifiers(
+        &mut self,
+        dependency_type: &DependencyType,
+        index: usize,
+        specifiers: VersionSpecifiers,
+    ) -> Result<bool, Error> {
+        self.update_dependency(dependency_type, index, |req| {
+            req.version_or_url = Some(VersionOrUrl::VersionSpecifier(specifiers));
+        })
+    }
+
+    /// Replace the marker of an existing dependency, e.g., with `sys_platform == 'linux'`.
+    ///
+    /// Returns `true` if the document was modified, i.e., if the requirement didn't already have
+    /// the marker.
+    pub fn set_dependency_marker(
+        &mut self,
+        dependency_type: &DependencyType,
+        index: usize,
+        marker: MarkerTree,
+    ) -> Result<bool, Error> {
+        self.update_dependency(dependency_type, index, |req| req.marker = marker)
+    }
+
+    /// Rewrite the requirement of an existing dependency.
+    fn update_dependency(
+        &mut self,
+        dependency_type: &DependencyType,
+        index: usize,
+        update: impl FnOnce(&mut Requirement),
+    ) -> Result<bool, Error> {
+        self.checkpoint();
+
+        let group = self.dependency_array_mut(dependency_type)?;
 
         let Some(req) = group.get(index) else {
             return Err(Error::MissingDependency(index));
         };
 
-        let mut req = req
+        let raw = req
             .as_str()
-            .and_then(try_parse_requirement)
-            .ok_or(Error::MalformedDependencies)?;
-        req.version_or_url = Some(VersionOrUrl::VersionSpecifier(
-            bound_kind.specifiers(version),
-        ));
-        group.replace(index, req.to_string());
+            .ok_or(Error::MalformedDependencies)?
+            .to_string();
+        let mut req = match Requirement::from_str(&raw) {
+            Ok(req) => req,
+            Err(err) => return Err(self.requirement_error(dependency_type, index, raw, err)),
+        };
+        update(&mut req);
+        let edit = if try_parse_requirement(&raw)
+            .is_some_and(|existing| requirements_equal(&existing, &req))
+        {
+            ArrayEdit::Unchanged(index)
+        } else {
+            group.replace(index, req.to_string());
+            ArrayEdit::Update(index)
+        };
+        trace_edit(dependency_type, &req.name, &edit);
+
+        if edit.is_changed() {
+            self.log(PyProjectEdit::AddDependency {
+                location: section(dependency_type),
+                requirement: req.to_string(),
+                replaced: Some(history::redact(&raw)),
+                source: None,
+            });
+        }
+        self.recorded.push(RecordedEdit::AddDependency {
+            dependency_type: dependency_type.clone(),
+            requirement: Box::new(req),
+            source: None,
+            raw: false,
+        });
 
-        Ok(())
+        Ok(edit.is_changed())
+    }
+
+    /// Get the TOML array for the given section.
+    fn dependency_array_mut(
+        &mut self,
+        dependency_type: &DependencyType,
+    ) -> Result<&mut Array, Error> {
+        match dependency_type {
+            DependencyType::Production => self.dependencies_array(),
+            DependencyType::Dev => self.dev_dependencies_array(),
+            DependencyType::Optional(extra) => self.optional_dependencies_array(extra),
+            DependencyType::Group(group) => self.dependency_groups_array(group),
+        }
     }
 
     /// Get the TOML array for `project.dependencies`.
@@ -899,8 +1480,63 @@ impl PyProjectTomlMut {
         Ok(group)
     }
 
+    /// Add the source for a requirement added to the given section with the given [`ArrayEdit`],
+    /// if any, and log the edit.
+    ///
+    /// If the requirement was already present, but its source changed, the edit is reported as an
+    /// [`ArrayEdit::Update`].
+    fn add_dependency_source(
+        &mut self,
+        dependency_type: &DependencyType,
+        req: &Requirement,
+        source: Option<&Source>,
+        edit: ArrayEdit,
+        replaced: Option<&str>,
+    ) -> Result<ArrayEdit, Error> {
+        // The array was modified, even if adding the source fails.
+        self.modified |= edit.is_changed();
+
+        let source = match source {
+            Some(source) => self.add_source(&req.name, source)?,
+            None => None,
+        };
+        let edit = match edit {
+            ArrayEdit::Unchanged(index) if source.is_some() => ArrayEdit::Update(index),
+            edit => edit,
+        };
+        trace_edit(dependency_type, &req.name, &edit);
+
+        if edit.is_changed() {
+            let entry = |doc: &DocumentMut| {
+                dependency_array(doc.as_table(), self.target, dependency_type)
+                    .and_then(|array| array.get(edit.index()))
+                    .and_then(Value::as_str)
+                    .map(history::redact)
+            };
+            let requirement = entry(&self.doc).unwrap_or_else(|| req.to_string());
+            // If only the source changed, the entry was replaced with itself.
+            let replaced = match edit {
+                ArrayEdit::Update(_) => replaced
+                    .map(history::redact)
+                    .or_else(|| Some(requirement.clone())),
+                _ => None,
+            };
+            self.log(PyProjectEdit::AddDependency {
+                location: section(dependency_type),
+                requirement,
+                replaced,
+                source,
+            });
+        }
+
+        Ok(edit)
+    }
+
     /// Adds a source to `tool.uv.sources`.
-    fn add_source(&mut self, name: &PackageName, source: &Source) -> Result<(), Error> {
+    ///
+    /// Returns the source as written if the document was modified, i.e., if the package didn't
+    /// already have an equivalent source.
+    fn add_source(&mut self, name: &PackageName, source: &Source) -> Result<Option<String>, Error> {
         // Get or create `tool.uv.sources`.
         let sources = self
             .doc
@@ -917,16 +1553,30 @@ impl PyProjectTomlMut {
             .as_table_mut()
             .ok_or(Error::MalformedSources)?;
 
+        let value = source_value(source)?;
         if let Some(key) = find_source(name, sources) {
+            if sources
+                .get(&key)
+                .and_then(Item::as_value)
+                .is_some_and(|existing| same_value(existing, &value))
+            {
+                return Ok(None);
+            }
             sources.remove(&key);
         }
-        add_source(name, source, sources)?;
+        let written = value.to_string().trim().to_string();
+        sources.insert(name.as_ref(), Item::Value(value));
 
-        Ok(())
+        Ok(Some(written))
     }
 
     /// Removes all occurrences of dependencies with the given name.
     pub fn remove_dependency(&mut self, name: &PackageName) -> Result<Vec<Requirement>, Error> {
+        self.record(RecordedEdit::RemoveDependency {
+            dependency_type: DependencyType::Production,
+            name: name.clone(),
+        });
+
         // Try to get `project.dependencies`.
         let Some(dependencies) = self
             .project_mut()?
@@ -942,13 +1592,21 @@ impl PyProjectTomlMut {
         };
 
         let requirements = remove_dependency(name, dependencies);
-        self.remove_source(name)?;
+        self.modified |= !requirements.is_empty();
+        trace_removal(&DependencyType::Production, name, &requirements);
+        let source = self.remove_source(name)?;
+        self.log_removal(&DependencyType::Production, &requirements, source);
 
         Ok(requirements)
     }
 
     /// Removes all occurrences of development dependencies with the given name.
     pub fn remove_dev_dependency(&mut self, name: &PackageName) -> Result<Vec<Requirement>, Error> {
+        self.record(RecordedEdit::RemoveDependency {
+            dependency_type: DependencyType::Dev,
+            name: name.clone(),
+        });
+
         // Try to get `tool.uv.dev-dependencies`.
         let Some(dev_dependencies) = self
             .doc
@@ -970,7 +1628,10 @@ impl PyProjectTomlMut {
         };
 
         let requirements = remove_dependency(name, dev_dependencies);
-        self.remove_source(name)?;
+        self.modified |= !requirements.is_empty();
+        trace_removal(&DependencyType::Dev, name, &requirements);
+        let source = self.remove_source(name)?;
+        self.log_removal(&DependencyType::Dev, &requirements, source);
 
         Ok(requirements)
     }
@@ -981,6 +1642,12 @@ impl PyProjectTomlMut {
         name: &PackageName,
         group: &ExtraName,
     ) -> Result<Vec<Requirement>, Error> {
+        let dependency_type = DependencyType::Optional(group.clone());
+        self.record(RecordedEdit::RemoveDependency {
+            dependency_type: dependency_type.clone(),
+            name: name.clone(),
+        });
+
         // Try to get `project.optional-dependencies.<group>`.
         let Some(optional_dependencies) = self
             .project_mut()?
@@ -1011,7 +1678,10 @@ impl PyProjectTomlMut {
         };
 
         let requirements = remove_dependency(name, optional_dependencies);
-        self.remove_source(name)?;
+        self.modified |= !requirements.is_empty();
+        trace_removal(&dependency_type, name, &requirements);
+        let source = self.remove_source(name)?;
+        self.log_removal(&dependency_type, &requirements, source);
 
         Ok(requirements)
     }
@@ -1022,6 +1692,12 @@ impl PyProjectTomlMut {
         name: &PackageName,
         group: &GroupName,
     ) -> Result<Vec<Requirement>, Error> {
+        let dependency_type = DependencyType::Group(group.clone());
+        self.record(RecordedEdit::RemoveDependency {
+            dependency_type: dependency_type.clone(),
+            name: name.clone(),
+        });
+
         // Try to get `project.optional-dependencies.<group>`.
         let Some(group_dependencies) = self
             .doc
@@ -1052,16 +1728,53 @@ impl PyProjectTomlMut {
         };
 
         let requirements = remove_dependency(name, group_dependencies);
-        self.remove_source(name)?;
+        self.modified |= !requirements.is_empty();
+        trace_removal(&dependency_type, name, &requirements);
+        let source = self.remove_source(name)?;
+        self.log_removal(&dependency_type, &requirements, source);
 
         Ok(requirements)
     }
 
-    /// Remove a matching source from `tool.uv.sources`, if it exists.
-    fn remove_source(&mut self, name: &PackageName) -> Result<(), Error> {
-        // If the dependency is still in use, don't remove the source.
-        if !self.find_dependency(name, None).is_empty() {
-            return Ok(());
+    /// Removes all occurrences of dependencies with the given name from the given section.
+    pub fn remove_requirement(
+        &mut self,
+        dependency_type: &DependencyType,
+        name: &PackageName,
+    ) -> Result<Vec<Requirement>, Error> {
+        match dependency_type {
+            DependencyType::Production => self.remove_dependency(name),
+            DependencyType::Dev => self.remove_dev_dependency(name),
+            DependencyType::Optional(extra) => self.remove_optional_dependency(name, extra),
+            DependencyType::Group(group) => self.remove_dependency_group_requirement(name, group),
+        }
+    }
+
+    /// Log the removal of the given requirements from the given section, along with the source
+    /// that was removed as a result, if any.
+    fn log_removal(
+        &mut self,
+        dependency_type: &DependencyType,
+        requirements: &[Requirement],
+        source: Option<String>,
+    ) {
+        if requirements.is_empty() && source.is_none() {
+            return;
+        }
+        self.log(PyProjectEdit::RemoveDependency {
+            location: section(dependency_type),
+            removed: requirements.iter().map(ToString::to_string).collect(),
+            source,
+        });
+    }
+
+    /// Remove a matching source from `tool.uv.sources`, if it exists.
+    ///
+    /// Returns the removed source, if any.
+    fn remove_source(&mut self, name: &PackageName) -> Result<Option<String>, Error> {
+        // If the dependency is still in use, don't remove the source.
+        if !self.find_dependency(name, None).is_empty() {
+            return Ok(None);
         }
 
         if let Some(sources) = self
@@ -1077,7 +1790,10 @@ impl PyProjectTomlMut {
             .transpose()?
         {
             if let Some(key) = find_source(name, sources) {
-                sources.remove(&key);
+                let removed = sources
+                    .remove(&key)
+                    .map(|source| source.to_string().trim().to_string());
+                self.modified = true;
 
                 // Remove the `tool.uv.sources` table if it is empty.
                 if sources.is_empty() {
@@ -1092,10 +1808,12 @@ impl PyProjectTomlMut {
                         .ok_or(Error::MalformedSources)?
                         .remove("sources");
                 }
+
+                return Ok(removed);
             }
         }
 
-        Ok(())
+        Ok(None)
     }
 
     /// Returns `true` if the `tool.uv.dev-dependencies` table is present.
@@ -1109,6 +1827,136 @@ impl PyProjectTomlMut {
             .is_some()
     }
 
+    /// Returns the requirements in the given section.
+    ///
+    /// Unlike the editing methods, which skip over entries that aren't valid requirements, an
+    /// invalid entry is returned as an [`Error::RequirementParse`]. Entries that aren't strings
+    /// (e.g., `{ include-group = "..." }`) are skipped.
+    pub fn dependencies(
+        &self,
+        dependency_type: &DependencyType,
+    ) -> Result<Vec<Requirement>, Error> {
+        let Some(array) = dependency_array(self.doc.as_table(), self.target, dependency_type)
+        else {
+            return Ok(Vec::new());
+        };
+        array
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| entry.as_str().map(|entry| (index, entry)))
+            .map(|(index, entry)| {
+                Requirement::from_str(entry).map_err(|err| {
+                    self.requirement_error(dependency_type, index, entry.to_string(), err)
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the requirements that would be installed in the given environment, with the given
+    /// extras enabled.
+    ///
+    /// Includes `project.dependencies`, along with the `project.optional-dependencies` for each
+    /// extra, and drops any requirement whose markers evaluate to `false`. Markers on the extras
+    /// (e.g., `extra == "cli"`) are evaluated against the enabled extras. Requirements on the
+    /// project itself (e.g., `albatross[cli]` in `project.optional-dependencies.all`) are
+    /// flattened, such that they enable their extras rather than being returned.
+    pub fn dependencies_for_environment(
+        &self,
+        env: &MarkerEnvironment,
+        extras: &[ExtraName],
+    ) -> Result<Vec<Requirement>, Error> {
+        let name = self
+            .doc
+            .get("project")
+            .and_then(|project| project.get("name"))
+            .and_then(Item::as_str)
+            .and_then(|name| PackageName::from_str(name).ok());
+
+        let mut enabled = Vec::new();
+        let mut sections = vec![DependencyType::Production];
+        for extra in extras {
+            if !enabled.contains(extra) {
+                enabled.push(extra.clone());
+                sections.push(DependencyType::Optional(extra.clone()));
+            }
+        }
+
+        // Enabling an extra through a self-reference may enable further extras, so markers are
+        // only evaluated against the final set of extras once every section has been read.
+        let mut requirements = Vec::new();
+        let mut index = 0;
+        while let Some(dependency_type) = sections.get(index) {
+            for requirement in self.dependencies(dependency_type)? {
+                if name.as_ref() == Some(&requirement.name) {
+                    if requirement.marker.evaluate(env, &enabled) {
+                        for extra in &requirement.extras {
+                            if !enabled.contains(extra) {
+                                enabled.push(extra.clone());
+                                sections.push(DependencyType::Optional(extra.clone()));
+                            }
+                        }
+                    }
+                } else {
+                    requirements.push(requirement);
+                }
+            }
+            index += 1;
+        }
+
+        requirements.retain(|requirement| requirement.marker.evaluate(env, &enabled));
+        Ok(requirements)
+    }
+
+    /// Create an [`Error::RequirementParse`] for the invalid entry at the given index.
+    fn requirement_error(
+        &self,
+        dependency_type: &DependencyType,
+        index: usize,
+        requirement: String,
+        err: Pep508Error,
+    ) -> Error {
+        let offset = requirement
+            .get(..err.start)
+            .map_or(err.start, |prefix| prefix.chars().count());
+        let span = self.entry_span(dependency_type, index, &requirement, &err);
+        Error::RequirementParse(Box::new(RequirementParseError {
+            section: section(dependency_type),
+            index,
+            requirement,
+            offset,
+            span,
+            err,
+        }))
+    }
+
+    /// Returns the byte range in the file of the position at which the requirement at the given
+    /// index failed to parse.
+    ///
+    /// If the entry was modified since the document was loaded, its position in the file is
+    /// unknown. If the TOML string contains escapes, the position within the string can't be
+    /// mapped to the file, so the range of the whole string is returned.
+    fn entry_span(
+        &self,
+        dependency_type: &DependencyType,
+        index: usize,
+        requirement: &str,
+        err: &Pep508Error,
+    ) -> Option<Range<usize>> {
+        let source = self.contents.as_str();
+        let document = toml_edit::Document::parse(source).ok()?;
+        let span = dependency_array(document.as_table(), self.target, dependency_type)?
+            .get(index)
+            .filter(|entry| entry.as_str() == Some(requirement))?
+            .span()?;
+        let raw = source.get(span.clone())?;
+        if raw.len() == requirement.len() + 2 && raw.get(1..raw.len() - 1) == Some(requirement) {
+            let start = span.start + 1 + err.start;
+            Some(start..start + err.len.max(1))
+        } else {
+            Some(span)
+        }
+    }
+
     /// Returns `true` if the `dependency-groups` table is present and contains the given group.
     pub fn has_dependency_group(&self, group: &GroupName) -> bool {
         self.doc
@@ -1118,6 +1966,20 @@ impl PyProjectTomlMut {
             .is_some()
     }
 
+    /// Returns the entries in the given section that are requirements for the given package, along
+    /// with their indices.
+    ///
+    /// Entries that aren't valid requirements are skipped.
+    pub fn find_requirements(
+        &self,
+        dependency_type: &DependencyType,
+        name: &PackageName,
+    ) -> Vec<(usize, Requirement)> {
+        dependency_array(self.doc.as_table(), self.target, dependency_type)
+            .map(|array| find_dependencies(name, None, array))
+            .unwrap_or_default()
+    }
+
     /// Returns all the places in this `pyproject.toml` that contain a dependency with the given
     /// name.
     ///
@@ -1218,13 +2080,26 @@ impl PyProjectTomlMut {
         dynamic.iter().any(|val| val.as_str() == Some("version"))
     }
 
-    pub fn set_version(&mut self, version: &Version) -> Result<(), Error> {
+    /// Set `project.version` to the given version.
+    ///
+    /// Returns `true` if the document was modified, i.e., if the project had a different version.
+    pub fn set_version(&mut self, version: &Version) -> Result<bool, Error> {
+        self.record(RecordedEdit::SetVersion(version.clone()));
+
         let project = self
             .doc
             .get_mut("project")
             .and_then(Item::as_table_mut)
             .ok_or(Error::MalformedWorkspace)?;
 
+        let old = project
+            .get("version")
+            .and_then(Item::as_str)
+            .map(ToString::to_string);
+        if old.as_deref() == Some(version.to_string().as_str()) {
+            return Ok(false);
+        }
+
         if let Some(existing) = project.get_mut("version") {
             if let Some(value) = existing.as_value_mut() {
                 let mut formatted = Value::from(version.to_string());
@@ -1236,11 +2111,81 @@ impl PyProjectTomlMut {
         } else {
             project.insert("version", Item::Value(Value::from(version.to_string())));
         }
+        debug!(version = %version, "Set `project.version`");
 
-        Ok(())
+        self.log(PyProjectEdit::SetKey {
+            path: "project.version".to_string(),
+            old,
+            new: Some(version.to_string()),
+        });
+        Ok(true)
+    }
+
+    /// Set `project.requires-python` to the given specifiers.
+    ///
+    /// Returns `true` if the document was modified, i.e., if the project had different specifiers.
+    pub fn set_requires_python(&mut self, specifiers: &VersionSpecifiers) -> Result<bool, Error> {
+        self.record(RecordedEdit::SetRequiresPython(specifiers.clone()));
+
+        let project = self.project()?;
+        let old = project
+            .get("requires-python")
+            .and_then(Item::as_str)
+            .map(ToString::to_string);
+        let new = specifiers.to_string();
+        if old
+            .as_deref()
+            .and_then(|old| VersionSpecifiers::from_str(old).ok())
+            .is_some_and(|old| old == *specifiers)
+        {
+            return Ok(false);
+        }
+
+        if let Some(value) = project
+            .get_mut("requires-python")
+            .and_then(Item::as_value_mut)
+        {
+            let mut formatted = Value::from(new.as_str());
+            *formatted.decor_mut() = value.decor().clone();
+            *value = formatted;
+        } else {
+            project.insert("requires-python", Item::Value(Value::from(new.as_str())));
+        }
+        debug!(specifiers = %new, "Set `project.requires-python`");
+
+        self.log(PyProjectEdit::SetKey {
+            path: "project.requires-python".to_string(),
+            old,
+            new: Some(new),
+        });
+        Ok(true)
     }
 }
 
+/// Emit an event for a dependency that was added to, or replaced in, the given section.
+fn trace_edit(dependency_type: &DependencyType, name: &PackageName, edit: &ArrayEdit) {
+    debug!(
+        package = %name,
+        section = %section(dependency_type),
+        action = match edit {
+            ArrayEdit::Add(_) => "added",
+            ArrayEdit::Update(_) => "replaced",
+            ArrayEdit::Unchanged(_) => "unchanged",
+        },
+        "Edited dependency"
+    );
+}
+
+/// Emit an event for the entries of a dependency that were removed from the given section.
+fn trace_removal(dependency_type: &DependencyType, name: &PackageName, removed: &[Requirement]) {
+    debug!(
+        package = %name,
+        section = %section(dependency_type),
+        removed = removed.len(),
+        "Removed dependency"
+    );
+}
+
 /// Returns an implicit table.
 fn implicit() -> Item {
     let mut table = Table::new();
@@ -1250,13 +2195,28 @@ fn implicit() -> Item {
 
 /// Adds a dependency to the given `deps` array.
 ///
-/// Returns `true` if the dependency was added, `false` if it was updated.
+/// Returns the [`ArrayEdit`] applied to the array; [`ArrayEdit::Unchanged`] if an equivalent
+/// requirement is already present, in which case the array is left as-is.
 pub fn add_dependency(
     req: &Requirement,
     deps: &mut Array,
     has_source: bool,
     raw: bool,
 ) -> Result<ArrayEdit, Error> {
+    insert_dependency(req, deps, has_source, raw, true).map(|(edit, _)| edit)
+}
+
+/// Adds a dependency to the given `deps` array, reformatting the array afterwards if `reformat`
+/// is set.
+///
+/// Returns the [`ArrayEdit`] applied to the array, along with the entry it replaced, if any.
+fn insert_dependency(
+    req: &Requirement,
+    deps: &mut Array,
+    has_source: bool,
+    raw: bool,
+    reformat: bool,
+) -> Result<(ArrayEdit, Option<String>), Error> {
     let mut to_replace = find_dependencies(&req.name, Some(&req.marker), deps);
 
     match to_replace.as_slice() {
@@ -1319,16 +2279,16 @@ pub fn add_dependency(
             } else {
                 req.to_string()
             };
+            let req_lowercase = req_string.to_lowercase();
             let index = match sort {
                 Sort::CaseInsensitive => deps.iter().position(|dep| {
                     dep.as_str().is_some_and(|dep| {
-                        split_specifiers(&dep.to_lowercase())
-                            > split_specifiers(&req_string.to_lowercase())
+                        split_specifiers(&dep.to_lowercase()) > split_specifiers(&req_lowercase)
                     })
                 }),
                 Sort::CaseInsensitiveNaive => deps.iter().position(|dep| {
                     dep.as_str()
-                        .is_some_and(|dep| dep.to_lowercase() > req_string.to_lowercase())
+                        .is_some_and(|dep| dep.to_lowercase() > req_lowercase)
                 }),
                 Sort::CaseSensitive => deps.iter().position(|dep| {
                     dep.as_str()
@@ -1414,7 +2374,6 @@ pub fn add_dependency(
             // the new dependency.
             if deps.len() > 1 && index == 0 {
                 let prefix = deps
-                    .clone()
                     .get(index + 1)
                     .unwrap()
                     .decor()
@@ -1494,16 +2453,33 @@ pub fn add_dependency(
                 }
             }
 
-            reformat_array_multiline(deps);
+            if reformat {
+                reformat_array_multiline(deps);
+            }
 
-            Ok(ArrayEdit::Add(index))
+            Ok((ArrayEdit::Add(index), None))
         }
         [_] => {
             let (i, mut old_req) = to_replace.remove(0);
             update_requirement(&mut old_req, req, has_source);
-            deps.replace(i, old_req.to_string());
-            reformat_array_multiline(deps);
-            Ok(ArrayEdit::Update(i))
+            let updated = old_req.to_string();
+
+            // If the existing entry is equivalent to the updated requirement (e.g., it only
+            // differs in whitespace or in the order of its specifiers), leave it as-is.
+            if deps
+                .get(i)
+                .and_then(Value::as_str)
+                .and_then(try_parse_requirement)
+                .is_some_and(|existing| requirements_equal(&existing, &old_req))
+            {
+                return Ok((ArrayEdit::Unchanged(i), None));
+            }
+
+            let replaced = deps.replace(i, updated);
+            if reformat {
+                reformat_array_multiline(deps);
+            }
+            Ok((ArrayEdit::Update(i), replaced.as_str().map(str::to_string)))
         }
         // Cannot perform ambiguous updates.
         _ => Err(Error::Ambiguous {
@@ -1612,7 +2588,11 @@ fn find_dependencies(
 ) -> Vec<(usize, Requirement)> {
     let mut to_replace = Vec::new();
     for (i, dep) in deps.iter().enumerate() {
-        if let Some(req) = dep.as_str().and_then(try_parse_requirement) {
+        // Avoid parsing requirements for other packages.
+        let Some(dep) = dep.as_str().filter(|dep| may_be_named(dep, name)) else {
+            continue;
+        };
+        if let Some(req) = try_parse_requirement(dep) {
             if marker.is_none_or(|m| *m == req.marker) && *name == req.name {
                 to_replace.push((i, req));
             }
@@ -1621,18 +2601,141 @@ fn find_dependencies(
     to_replace
 }
 
+/// Returns `false` if the requirement can't be for the package with the given name, without
+/// parsing it.
+///
+/// The leading name of the requirement is compared to the package name after normalization. A
+/// requirement that doesn't start with a name may match.
+fn may_be_named(req: &str, name: &PackageName) -> bool {
+    let req = req.trim_start();
+    let end = req
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .unwrap_or(req.len());
+    let prefix = &req[..end];
+    if prefix.is_empty() {
+        return true;
+    }
+
+    // Normalize as in `PackageName`, i.e., lowercase and replace runs of `-`, `_`, and `.` with
+    // a single `-`.
+    let mut expected = name.as_ref().bytes();
+    let mut separator = false;
+    for byte in prefix.bytes() {
+        if matches!(byte, b'-' | b'_' | b'.') {
+            separator = true;
+            continue;
+        }
+        if separator {
+            if expected.next() != Some(b'-') {
+                return false;
+            }
+            separator = false;
+        }
+        if expected.next() != Some(byte.to_ascii_lowercase()) {
+            return false;
+        }
+    }
+    if separator && expected.next() != Some(b'-') {
+        return false;
+    }
+    expected.next().is_none()
+}
+
+/// Returns the name of the section for the given dependency type, e.g., `project.dependencies`.
+pub(crate) fn section(dependency_type: &DependencyType) -> String {
+    match dependency_type {
+        DependencyType::Production => "project.dependencies".to_string(),
+        DependencyType::Dev => "tool.uv.dev-dependencies".to_string(),
+        DependencyType::Optional(extra) => format!("project.optional-dependencies.{extra}"),
+        DependencyType::Group(group) => format!("dependency-groups.{group}"),
+    }
+}
+
+/// Returns the dependency type for the given section name, e.g., `project.dependencies`; the
+/// inverse of [`section`].
+pub(crate) fn dependency_type(section: &str) -> Option<DependencyType> {
+    if let Some(extra) = section.strip_prefix("project.optional-dependencies.") {
+        return ExtraName::from_str(extra)
+            .ok()
+            .map(DependencyType::Optional);
+    }
+    if let Some(group) = section.strip_prefix("dependency-groups.") {
+        return GroupName::from_str(group).ok().map(DependencyType::Group);
+    }
+    match section {
+        "project.dependencies" => Some(DependencyType::Production),
+        "tool.uv.dev-dependencies" => Some(DependencyType::Dev),
+        _ => None,
+    }
+}
+
+/// Returns the array for the given dependency type, if it exists.
+///
+/// The root table is taken as an argument, such that the array can be retrieved from both a
+/// [`DocumentMut`] and a span-preserving [`toml_edit::Document`].
+fn dependency_array<'doc>(
+    root: &'doc Table,
+    target: DependencyTarget,
+    dependency_type: &DependencyType,
+) -> Option<&'doc Array> {
+    let project = match target {
+        DependencyTarget::Script => Some(root),
+        DependencyTarget::PyProjectToml => root.get("project").and_then(Item::as_table),
+    };
+    match dependency_type {
+        DependencyType::Production => project?.get("dependencies")?.as_array(),
+        DependencyType::Dev => root
+            .get("tool")?
+            .get("uv")?
+            .get("dev-dependencies")?
+            .as_array(),
+        DependencyType::Optional(extra) => {
+            let extras = project?.get("optional-dependencies")?.as_table_like()?;
+            find_group(extras, |key| {
+                ExtraName::from_str(key).is_ok_and(|key| key == *extra)
+            })
+        }
+        DependencyType::Group(group) => {
+            let groups = root.get("dependency-groups")?.as_table_like()?;
+            find_group(groups, |key| {
+                GroupName::from_str(key).is_ok_and(|key| key == *group)
+            })
+        }
+    }
+}
+
+/// Returns the array for the first key in the table that matches the predicate.
+fn find_group(table: &dyn TableLike, matches: impl Fn(&str) -> bool) -> Option<&Array> {
+    table
+        .iter()
+        .find(|(key, _)| matches(key))
+        .and_then(|(_, value)| value.as_array())
+}
+
 /// Returns the key in `tool.uv.sources` that matches the given package name.
 fn find_source(name: &PackageName, sources: &Table) -> Option<String> {
     for (key, _) in sources {
-        if PackageName::from_str(key).is_ok_and(|ref key| key == name) {
+        if PackageName::normalize(key).is_ok_and(|key| key == name.as_str()) {
             return Some(key.to_string());
         }
     }
     None
 }
 
-// Add a source to `tool.uv.sources`.
-fn add_source(req: &PackageName, source: &Source, sources: &mut Table) -> Result<(), Error> {
+/// Serialize a source as an inline table, for `tool.uv.sources`.
+fn source_value(source: &Source) -> Result<Value, Error> {
+    // Write path sources with forward slashes, such that they're portable.
+    let source = if let Source::Path { path, .. } = source {
+        let normalized = portable::normalize_for_write(&path.to_string())?.into_owned();
+        let mut source = source.clone();
+        if let Source::Path { path, .. } = &mut source {
+            *path = PortablePathBuf::from(normalized.as_str());
+        }
+        Cow::Owned(source)
+    } else {
+        Cow::Borrowed(source)
+    };
+
     // Serialize as an inline table.
     let mut doc = toml::to_string(&source)
         .map_err(Box::new)?
@@ -1640,9 +2743,20 @@ fn add_source(req: &PackageName, source: &Source, sources: &mut Table) -> Result
         .unwrap();
     let table = mem::take(doc.as_table_mut()).into_inline_table();
 
-    sources.insert(req.as_ref(), Item::Value(Value::InlineTable(table)));
+    Ok(Value::InlineTable(table))
+}
 
-    Ok(())
+/// Returns `true` if the two values are equal, ignoring their formatting.
+fn same_value(left: &Value, right: &Value) -> bool {
+    let normalize = |value: &Value| {
+        let mut value = value.clone();
+        value.decor_mut().clear();
+        if let Value::InlineTable(table) = &mut value {
+            table.fmt();
+        }
+        value.to_string()
+    };
+    normalize(left) == normalize(right)
 }
 
 impl fmt::Display for PyProjectTomlMut {
@@ -1655,6 +2769,12 @@ fn try_parse_requirement(req: &str) -> Option<Requirement> {
     Requirement::from_str(req).ok()
 }
 
+/// Returns the canonical form of a requirement, as written by [`add_dependency`], such that
+/// equivalent requirements (e.g., `anyio >= 4` and `anyio>=4`) compare equal.
+fn canonical_requirement(req: &str) -> Option<String> {
+    try_parse_requirement(req).map(|req| req.to_string())
+}
+
 /// Reformats a TOML array to multi line while trying to preserve all comments
 /// and move them around. This also formats the array to have a trailing comma.
 fn reformat_array_multiline(deps: &mut Array) {
@@ -1790,12 +2910,958 @@ fn split_specifiers(req: &str) -> (&str, &str) {
 
 #[cfg(test)]
 mod test {
-    use super::{AddBoundsKind, reformat_array_multiline, remove_dependency, split_specifiers};
+    use super::{
+        AddBoundsKind, ArrayEdit, DependencyTarget, Error, PyProjectTomlMut,
+        reformat_array_multiline, remove_dependency, split_specifiers,
+    };
+    use super::{BackupStyle, SaveOptions, SaveOutcome};
+    use crate::pyproject::DependencyType;
+    use crate::pyproject::Source;
     use insta::assert_snapshot;
     use std::str::FromStr;
     use toml_edit::DocumentMut;
-    use uv_normalize::PackageName;
-    use uv_pep440::Version;
+    use uv_fs::{PortablePathBuf, Simplified};
+    use uv_normalize::{ExtraName, GroupName, PackageName};
+    use uv_pep440::{Version, VersionSpecifiers};
+    use uv_pep508::{MarkerEnvironment, MarkerEnvironmentBuilder, MarkerTree, Requirement};
+
+    #[test]
+    fn save() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("pyproject.toml");
+        fs_err::write(&path, "[project]\nname = \"albatross\"\n")?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs_err::set_permissions(&path, std::fs::Permissions::from_mode(0o640))?;
+        }
+
+        let mut pyproject = PyProjectTomlMut::from_toml(
+            &fs_err::read_to_string(&path)?,
+            DependencyTarget::PyProjectToml,
+        )?;
+        pyproject.add_dependency(&Requirement::from_str("anyio>=4")?, None, false)?;
+        pyproject.save_to(&path)?;
+
+        assert_snapshot!(fs_err::read_to_string(&path)?, @r#"
+        [project]
+        name = "albatross"
+        dependencies = [
+            "anyio>=4",
+        ]
+        "#);
+
+        // The permissions of the existing file are preserved.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs_err::metadata(&path)?.permissions().mode() & 0o777, 0o640);
+        }
+
+        // No temporary files are left behind.
+        assert_eq!(fs_err::read_dir(temp_dir.path())?.count(), 1);
+
+        // The serialized document matches the saved contents.
+        assert_eq!(pyproject.to_string(), fs_err::read_to_string(&path)?);
+        assert_eq!(pyproject.to_bytes(), fs_err::read(&path)?);
+        let mut buffer = Vec::new();
+        pyproject.write_to(&mut buffer)?;
+        assert_eq!(buffer, fs_err::read(&path)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn save_origin() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("pyproject.toml");
+        fs_err::write(&path, "[project]\nname = \"albatross\"\n")?;
+
+        let mut pyproject = PyProjectTomlMut::load(&path, DependencyTarget::PyProjectToml)?;
+        assert_eq!(pyproject.path(), Some(path.as_path()));
+        pyproject.add_dependency(&Requirement::from_str("anyio>=4")?, None, false)?;
+        assert_eq!(pyproject.save()?, SaveOutcome::Written);
+        assert!(fs_err::read_to_string(&path)?.contains("anyio>=4"));
+
+        // An unmodified document is still written to a different path.
+        let other = temp_dir.path().join("other.toml");
+        assert_eq!(pyproject.save_to(&other)?, SaveOutcome::Written);
+        assert_eq!(fs_err::read_to_string(&other)?, pyproject.to_string());
+        assert_eq!(pyproject.path(), Some(path.as_path()));
+
+        // A document that wasn't read from a file can only be saved to an explicit path.
+        let mut pyproject = PyProjectTomlMut::from_toml(
+            "[project]\nname = \"albatross\"\n",
+            DependencyTarget::PyProjectToml,
+        )?;
+        assert_eq!(pyproject.path(), None);
+        assert!(matches!(pyproject.save(), Err(super::Error::NotLoaded)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn save_unchanged() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("pyproject.toml");
+        fs_err::write(
+            &path,
+            "[project]\nname = \"albatross\"\ndependencies = [\n    \"anyio>=4\",\n]\n",
+        )?;
+        let mtime = filetime::FileTime::from_unix_time(1_000_000_000, 0);
+        filetime::set_file_mtime(&path, mtime)?;
+        let modified = || -> anyhow::Result<_> {
+            Ok(filetime::FileTime::from_last_modification_time(
+                &fs_err::metadata(&path)?,
+            ))
+        };
+
+        let mut pyproject = PyProjectTomlMut::from_toml(
+            &fs_err::read_to_string(&path)?,
+            DependencyTarget::PyProjectToml,
+        )?;
+        assert!(!pyproject.is_dirty());
+        assert_eq!(pyproject.save_to(&path)?, SaveOutcome::Unchanged);
+
+        // Re-adding an existing requirement verbatim doesn't rewrite the file.
+        pyproject.add_dependency(&Requirement::from_str("anyio>=4")?, None, false)?;
+        assert_eq!(pyproject.save_to(&path)?, SaveOutcome::Unchanged);
+        assert!(!pyproject.is_dirty());
+        assert_eq!(modified()?, mtime);
+
+        pyproject.add_dependency(&Requirement::from_str("anyio>=4.1")?, None, false)?;
+        assert!(pyproject.is_dirty());
+        assert_eq!(pyproject.save_to(&path)?, SaveOutcome::Written);
+        assert!(!pyproject.is_dirty());
+        assert_ne!(modified()?, mtime);
+
+        Ok(())
+    }
+
+    /// A document that wasn't loaded from disk is written over a differing existing file, even
+    /// if it wasn't modified.
+    #[test]
+    fn save_new_document_over_existing() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("pyproject.toml");
+        fs_err::write(&path, "[project]\nname = \"seeds\"\n")?;
+
+        let contents = "[project]\nname = \"albatross\"\n";
+        let mut pyproject = PyProjectTomlMut::from_toml(contents, DependencyTarget::PyProjectToml)?;
+        assert!(!pyproject.is_dirty());
+        assert_eq!(pyproject.save_to(&path)?, SaveOutcome::Written);
+        assert_eq!(fs_err::read_to_string(&path)?, contents);
+
+        Ok(())
+    }
+
+    #[test]
+    fn is_modified() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("pyproject.toml");
+        let contents = r#"[project]
+name = "albatross"
+version = "1.0.0"
+dependencies = [
+    "anyio >= 4",
+]
+
+[dependency-groups]
+dev = ["pytest"]
+
+[tool.uv.sources]
+anyio = { path = "packages/anyio", editable = true }
+"#;
+        fs_err::write(&path, contents)?;
+
+        let mut pyproject = PyProjectTomlMut::load(&path, DependencyTarget::PyProjectToml)?;
+        assert!(!pyproject.is_modified());
+
+        // Adding an equivalent requirement, or an existing source, leaves the document as-is.
+        let source = Source::Path {
+            path: PortablePathBuf::from("packages/anyio"),
+            editable: Some(true),
+            package: None,
+            marker: MarkerTree::TRUE,
+            extra: None,
+            group: None,
+        };
+        let anyio = Requirement::from_str("anyio>=4")?;
+        assert_eq!(
+            pyproject.add_dependency(&anyio, Some(&source), false)?,
+            ArrayEdit::Unchanged(0)
+        );
+        assert_eq!(
+            pyproject.add_dependency(&Requirement::from_str("anyio")?, None, false)?,
+            ArrayEdit::Unchanged(0)
+        );
+        assert_eq!(
+            pyproject.add_dependency_group_requirement(
+                &GroupName::from_str("dev")?,
+                &Requirement::from_str("pytest")?,
+                None,
+                false,
+            )?,
+            ArrayEdit::Unchanged(0)
+        );
+        assert!(!pyproject.ensure_dependency_group(&GroupName::from_str("dev")?)?);
+        assert!(!pyproject.set_version(&Version::from_str("1.0.0")?)?);
+        assert!(
+            pyproject
+                .remove_dependency(&PackageName::from_str("flask")?)?
+                .is_empty()
+        );
+        assert!(!pyproject.is_modified());
+        assert_eq!(pyproject.to_string(), contents);
+
+        // A changed source is reported as an update to the requirement.
+        let source = Source::Path {
+            path: PortablePathBuf::from("packages/anyio"),
+            editable: Some(false),
+            package: None,
+            marker: MarkerTree::TRUE,
+            extra: None,
+            group: None,
+        };
+        assert_eq!(
+            pyproject.add_dependency(&anyio, Some(&source), false)?,
+            ArrayEdit::Update(0)
+        );
+        assert!(pyproject.is_modified());
+
+        // Saving clears the flag.
+        assert_eq!(pyproject.save()?, SaveOutcome::Written);
+        assert!(!pyproject.is_modified());
+
+        assert!(pyproject.add_workspace("packages/anyio")?);
+        assert!(!pyproject.add_workspace("packages/anyio")?);
+        assert!(pyproject.is_modified());
+
+        // Reloading clears the flag.
+        pyproject.reload()?;
+        assert!(!pyproject.is_modified());
+
+        Ok(())
+    }
+
+    #[test]
+    fn add_dependencies() -> anyhow::Result<()> {
+        let contents = r#"[project]
+name = "albatross"
+version = "1.0.0"
+dependencies = [
+    # comment
+    "anyio>=3", # end-of-line comment
+    "flask",
+    "requests",
+]
+"#;
+        let requirements = ["aiohttp", "anyio>=4", "click", "flask", "zstandard"]
+            .into_iter()
+            .map(Requirement::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Adding the requirements as a batch is equivalent to adding them one by one.
+        let mut sequential =
+            PyProjectTomlMut::from_toml(contents, DependencyTarget::PyProjectToml)?;
+        for requirement in &requirements {
+            sequential.add_dependency(requirement, None, false)?;
+        }
+
+        let mut pyproject =
+            PyProjectTomlMut::from_toml(contents, DependencyTarget::PyProjectToml)?.with_history();
+        let edits = pyproject.add_dependencies(
+            &DependencyType::Production,
+            requirements.iter().map(|requirement| (requirement, None)),
+            false,
+        )?;
+        assert_eq!(
+            edits,
+            [
+                ArrayEdit::Add(0),
+                ArrayEdit::Update(1),
+                ArrayEdit::Add(2),
+                ArrayEdit::Unchanged(3),
+                ArrayEdit::Add(5),
+            ]
+        );
+        assert_eq!(pyproject.to_string(), sequential.to_string());
+        assert_snapshot!(pyproject.to_string(), @r#"
+        [project]
+        name = "albatross"
+        version = "1.0.0"
+        dependencies = [
+            "aiohttp",
+            # comment
+            "anyio>=4", # end-of-line comment
+            "click",
+            "flask",
+            "requests",
+            "zstandard",
+        ]
+        "#);
+        assert_eq!(pyproject.edits(), sequential.edits());
+
+        // The batch is reverted as a whole.
+        pyproject.set_version(&Version::from_str("1.1.0")?)?;
+        assert_eq!(pyproject.revert_last().len(), 1);
+        assert_eq!(pyproject.revert_last().len(), 4);
+        assert_eq!(pyproject.to_string(), contents);
+        assert!(!pyproject.is_modified());
+
+        Ok(())
+    }
+
+    #[test]
+    fn dependencies_for_environment() -> anyhow::Result<()> {
+        let contents = r#"[project]
+name = "albatross"
+version = "1.0.0"
+dependencies = [
+    "anyio",
+    "tomli ; python_version < '3.11'",
+    "exceptiongroup ; python_full_version < '3.11.0'",
+    "pywin32 ; sys_platform == 'win32'",
+    "uvloop ; sys_platform == 'linux' or sys_platform == 'darwin'",
+    "colorama ; sys_platform == 'win32' or python_version >= '3.13'",
+    "rich ; extra == 'cli'",
+]
+
+[project.optional-dependencies]
+cli = ["click>=8"]
+docs = ["sphinx ; python_version >= '3.12'"]
+all = ["albatross[cli,docs]", "albatross[win] ; sys_platform == 'win32'"]
+win = ["pywin32-ctypes"]
+"#;
+        let pyproject = PyProjectTomlMut::from_toml(contents, DependencyTarget::PyProjectToml)?;
+
+        let environment = |python_version: &str, sys_platform: &str| {
+            MarkerEnvironment::try_from(MarkerEnvironmentBuilder {
+                implementation_name: "cpython",
+                implementation_version: python_version,
+                os_name: "posix",
+                platform_machine: "x86_64",
+                platform_python_implementation: "CPython",
+                platform_release: "",
+                platform_system: "",
+                platform_version: "",
+                python_full_version: python_version,
+                python_version: python_version
+                    .rsplit_once('.')
+                    .map_or(python_version, |(major_minor, _)| major_minor),
+                sys_platform,
+            })
+        };
+        let names = |env: &MarkerEnvironment, extras: &[&str]| -> anyhow::Result<Vec<String>> {
+            let extras = extras
+                .iter()
+                .map(|extra| ExtraName::from_str(extra))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(pyproject
+                .dependencies_for_environment(env, &extras)?
+                .into_iter()
+                .map(|requirement| requirement.name.to_string())
+                .collect())
+        };
+
+        let linux_310 = environment("3.10.4", "linux")?;
+        assert_eq!(
+            names(&linux_310, &[])?,
+            ["anyio", "tomli", "exceptiongroup", "uvloop"]
+        );
+
+        let linux_313 = environment("3.13.1", "linux")?;
+        assert_eq!(names(&linux_313, &[])?, ["anyio", "uvloop", "colorama"]);
+
+        let windows_311 = environment("3.11.0", "win32")?;
+        assert_eq!(names(&windows_311, &[])?, ["anyio", "pywin32", "colorama"]);
+
+        // Extras enable their optional dependencies, and `extra` markers.
+        assert_eq!(
+            names(&linux_310, &["cli"])?,
+            [
+                "anyio",
+                "tomli",
+                "exceptiongroup",
+                "uvloop",
+                "rich",
+                "click"
+            ]
+        );
+        assert_eq!(
+            names(&linux_310, &["docs"])?,
+            ["anyio", "tomli", "exceptiongroup", "uvloop"]
+        );
+
+        // Self-references are flattened, subject to their markers.
+        assert_eq!(
+            names(&linux_313, &["all"])?,
+            ["anyio", "uvloop", "colorama", "rich", "click", "sphinx"]
+        );
+        assert_eq!(
+            names(&windows_311, &["all"])?,
+            [
+                "anyio",
+                "pywin32",
+                "colorama",
+                "rich",
+                "click",
+                "pywin32-ctypes"
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn add_equivalent_dependency() -> anyhow::Result<()> {
+        let contents = r#"[project]
+name = "albatross"
+version = "1.0.0"
+dependencies = [
+    "Anyio < 3, >= 2.0",
+    "flask[dotenv, async] ; python_version >= '3.9'",
+]
+"#;
+        let mut pyproject = PyProjectTomlMut::from_toml(contents, DependencyTarget::PyProjectToml)?;
+
+        // Requirements that are only spelled differently are left as-is.
+        for (requirement, index) in [
+            ("anyio>=2,<3", 0),
+            (r#"flask[async,dotenv]; python_version>="3.9""#, 1),
+        ] {
+            assert_eq!(
+                pyproject.add_dependency(&Requirement::from_str(requirement)?, None, false)?,
+                ArrayEdit::Unchanged(index)
+            );
+        }
+        assert_eq!(pyproject.to_string(), contents);
+
+        assert_eq!(
+            pyproject.add_dependency(&Requirement::from_str("anyio~=2.0")?, None, false)?,
+            ArrayEdit::Update(0)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn optional_dependencies_normalized() -> anyhow::Result<()> {
+        let contents = r#"[project]
+name = "albatross"
+version = "1.0.0"
+
+[project.optional-dependencies]
+Dev_Test = ["anyio"]
+"#;
+        let extra = ExtraName::from_str("dev-test")?;
+        let mut pyproject = PyProjectTomlMut::from_toml(contents, DependencyTarget::PyProjectToml)?;
+
+        // Extras are matched by their normalized names, so the existing group is reused.
+        assert!(!pyproject.ensure_optional_dependency(&ExtraName::from_str("DEV.TEST")?)?);
+        pyproject.add_optional_dependency(&extra, &Requirement::from_str("flask")?, None, false)?;
+        assert_eq!(
+            pyproject
+                .remove_optional_dependency(&PackageName::from_str("anyio")?, &extra)?
+                .len(),
+            1
+        );
+        assert_snapshot!(pyproject.to_string(), @r#"
+        [project]
+        name = "albatross"
+        version = "1.0.0"
+
+        [project.optional-dependencies]
+        Dev_Test = [
+            "flask",
+        ]
+        "#);
+
+        Ok(())
+    }
+
+    #[test]
+    fn diff() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("pyproject.toml");
+        fs_err::write(
+            &path,
+            "[project]\nname = \"albatross\"\ndependencies = [\n    \"anyio>=4\",\n    \"flask\",\n]\nversion = \"1.0.0\"",
+        )?;
+
+        let mut pyproject = PyProjectTomlMut::load(&path, DependencyTarget::PyProjectToml)?;
+        assert_eq!(pyproject.diff(), None);
+
+        // The file lacks a trailing newline, which is added when the document is written.
+        pyproject.set_version(&Version::from_str("1.1.0")?)?;
+        pyproject.remove_dependency(&PackageName::from_str("flask")?)?;
+        let diff = pyproject.diff_with_context(1).unwrap();
+        let diff = diff.replace(&path.user_display().to_string(), "[PATH]");
+        assert_snapshot!(diff, @r#"
+        --- [PATH]
+        +++ [PATH]
+        @@ -4,4 +4,3 @@
+             "anyio>=4",
+        -    "flask",
+         ]
+        -version = "1.0.0"
+        \ No newline at end of file
+        +version = "1.1.0"
+        "#);
+
+        // Once saved, there are no pending changes.
+        pyproject.save()?;
+        assert_eq!(pyproject.diff(), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn save_backup() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("pyproject.toml");
+        fs_err::write(&path, "[project]\nname = \"albatross\"\n")?;
+        let mtime = filetime::FileTime::from_unix_time(1_000_000_000, 0);
+        filetime::set_file_mtime(&path, mtime)?;
+
+        let mut pyproject = PyProjectTomlMut::from_toml(
+            &fs_err::read_to_string(&path)?,
+            DependencyTarget::PyProjectToml,
+        )?;
+        let options = SaveOptions {
+            backup: Some(BackupStyle::Suffix(".bak".to_string())),
+            ..SaveOptions::default()
+        };
+        pyproject.add_dependency(&Requirement::from_str("anyio>=4")?, None, false)?;
+        pyproject.save_to_with(&path, &options)?;
+
+        // The backup retains the original contents and modification time.
+        let backup = temp_dir.path().join("pyproject.toml.bak");
+        assert_eq!(
+            fs_err::read_to_string(&backup)?,
+            "[project]\nname = \"albatross\"\n"
+        );
+        assert_eq!(
+            filetime::FileTime::from_last_modification_time(&fs_err::metadata(&backup)?),
+            mtime
+        );
+
+        // Numbered backups are rotated, keeping the most recent.
+        let options = SaveOptions {
+            backup: Some(BackupStyle::Numbered { keep: 2 }),
+            ..SaveOptions::default()
+        };
+        for version in ["4.1", "4.2", "4.3"] {
+            pyproject.add_dependency(
+                &Requirement::from_str(&format!("anyio>={version}"))?,
+                None,
+                false,
+            )?;
+            pyproject.save_to_with(&path, &options)?;
+        }
+        let read = |name: &str| fs_err::read_to_string(temp_dir.path().join(name));
+        assert!(read("pyproject.toml.bak.1")?.contains("anyio>=4.2"));
+        assert!(read("pyproject.toml.bak.2")?.contains("anyio>=4.1"));
+        assert!(!temp_dir.path().join("pyproject.toml.bak.3").exists());
+        assert!(read("pyproject.toml")?.contains("anyio>=4.3"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn save_concurrent_modification() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("pyproject.toml");
+        fs_err::write(&path, "[project]\nname = \"albatross\"\n")?;
+
+        let mut pyproject = PyProjectTomlMut::load(&path, DependencyTarget::PyProjectToml)?;
+        pyproject.add_dependency(&Requirement::from_str("anyio>=4")?, None, false)?;
+
+        // Another tool edits the file in the meantime.
+        fs_err::write(
+            &path,
+            "[project]\nname = \"albatross\"\nversion = \"1.0.0\"\n",
+        )?;
+        let err = pyproject.save_to(&path).unwrap_err();
+        assert_snapshot!(
+            err.to_string().replace(&path.user_display().to_string(), "[PATH]"),
+            @"`[PATH]` was modified since it was read"
+        );
+        assert!(!fs_err::read_to_string(&path)?.contains("anyio"));
+
+        // Saving to a different path isn't affected.
+        let other = temp_dir.path().join("other.toml");
+        assert_eq!(pyproject.save_to(&other)?, SaveOutcome::Written);
+
+        // Forcing the save overwrites the modification.
+        let options = SaveOptions {
+            force: true,
+            ..SaveOptions::default()
+        };
+        pyproject.add_dependency(&Requirement::from_str("anyio>=4.1")?, None, false)?;
+        assert_eq!(
+            pyproject.save_to_with(&path, &options)?,
+            SaveOutcome::Written
+        );
+
+        // After a save, subsequent saves are checked against the written file.
+        pyproject.add_dependency(&Requirement::from_str("anyio>=4.2")?, None, false)?;
+        assert_eq!(pyproject.save_to(&path)?, SaveOutcome::Written);
+
+        // Reloading picks up the modification, discarding any unsaved changes.
+        fs_err::write(
+            &path,
+            "[project]\nname = \"albatross\"\nversion = \"1.0.0\"\n",
+        )?;
+        pyproject.add_dependency(&Requirement::from_str("anyio>=4.3")?, None, false)?;
+        pyproject.reload()?;
+        assert!(!pyproject.is_dirty());
+        assert_snapshot!(pyproject.to_string(), @r#"
+        [project]
+        name = "albatross"
+        version = "1.0.0"
+        "#);
+        pyproject.add_dependency(&Requirement::from_str("anyio>=4.3")?, None, false)?;
+        assert_eq!(pyproject.save_to(&path)?, SaveOutcome::Written);
+
+        Ok(())
+    }
+
+    #[test]
+    fn pyproject_toml() -> anyhow::Result<()> {
+        let mut pyproject = PyProjectTomlMut::from_toml(
+            "[project]\nname = \"albatross\"\nversion = \"0.1.0\"\n",
+            DependencyTarget::PyProjectToml,
+        )?;
+        pyproject.add_dependency(&Requirement::from_str("anyio>=4")?, None, false)?;
+
+        // The typed view reflects the edits, and retains the raw contents.
+        let pyproject_toml = pyproject.pyproject_toml()?;
+        let dependencies = pyproject_toml
+            .project
+            .and_then(|project| project.dependencies)
+            .unwrap_or_default();
+        assert_eq!(dependencies, vec!["anyio>=4"]);
+        assert_eq!(pyproject_toml.raw, pyproject.to_string());
+
+        // Errors are reported with their position in the file.
+        let source = "[project]\nname = \"albatross\"\nversion = 1\n";
+        let pyproject = PyProjectTomlMut::from_toml(source, DependencyTarget::PyProjectToml)?;
+        let err = pyproject.pyproject_toml().unwrap_err();
+        assert_snapshot!(err, @r"
+        TOML parse error at line 3, column 11
+          |
+        3 | version = 1
+          |           ^
+        invalid type: integer `1`, expected a string
+        ");
+
+        Ok(())
+    }
+
+    /// Edits to sections with an unexpected type are rejected, rather than panicking.
+    #[test]
+    fn malformed_sections() -> anyhow::Result<()> {
+        let requirement = Requirement::from_str("anyio>=4")?;
+        let name = PackageName::from_str("anyio")?;
+        let group = GroupName::from_str("dev")?;
+        let extra = ExtraName::from_str("cli")?;
+        for source in [
+            "project = 1\n",
+            "[project]\ndependencies = \"anyio\"\n",
+            "[project]\noptional-dependencies = 1\n",
+            "[project.optional-dependencies]\ncli = 1\n",
+            "dependency-groups = { dev = 1 }\n",
+            "tool = 1\n",
+            "tool = { uv = { dev-dependencies = {} } }\n",
+        ] {
+            let mut pyproject =
+                PyProjectTomlMut::from_toml(source, DependencyTarget::PyProjectToml)?;
+            let results = [
+                pyproject.add_dependency(&requirement, None, false).err(),
+                pyproject
+                    .add_dev_dependency(&requirement, None, false)
+                    .err(),
+                pyproject
+                    .add_optional_dependency(&extra, &requirement, None, false)
+                    .err(),
+                pyproject
+                    .add_dependency_group_requirement(&group, &requirement, None, false)
+                    .err(),
+                pyproject.remove_dependency(&name).err(),
+                pyproject.remove_dev_dependency(&name).err(),
+                pyproject.remove_optional_dependency(&name, &extra).err(),
+                pyproject
+                    .remove_dependency_group_requirement(&name, &group)
+                    .err(),
+            ];
+            assert!(
+                results.iter().any(Option::is_some),
+                "expected an error for: {source}"
+            );
+            assert!(pyproject.to_string().parse::<DocumentMut>().is_ok());
+        }
+        Ok(())
+    }
+
+    /// Parsed requirements can be added to and removed from any section.
+    #[test]
+    fn add_and_remove_requirement() -> anyhow::Result<()> {
+        let source = "[project]\nname = \"albatross\"\n";
+        let requirement = Requirement::from_str("Anyio>=4")?;
+        for dependency_type in [
+            DependencyType::Production,
+            DependencyType::Dev,
+            DependencyType::Optional(ExtraName::from_str("cli")?),
+            DependencyType::Group(GroupName::from_str("dev")?),
+        ] {
+            let mut pyproject =
+                PyProjectTomlMut::from_toml(source, DependencyTarget::PyProjectToml)?;
+            let edit = pyproject.add_requirement(&dependency_type, &requirement, None, false)?;
+            assert!(
+                matches!(edit, ArrayEdit::Add(_)),
+                "expected an addition to {dependency_type:?}"
+            );
+            assert!(pyproject.to_string().contains("\"anyio>=4\""));
+
+            let removed =
+                pyproject.remove_requirement(&dependency_type, &PackageName::from_str("anyio")?)?;
+            assert_eq!(removed, [Requirement::from_str("anyio>=4")?]);
+            assert!(!pyproject.to_string().contains("anyio"));
+        }
+        Ok(())
+    }
+
+    /// Dependencies can be gated to a range of Python versions.
+    #[test]
+    fn add_dependency_for_python() -> anyhow::Result<()> {
+        let source = "[project]\nname = \"albatross\"\n";
+        let mut pyproject = PyProjectTomlMut::from_toml(source, DependencyTarget::PyProjectToml)?;
+        for (requirement, python) in [
+            ("tomli", "<3.11"),
+            ("typing-extensions>=4", ">=3.9, <3.12"),
+            ("uvloop ; sys_platform == 'linux'", ">=3.12"),
+            ("exceptiongroup", "<=3.10"),
+        ] {
+            pyproject.add_dependency_for_python(
+                &Requirement::from_str(requirement)?,
+                &VersionSpecifiers::from_str(python)?,
+                None,
+                false,
+            )?;
+        }
+        assert_snapshot!(pyproject.to_string(), @r#"
+        [project]
+        name = "albatross"
+        dependencies = [
+            "exceptiongroup ; python_full_version <= '3.10'",
+            "tomli ; python_full_version < '3.11'",
+            "typing-extensions>=4 ; python_full_version >= '3.9' and python_full_version < '3.12'",
+            "uvloop ; python_full_version >= '3.12' and sys_platform == 'linux'",
+        ]
+        "#);
+
+        let err = pyproject
+            .add_dependency_for_python(
+                &Requirement::from_str("anyio")?,
+                &VersionSpecifiers::from_str(">=3.13.0rc1")?,
+                None,
+                false,
+            )
+            .unwrap_err();
+        assert!(matches!(err, Error::PythonMarker(_)));
+        assert!(!pyproject.to_string().contains("anyio"));
+        Ok(())
+    }
+
+    #[test]
+    fn set_requires_python_and_marker() -> anyhow::Result<()> {
+        let source = "[project]\nname = \"albatross\"\nrequires-python = \">=3.9\" # minimum\ndependencies = [\"tomli\"]\n";
+        let mut pyproject = PyProjectTomlMut::from_toml(source, DependencyTarget::PyProjectToml)?;
+        assert!(!pyproject.set_requires_python(&VersionSpecifiers::from_str(">= 3.9")?)?);
+        assert!(pyproject.set_requires_python(&VersionSpecifiers::from_str(">=3.10")?)?);
+        assert!(pyproject.set_dependency_marker(
+            &DependencyType::Production,
+            0,
+            MarkerTree::from_str("python_version < '3.11'")?,
+        )?);
+        assert_snapshot!(pyproject.to_string(), @r#"
+        [project]
+        name = "albatross"
+        requires-python = ">=3.10" # minimum
+        dependencies = ["tomli ; python_full_version < '3.11'"]
+        "#);
+        assert_eq!(
+            pyproject
+                .edits()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            [
+                "Set `project.requires-python` to `>=3.10`",
+                "Replace `tomli` with `tomli ; python_full_version < '3.11'` in `project.dependencies`",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[tracing_test::traced_test]
+    fn trace_events() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("pyproject.toml");
+        fs_err::write(
+            &path,
+            "[project]\nname = \"albatross\"\ndependencies = [\"anyio>=3\"]\n",
+        )?;
+
+        let mut pyproject = PyProjectTomlMut::load(&path, DependencyTarget::PyProjectToml)?;
+        assert!(logs_contain("Loaded `pyproject.toml`"));
+        assert!(logs_contain("bytes=57"));
+
+        pyproject.add_dependency(&Requirement::from_str("anyio>=4")?, None, false)?;
+        assert!(logs_contain(
+            r#"Edited dependency package=anyio section=project.dependencies action="replaced""#
+        ));
+        pyproject.add_dependency(&Requirement::from_str("tqdm")?, None, false)?;
+        assert!(logs_contain(
+            r#"Edited dependency package=tqdm section=project.dependencies action="added""#
+        ));
+        pyproject.remove_dependency(&PackageName::from_str("tqdm")?)?;
+        assert!(logs_contain(
+            "Removed dependency package=tqdm section=project.dependencies removed=1"
+        ));
+
+        pyproject.save()?;
+        assert!(logs_contain("Saved `pyproject.toml`"));
+        assert!(logs_contain("durable=false"));
+        pyproject.save()?;
+        assert!(logs_contain(r#"reason="unmodified""#));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn load_locked() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("pyproject.toml");
+        fs_err::write(&path, "[project]\nname = \"albatross\"\n")?;
+
+        let mut locked =
+            PyProjectTomlMut::load_locked(&path, DependencyTarget::PyProjectToml).await?;
+
+        // While the lock is held, other attempts fail rather than block.
+        let err = PyProjectTomlMut::try_load_locked(&path, DependencyTarget::PyProjectToml)
+            .err()
+            .unwrap();
+        assert!(matches!(err, super::Error::Locked(_)));
+        let err = PyProjectTomlMut::load_locked_with_timeout(
+            &path,
+            DependencyTarget::PyProjectToml,
+            std::time::Duration::from_millis(50),
+        )
+        .await
+        .err()
+        .unwrap();
+        assert!(matches!(err, super::Error::Locked(_)));
+
+        // Saving releases the lock.
+        locked.add_dependency(&Requirement::from_str("anyio>=4")?, None, false)?;
+        locked.save()?;
+        let locked = PyProjectTomlMut::try_load_locked(&path, DependencyTarget::PyProjectToml)?;
+        assert!(locked.to_string().contains("anyio>=4"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn save_durable() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("pyproject.toml");
+        fs_err::write(&path, "[project]\nname = \"albatross\"\n")?;
+
+        let mut pyproject = PyProjectTomlMut::load(&path, DependencyTarget::PyProjectToml)?;
+        pyproject.add_dependency(&Requirement::from_str("anyio>=4")?, None, false)?;
+        let options = SaveOptions {
+            durable: true,
+            ..SaveOptions::default()
+        };
+        assert_eq!(
+            pyproject.save_to_with(&path, &options)?,
+            SaveOutcome::Written
+        );
+        assert!(fs_err::read_to_string(&path)?.contains("anyio>=4"));
+        assert_eq!(fs_err::read_dir(temp_dir.path())?.count(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn save_read_only() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("pyproject.toml");
+        fs_err::write(&path, "[project]\nname = \"albatross\"\n")?;
+        let mut permissions = fs_err::metadata(&path)?.permissions();
+        permissions.set_readonly(true);
+        fs_err::set_permissions(&path, permissions)?;
+
+        let mut pyproject = PyProjectTomlMut::load(&path, DependencyTarget::PyProjectToml)?;
+        pyproject.add_dependency(&Requirement::from_str("anyio>=4")?, None, false)?;
+        let err = pyproject.save_to(&path).unwrap_err();
+        assert!(matches!(err, super::Error::ReadOnly { is_dir: false, .. }));
+        assert_snapshot!(
+            err.to_string().replace(&path.user_display().to_string(), "[PATH]"),
+            @"Failed to write `[PATH]`, since the file is read-only; make it writable to allow it to be edited"
+        );
+        assert!(!fs_err::read_to_string(&path)?.contains("anyio"));
+
+        Ok(())
+    }
+
+    /// A permission error when writing to a read-only directory.
+    ///
+    /// Permissions aren't enforced for root, so the test is skipped when running as root.
+    #[test]
+    #[cfg(unix)]
+    fn save_read_only_directory() -> anyhow::Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::tempdir()?;
+        let directory = temp_dir.path().join("project");
+        fs_err::create_dir(&directory)?;
+        let path = directory.join("pyproject.toml");
+        fs_err::write(&path, "[project]\nname = \"albatross\"\n")?;
+        fs_err::set_permissions(&directory, std::fs::Permissions::from_mode(0o555))?;
+        if tempfile::tempfile_in(&directory).is_ok() {
+            fs_err::set_permissions(&directory, std::fs::Permissions::from_mode(0o755))?;
+            return Ok(());
+        }
+
+        let mut pyproject = PyProjectTomlMut::load(&path, DependencyTarget::PyProjectToml)?;
+        pyproject.add_dependency(&Requirement::from_str("anyio>=4")?, None, false)?;
+        let err = pyproject.save_to(&path).unwrap_err();
+        fs_err::set_permissions(&directory, std::fs::Permissions::from_mode(0o755))?;
+        assert!(
+            matches!(&err, super::Error::ReadOnly { path, is_dir: true } if *path == directory)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn save_failure() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("missing").join("pyproject.toml");
+
+        let mut pyproject = PyProjectTomlMut::from_toml(
+            "[project]\nname = \"albatross\"\n",
+            DependencyTarget::PyProjectToml,
+        )?;
+        assert!(pyproject.save_to(&path).is_err());
+        assert_eq!(fs_err::read_dir(temp_dir.path())?.count(), 0);
+
+        Ok(())
+    }
 
     #[test]
     fn split() {
@@ -2156,4 +4222,328 @@ dependencies = [
 "#
         );
     }
+
+    #[test]
+    fn requirement_parse_error() -> anyhow::Result<()> {
+        let source = r#"[project]
+name = "albatross"
+dependencies = [
+    "anyio>=4",
+    { include-group = "dev" },
+    "flask >=1.0,<",
+]
+
+[project.optional-dependencies]
+cli = ["tqdm\u0020>="]
+
+[dependency-groups]
+dev = ["pytest", 'rich ; platform_machine == "ü" and']
+"#;
+        let pyproject = PyProjectTomlMut::from_toml(source, DependencyTarget::PyProjectToml)?;
+
+        // The offset is into the entry, and the span is into the file.
+        let Err(Error::RequirementParse(err)) = pyproject.dependencies(&DependencyType::Production)
+        else {
+            panic!("expected a requirement parse error");
+        };
+        assert_snapshot!(err, @"Failed to parse entry 2 of `project.dependencies`: `flask >=1.0,<`");
+        assert_eq!((err.index, err.offset), (2, 12));
+        assert_eq!(err.span.clone().map(|span| &source[span]), Some("<"));
+
+        // The offset is in characters, but the span is in bytes.
+        let group = DependencyType::Group(GroupName::from_str("dev")?);
+        let Err(Error::RequirementParse(err)) = pyproject.dependencies(&group) else {
+            panic!("expected a requirement parse error");
+        };
+        assert_snapshot!(err, @r#"Failed to parse entry 1 of `dependency-groups.dev`: `rich ; platform_machine == "ü" and`"#);
+        assert_eq!((err.index, err.offset), (1, 34));
+        assert_eq!(err.span.clone().map(|span| &source[span]), Some("'"));
+
+        // If the entry contains escapes, the span covers the whole entry.
+        let extra = DependencyType::Optional(ExtraName::from_str("cli")?);
+        let Err(Error::RequirementParse(err)) = pyproject.dependencies(&extra) else {
+            panic!("expected a requirement parse error");
+        };
+        assert_eq!(
+            err.span.clone().map(|span| &source[span]),
+            Some(r#""tqdm\u0020>=""#)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn requirement_parse_error_edited() -> anyhow::Result<()> {
+        let source = "[project]\nname = \"albatross\"\ndependencies = [\"flask >=1.0,<\"]\n";
+        let mut pyproject = PyProjectTomlMut::from_toml(source, DependencyTarget::PyProjectToml)?;
+        let err = pyproject
+            .set_dependency_bound(
+                &DependencyType::Production,
+                0,
+                Version::from_str("1.0")?,
+                AddBoundsKind::Lower,
+            )
+            .unwrap_err();
+        let Error::RequirementParse(err) = err else {
+            panic!("expected a requirement parse error, found: {err:?}");
+        };
+        assert_eq!(err.section, "project.dependencies");
+        assert_eq!(err.span.clone().map(|span| &source[span]), Some("<"));
+
+        // Entries that were modified since the document was loaded have no known span.
+        pyproject.dependencies_array()?.replace(0, "tqdm >=");
+        let Err(Error::RequirementParse(err)) = pyproject.dependencies(&DependencyType::Production)
+        else {
+            panic!("expected a requirement parse error");
+        };
+        assert_eq!(err.requirement, "tqdm >=");
+        assert_eq!(err.span, None);
+
+        Ok(())
+    }
+
+    /// Property tests for sequences of edits to `project.dependencies` and `dependency-groups.dev`.
+    mod properties {
+        use std::collections::BTreeMap;
+        use std::fmt::Write;
+        use std::ops::Range;
+        use std::str::FromStr;
+
+        use proptest::prelude::*;
+        use proptest::sample::select;
+
+        use uv_normalize::{GroupName, PackageName};
+        use uv_pep508::Requirement;
+
+        use crate::pyproject::{DependencyType, PyProjectToml};
+        use crate::pyproject_mut::SaveOutcome;
+        use crate::pyproject_mut::{DependencyTarget, PyProjectTomlMut};
+
+        /// Package names, including different spellings of the same normalized name.
+        const NAMES: &[&str] = &[
+            "anyio",
+            "Flask",
+            "flask",
+            "typing_extensions",
+            "typing-extensions",
+            "Zope.Interface",
+            "rich",
+            "a",
+        ];
+
+        const SPECIFIERS: &[&str] = &["", ">=1", "<2", "==1.0.*", ">=1,<2"];
+
+        #[derive(Debug, Clone)]
+        enum Operation {
+            Add {
+                group: bool,
+                name: &'static str,
+                specifier: &'static str,
+            },
+            Remove {
+                group: bool,
+                name: &'static str,
+            },
+        }
+
+        fn operation() -> impl Strategy<Value = Operation> {
+            prop_oneof![
+                3 => (any::<bool>(), select(NAMES), select(SPECIFIERS)).prop_map(
+                    |(group, name, specifier)| Operation::Add {
+                        group,
+                        name,
+                        specifier,
+                    }
+                ),
+                1 => (any::<bool>(), select(NAMES))
+                    .prop_map(|(group, name)| Operation::Remove { group, name }),
+            ]
+        }
+
+        /// The initial requirements of a section, with distinct normalized names.
+        fn requirements() -> impl Strategy<Value = Vec<String>> {
+            proptest::collection::vec((select(NAMES), select(SPECIFIERS)), 0..4).prop_map(
+                |requirements| {
+                    let mut seen = Vec::new();
+                    requirements
+                        .into_iter()
+                        .filter(|(name, _)| {
+                            let name = PackageName::from_str(name).unwrap();
+                            let unseen = !seen.contains(&name);
+                            seen.push(name);
+                            unseen
+                        })
+                        .map(|(name, specifier)| format!("{name}{specifier}"))
+                        .collect()
+                },
+            )
+        }
+
+        /// Render a document with the given initial requirements, surrounded by unrelated content.
+        fn document(dependencies: &[String], dev: &[String]) -> String {
+            let array = |requirements: &[String]| {
+                requirements
+                    .iter()
+                    .fold(String::new(), |mut array, requirement| {
+                        let _ = write!(array, "\n    \"{requirement}\", # {requirement}");
+                        array
+                    })
+            };
+            format!(
+                "# A project.\n[project]\nname = \"albatross\"\nversion = \"0.1.0\" # The version.\ndependencies = [{}\n] # Dependencies.\nreadme = \"README.md\"\n\n[dependency-groups]\ndev = [{}\n]\n\n[tool.other]\nkey = \"value\" # A comment.\n",
+                array(dependencies),
+                array(dev)
+            )
+        }
+
+        fn dependency_type(group: bool) -> DependencyType {
+            if group {
+                DependencyType::Group(GroupName::from_str("dev").unwrap())
+            } else {
+                DependencyType::Production
+            }
+        }
+
+        /// Returns the requirements of the section, as sorted strings.
+        fn listed(pyproject: &PyProjectTomlMut, group: bool) -> Vec<String> {
+            let mut requirements = pyproject
+                .dependencies(&dependency_type(group))
+                .unwrap()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            requirements.sort();
+            requirements
+        }
+
+        /// Apply the operations, returning the expected requirements of each section.
+        fn apply(
+            pyproject: &mut PyProjectTomlMut,
+            initial: [&[String]; 2],
+            operations: &[Operation],
+        ) -> [BTreeMap<PackageName, Requirement>; 2] {
+            let mut expected = initial.map(|requirements| {
+                requirements
+                    .iter()
+                    .map(|requirement| {
+                        let requirement = Requirement::from_str(requirement).unwrap();
+                        (requirement.name.clone(), requirement)
+                    })
+                    .collect::<BTreeMap<_, _>>()
+            });
+            for operation in operations {
+                match operation {
+                    Operation::Add {
+                        group,
+                        name,
+                        specifier,
+                    } => {
+                        let requirement =
+                            Requirement::from_str(&format!("{name}{specifier}")).unwrap();
+                        if *group {
+                            let group = GroupName::from_str("dev").unwrap();
+                            pyproject
+                                .add_dependency_group_requirement(&group, &requirement, None, false)
+                                .unwrap();
+                        } else {
+                            pyproject.add_dependency(&requirement, None, false).unwrap();
+                        }
+
+                        // An existing requirement only retains its specifier if none is given.
+                        let expected = &mut expected[usize::from(*group)];
+                        match expected.get_mut(&requirement.name) {
+                            Some(_) if specifier.is_empty() => {}
+                            Some(existing) => *existing = requirement,
+                            None => {
+                                expected.insert(requirement.name.clone(), requirement);
+                            }
+                        }
+                    }
+                    Operation::Remove { group, name } => {
+                        let name = PackageName::from_str(name).unwrap();
+                        if *group {
+                            let group = GroupName::from_str("dev").unwrap();
+                            pyproject
+                                .remove_dependency_group_requirement(&name, &group)
+                                .unwrap();
+                        } else {
+                            pyproject.remove_dependency(&name).unwrap();
+                        }
+                        expected[usize::from(*group)].remove(&name);
+                    }
+                }
+            }
+            expected
+        }
+
+        /// Returns the contents of the document outside the edited arrays.
+        fn untouched(contents: &str) -> Vec<String> {
+            let document = toml_edit::Document::parse(contents).unwrap();
+            let span =
+                |table: &str, key: &str| -> Range<usize> { document[table][key].span().unwrap() };
+            let dependencies = span("project", "dependencies");
+            let dev = span("dependency-groups", "dev");
+            vec![
+                contents[..dependencies.start].to_string(),
+                contents[dependencies.end..dev.start].to_string(),
+                contents[dev.end..].to_string(),
+            ]
+        }
+
+        proptest! {
+            #[test]
+            fn edits_match_model(
+                dependencies in requirements(),
+                dev in requirements(),
+                operations in proptest::collection::vec(operation(), 0..12),
+            ) {
+                let source = document(&dependencies, &dev);
+                let mut pyproject =
+                    PyProjectTomlMut::from_toml(&source, DependencyTarget::PyProjectToml).unwrap();
+                let expected = apply(&mut pyproject, [&dependencies, &dev], &operations);
+
+                // The document remains valid.
+                let contents = pyproject.to_string();
+                prop_assert!(PyProjectToml::from_string(contents.clone(), "pyproject.toml").is_ok());
+
+                // Each section contains exactly the surviving requirements, without duplicates.
+                for group in [false, true] {
+                    let mut expected = expected[usize::from(group)]
+                        .values()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>();
+                    expected.sort();
+                    prop_assert_eq!(listed(&pyproject, group), expected);
+                }
+
+                // Saving and reloading is a fixed point.
+                {
+                    let temp_dir = tempfile::tempdir().unwrap();
+                    let path = temp_dir.path().join("pyproject.toml");
+                    pyproject.save_to(&path).unwrap();
+                    let mut reloaded =
+                        PyProjectTomlMut::load(&path, DependencyTarget::PyProjectToml).unwrap();
+                    prop_assert_eq!(reloaded.to_string(), contents);
+                    for group in [false, true] {
+                        prop_assert_eq!(listed(&reloaded, group), listed(&pyproject, group));
+                    }
+                    prop_assert_eq!(reloaded.save().unwrap(), SaveOutcome::Unchanged);
+                }
+            }
+
+            #[test]
+            fn edits_are_local(
+                dependencies in requirements(),
+                dev in requirements(),
+                operations in proptest::collection::vec(operation(), 0..12),
+            ) {
+                let source = document(&dependencies, &dev);
+                let mut pyproject =
+                    PyProjectTomlMut::from_toml(&source, DependencyTarget::PyProjectToml).unwrap();
+                apply(&mut pyproject, [&dependencies, &dev], &operations);
+
+                prop_assert_eq!(untouched(&pyproject.to_string()), untouched(&source));
+            }
+        }
+    }
 }
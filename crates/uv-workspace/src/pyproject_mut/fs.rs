@@ -0,0 +1,518 @@
+//! Reading and writing a [`PyProjectTomlMut`] from and to the filesystem.
+
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use filetime::FileTime;
+use tracing::debug;
+
+use uv_cache_key::hash_digest;
+use uv_fs::{LockedFile, LockedFileMode, Simplified};
+use uv_warnings::warn_user;
+
+use crate::pyproject_mut::{DependencyTarget, Error, PyProjectTomlMut};
+
+/// The file a [`PyProjectTomlMut`] was read from, and its state at the time.
+pub(super) struct Origin {
+    path: PathBuf,
+    fingerprint: Fingerprint,
+}
+
+/// A fingerprint of a file's contents, used to detect modifications by other processes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Fingerprint {
+    len: u64,
+    modified: Option<SystemTime>,
+    digest: String,
+}
+
+impl Fingerprint {
+    fn new(contents: &[u8], metadata: &std::fs::Metadata) -> Self {
+        Self {
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+            digest: hash_digest(&contents),
+        }
+    }
+
+    /// Returns `true` if the file at the given path still matches the fingerprint.
+    ///
+    /// If the size and modification time are unchanged, the contents are assumed to be unchanged;
+    /// otherwise, the contents are compared by hash.
+    fn matches(&self, path: &Path) -> Result<bool, std::io::Error> {
+        let metadata = match fs_err::metadata(path) {
+            Ok(metadata) => metadata,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+            Err(err) => return Err(err),
+        };
+        if metadata.len() == self.len
+            && self.modified.is_some()
+            && metadata.modified().ok() == self.modified
+        {
+            return Ok(true);
+        }
+        let contents = fs_err::read(path)?;
+        Ok(hash_digest(&contents.as_slice()) == self.digest)
+    }
+}
+
+/// A [`PyProjectTomlMut`] that was loaded while holding an advisory lock on its file.
+///
+/// The lock is held for the lifetime of the guard, and released once the guard is saved or
+/// dropped. Since [`PyProjectTomlMut::save`] replaces the file via a rename, the lock is taken on a
+/// sidecar file (e.g., `.pyproject.toml.lock`), rather than on the file itself.
+///
+/// The lock is an operating system lock, which is released when the holding process exits, even if
+/// it's killed; a lockfile left behind by such a process can't block other processes.
+pub struct LockedPyProjectTomlMut {
+    pyproject: PyProjectTomlMut,
+    path: PathBuf,
+    _lock: LockedFile,
+}
+
+impl LockedPyProjectTomlMut {
+    /// The path to the locked file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Write the document back to the locked file, then release the lock.
+    pub fn save(mut self) -> Result<SaveOutcome, Error> {
+        self.pyproject.save()
+    }
+}
+
+impl Deref for LockedPyProjectTomlMut {
+    type Target = PyProjectTomlMut;
+
+    fn deref(&self) -> &Self::Target {
+        &self.pyproject
+    }
+}
+
+impl DerefMut for LockedPyProjectTomlMut {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.pyproject
+    }
+}
+
+/// Returns the path to the sidecar lockfile for the given file.
+fn lock_path(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{file_name}.lock"))
+}
+
+/// The result of saving a [`PyProjectTomlMut`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SaveOutcome {
+    /// The file was written.
+    Written,
+    /// The file already matched the document, and was left untouched.
+    Unchanged,
+}
+
+/// Options for [`PyProjectTomlMut::save_with`] and [`PyProjectTomlMut::save_to_with`].
+#[derive(Debug, Clone, Default)]
+pub struct SaveOptions {
+    /// Back up the existing file before replacing it.
+    pub backup: Option<BackupStyle>,
+    /// Overwrite the file even if it was modified since it was loaded.
+    pub force: bool,
+    /// Flush the file and its directory to disk, such that the save survives a power loss.
+    ///
+    /// This is off by default, since flushing is slow on some filesystems.
+    pub durable: bool,
+}
+
+/// How to name the backup of a file that's about to be replaced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BackupStyle {
+    /// Append a fixed suffix to the file name (e.g., `pyproject.toml.bak`), replacing any existing
+    /// backup.
+    Suffix(String),
+    /// Append a number to the file name (e.g., `pyproject.toml.bak.1`), where `1` is the most
+    /// recent backup, keeping at most `keep` backups.
+    Numbered { keep: usize },
+}
+
+impl PyProjectTomlMut {
+    /// Read the file at the given path while holding an exclusive advisory lock on it, waiting for
+    /// any other process to release its lock.
+    pub async fn load_locked(
+        path: impl AsRef<Path>,
+        target: DependencyTarget,
+    ) -> Result<LockedPyProjectTomlMut, Error> {
+        let path = path.as_ref();
+        let lock = LockedFile::acquire(
+            lock_path(path),
+            LockedFileMode::Exclusive,
+            path.user_display(),
+        )
+        .await?;
+        Self::from_locked(path, target, lock)
+    }
+
+    /// Read the file at the given path while holding an exclusive advisory lock on it.
+    ///
+    /// Returns [`Error::Locked`] if another process holds the lock, rather than waiting.
+    pub fn try_load_locked(
+        path: impl AsRef<Path>,
+        target: DependencyTarget,
+    ) -> Result<LockedPyProjectTomlMut, Error> {
+        let path = path.as_ref();
+        let lock = LockedFile::acquire_no_wait(
+            lock_path(path),
+            LockedFileMode::Exclusive,
+            path.user_display(),
+        )
+        .ok_or_else(|| Error::Locked(path.to_path_buf()))?;
+        Self::from_locked(path, target, lock)
+    }
+
+    /// Read the file at the given path while holding an exclusive advisory lock on it, waiting at
+    /// most `timeout` for any other process to release its lock.
+    ///
+    /// Returns [`Error::Locked`] if the lock couldn't be acquired in time.
+    pub async fn load_locked_with_timeout(
+        path: impl AsRef<Path>,
+        target: DependencyTarget,
+        timeout: Duration,
+    ) -> Result<LockedPyProjectTomlMut, Error> {
+        let path = path.as_ref();
+        let lock = tokio::time::timeout(
+            timeout,
+            LockedFile::acquire(
+                lock_path(path),
+                LockedFileMode::Exclusive,
+                path.user_display(),
+            ),
+        )
+        .await
+        .map_err(|_| Error::Locked(path.to_path_buf()))??;
+        Self::from_locked(path, target, lock)
+    }
+
+    /// Read the file at the given path, once the lock has been acquired.
+    fn from_locked(
+        path: &Path,
+        target: DependencyTarget,
+        lock: LockedFile,
+    ) -> Result<LockedPyProjectTomlMut, Error> {
+        Ok(LockedPyProjectTomlMut {
+            pyproject: Self::load(path, target)?,
+            path: path.to_path_buf(),
+            _lock: lock,
+        })
+    }
+
+    /// Read the file at the given path.
+    ///
+    /// The state of the file is recorded, such that [`PyProjectTomlMut::save`] can detect whether
+    /// the file was modified by another process in the meantime.
+    pub fn load(path: impl AsRef<Path>, target: DependencyTarget) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let (contents, origin) = read_origin(path)?;
+        let start = Instant::now();
+        let mut pyproject = Self::from_toml(&contents, target)?;
+        debug!(
+            path = %path.user_display(),
+            bytes = contents.len(),
+            parse_duration = ?start.elapsed(),
+            "Loaded `pyproject.toml`"
+        );
+        pyproject.origin = Some(origin);
+        Ok(pyproject)
+    }
+
+    /// Re-read the document from the file it was loaded from, discarding any modifications.
+    ///
+    /// Modifications aren't replayed onto the re-read document; it's up to the caller to reapply
+    /// them, if desired.
+    pub fn reload(&mut self) -> Result<(), Error> {
+        let path = self
+            .origin
+            .as_ref()
+            .map(|origin| origin.path.clone())
+            .ok_or(Error::NotLoaded)?;
+        let (contents, origin) = read_origin(&path)?;
+        self.doc = contents.parse().map_err(Box::new)?;
+        self.original = self.doc.clone();
+        self.contents = contents;
+        self.clear_edits();
+        self.dirty = false;
+        self.modified = false;
+        self.origin = Some(origin);
+        Ok(())
+    }
+
+    /// The path of the file the document was read from, if any.
+    pub fn path(&self) -> Option<&Path> {
+        self.origin.as_ref().map(|origin| origin.path.as_path())
+    }
+
+    /// Write the `pyproject.toml` back to the file it was read from.
+    ///
+    /// Returns [`Error::NotLoaded`] if the document wasn't read from a file. See
+    /// [`PyProjectTomlMut::save_to`] for details.
+    pub fn save(&mut self) -> Result<SaveOutcome, Error> {
+        self.save_with(&SaveOptions::default())
+    }
+
+    /// Write the `pyproject.toml` back to the file it was read from, with the given
+    /// [`SaveOptions`].
+    pub fn save_with(&mut self, options: &SaveOptions) -> Result<SaveOutcome, Error> {
+        let path = self.path().ok_or(Error::NotLoaded)?.to_path_buf();
+        self.save_to_with(path, options)
+    }
+
+    /// Write the `pyproject.toml` to the given path.
+    ///
+    /// The contents are first written to a temporary file in the same directory, which is then
+    /// renamed over the destination, such that readers never observe a partially written file. If
+    /// any step fails, the existing file is left untouched and the temporary file is removed.
+    ///
+    /// The permissions of the existing file, if any, are preserved.
+    ///
+    /// If the document wasn't modified since it was loaded, or if the serialized document is
+    /// identical to the existing file, the file isn't written, such that its modification time is
+    /// preserved.
+    pub fn save_to(&mut self, path: impl AsRef<Path>) -> Result<SaveOutcome, Error> {
+        self.save_to_with(path, &SaveOptions::default())
+    }
+
+    /// Write the `pyproject.toml` to the given path, as in [`PyProjectTomlMut::save_to`], with the
+    /// given [`SaveOptions`].
+    pub fn save_to_with(
+        &mut self,
+        path: impl AsRef<Path>,
+        options: &SaveOptions,
+    ) -> Result<SaveOutcome, Error> {
+        let path = path.as_ref();
+        let unmodified = match self.path() {
+            Some(origin) => origin == path,
+            // A document that wasn't loaded from disk says nothing about the file at the path.
+            None => false,
+        };
+        if !self.dirty && unmodified {
+            debug!(
+                path = %path.user_display(),
+                reason = "unmodified",
+                "Skipped saving `pyproject.toml`"
+            );
+            return Ok(SaveOutcome::Unchanged);
+        }
+
+        let contents = self.to_bytes();
+        match fs_err::read(path) {
+            Ok(existing) if existing == contents => {
+                debug!(
+                    path = %path.user_display(),
+                    reason = "unchanged",
+                    "Skipped saving `pyproject.toml`"
+                );
+                self.mark_saved(path, &contents);
+                return Ok(SaveOutcome::Unchanged);
+            }
+            Ok(_) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(read_error(path, err)),
+        }
+
+        // Unless forced, refuse to overwrite modifications made since the file was loaded.
+        if !options.force
+            && let Some(origin) = &self.origin
+            && origin.path == path
+            && !origin
+                .fingerprint
+                .matches(path)
+                .map_err(|err| read_error(path, err))?
+        {
+            return Err(Error::ConcurrentModification {
+                path: path.to_path_buf(),
+            });
+        }
+
+        // The file would be replaced regardless of its permissions, since the directory is
+        // writable; respect them instead.
+        if fs_err::metadata(path).is_ok_and(|metadata| metadata.permissions().readonly()) {
+            return Err(Error::ReadOnly {
+                path: path.to_path_buf(),
+                is_dir: false,
+            });
+        }
+
+        // Back up the existing file before it's replaced, such that a failure after this point
+        // can't lose its contents. A failed backup doesn't prevent the save.
+        if let Some(style) = &options.backup {
+            if let Err(err) = backup(path, style) {
+                warn_user!(
+                    "Failed to back up `{}` before writing: {err}",
+                    path.user_display()
+                );
+            }
+        }
+
+        uv_fs::overwrite_atomic_sync(path, &contents, options.durable)
+            .map_err(|err| write_error(path, err))?;
+        debug!(
+            path = %path.user_display(),
+            bytes = contents.len(),
+            durable = options.durable,
+            "Saved `pyproject.toml`"
+        );
+        self.mark_saved(path, &contents);
+        Ok(SaveOutcome::Written)
+    }
+
+    /// Mark the document as saved to `path`, with the given contents.
+    ///
+    /// If the document was saved to the file it was loaded from, the recorded state of that file
+    /// is updated, and the document becomes the baseline for subsequent edits.
+    fn mark_saved(&mut self, path: &Path, contents: &[u8]) {
+        self.dirty = false;
+        self.modified = false;
+        match &mut self.origin {
+            Some(origin) if origin.path != path => return,
+            Some(origin) => {
+                if let Ok(metadata) = fs_err::metadata(path) {
+                    origin.fingerprint = Fingerprint::new(contents, &metadata);
+                }
+            }
+            None => {}
+        }
+        self.original = self.doc.clone();
+        self.contents = String::from_utf8_lossy(contents).into_owned();
+        self.clear_edits();
+    }
+
+    /// Write the serialized document to the given writer.
+    ///
+    /// The output is identical to that written by [`PyProjectTomlMut::save`].
+    pub fn write_to(&self, mut writer: impl std::io::Write) -> Result<(), Error> {
+        writer.write_all(&self.to_bytes()).map_err(Error::WriteTo)
+    }
+}
+
+/// Returns the message for [`Error::ReadOnly`].
+pub(super) fn read_only_message(path: &Path, is_dir: bool) -> String {
+    if is_dir {
+        format!(
+            "Failed to write to `{}`, since the directory is read-only; it may be on a read-only filesystem, or owned by another user",
+            path.user_display()
+        )
+    } else {
+        format!(
+            "Failed to write `{}`, since the file is read-only; make it writable to allow it to be edited",
+            path.user_display()
+        )
+    }
+}
+
+/// Convert an error reading the file at `path` into an [`Error`].
+fn read_error(path: &Path, err: std::io::Error) -> Error {
+    if err.kind() == std::io::ErrorKind::PermissionDenied {
+        Error::Unreadable(path.to_path_buf())
+    } else {
+        Error::Read(path.to_path_buf(), err)
+    }
+}
+
+/// Convert an error writing the file at `path` into an [`Error`].
+///
+/// Since the file is written by creating a temporary file in its directory and renaming it over
+/// the file, a permission error indicates that the directory is read-only, unless the file itself
+/// is.
+fn write_error(path: &Path, err: std::io::Error) -> Error {
+    if err.kind() != std::io::ErrorKind::PermissionDenied {
+        return Error::Write(path.to_path_buf(), err);
+    }
+    if fs_err::metadata(path).is_ok_and(|metadata| metadata.permissions().readonly()) {
+        return Error::ReadOnly {
+            path: path.to_path_buf(),
+            is_dir: false,
+        };
+    }
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    Error::ReadOnly {
+        path: parent.to_path_buf(),
+        is_dir: true,
+    }
+}
+
+/// Read the file at `path`, along with its [`Origin`].
+pub(super) fn read_origin(path: &Path) -> Result<(String, Origin), Error> {
+    let read = || -> Result<_, std::io::Error> {
+        // Read the metadata first, such that a concurrent modification is detected, rather than
+        // attributed to the contents that were read.
+        let metadata = fs_err::metadata(path)?;
+        let contents = fs_err::read_to_string(path)?;
+        Ok((contents, metadata))
+    };
+    let (contents, metadata) = read().map_err(|err| read_error(path, err))?;
+    let origin = Origin {
+        path: path.to_path_buf(),
+        fingerprint: Fingerprint::new(contents.as_bytes(), &metadata),
+    };
+    Ok((contents, origin))
+}
+
+/// Back up the file at `path`, if it exists.
+///
+/// The backup is a hard link to the existing file where possible, and a copy otherwise. Either
+/// way, the backup retains the permissions and modification time of the original.
+fn backup(path: &Path, style: &BackupStyle) -> Result<(), std::io::Error> {
+    if !path.exists() {
+        return Ok(());
+    }
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    let backup_path = |suffix: &str| path.with_file_name(format!("{file_name}{suffix}"));
+
+    let target = match style {
+        BackupStyle::Suffix(suffix) => backup_path(suffix),
+        BackupStyle::Numbered { keep: 0 } => return Ok(()),
+        BackupStyle::Numbered { keep } => {
+            // Rotate the existing backups, dropping the oldest.
+            remove_if_exists(&backup_path(&format!(".bak.{keep}")))?;
+            for number in (1..*keep).rev() {
+                let source = backup_path(&format!(".bak.{number}"));
+                if source.exists() {
+                    fs_err::rename(&source, backup_path(&format!(".bak.{}", number + 1)))?;
+                }
+            }
+            backup_path(".bak.1")
+        }
+    };
+
+    remove_if_exists(&target)?;
+    debug!(
+        "Backing up `{}` to `{}`",
+        path.user_display(),
+        target.user_display()
+    );
+    if fs_err::hard_link(path, &target).is_ok() {
+        return Ok(());
+    }
+    fs_err::copy(path, &target)?;
+    let metadata = fs_err::metadata(path)?;
+    filetime::set_file_mtime(&target, FileTime::from_last_modification_time(&metadata))?;
+    Ok(())
+}
+
+/// Remove the file at `path`, if it exists.
+fn remove_if_exists(path: &Path) -> Result<(), std::io::Error> {
+    match fs_err::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err),
+    }
+}
@@ -0,0 +1,300 @@
+//! The log of edits applied to a [`PyProjectTomlMut`], and reverting them.
+
+use std::fmt::{Display, Formatter};
+
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::pyproject_mut::{PyProjectTomlMut, canonical_requirement};
+
+/// An edit that changed a [`PyProjectTomlMut`].
+///
+/// Requirements and sources are recorded as written to the document, with any credentials
+/// redacted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum PyProjectEdit {
+    /// A requirement was added to, or replaced in, a dependency section.
+    AddDependency {
+        /// The section, e.g., `project.dependencies`.
+        location: String,
+        requirement: String,
+        /// The requirement that was replaced, if any.
+        replaced: Option<String>,
+        /// The source that was added to `tool.uv.sources`, if any.
+        source: Option<String>,
+    },
+    /// The requirements for a package were removed from a dependency section.
+    RemoveDependency {
+        /// The section, e.g., `project.dependencies`.
+        location: String,
+        removed: Vec<String>,
+        /// The source that was removed from `tool.uv.sources`, if any.
+        source: Option<String>,
+    },
+    /// An empty dependency section was created, e.g., `dependency-groups.dev`.
+    CreateSection { location: String },
+    /// A member was added to `tool.uv.workspace.members`.
+    AddWorkspaceMember { member: String },
+    /// An index was added to `tool.uv.index`, or moved to the top.
+    AddIndex { name: Option<String>, url: String },
+    /// A key was set, e.g., `project.version`.
+    SetKey {
+        /// The dotted path of the key.
+        path: String,
+        old: Option<String>,
+        new: Option<String>,
+    },
+}
+
+impl Display for PyProjectEdit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AddDependency {
+                location,
+                requirement,
+                replaced: None,
+                ..
+            } => write!(f, "Add `{requirement}` to `{location}`"),
+            Self::AddDependency {
+                location,
+                requirement,
+                replaced: Some(replaced),
+                ..
+            } => write!(
+                f,
+                "Replace `{replaced}` with `{requirement}` in `{location}`"
+            ),
+            Self::RemoveDependency {
+                location, removed, ..
+            } => write!(
+                f,
+                "Remove {} from `{location}`",
+                removed.iter().map(|entry| format!("`{entry}`")).join(", ")
+            ),
+            Self::CreateSection { location } => write!(f, "Create `{location}`"),
+            Self::AddWorkspaceMember { member } => {
+                write!(f, "Add `{member}` to `tool.uv.workspace.members`")
+            }
+            Self::AddIndex { url, .. } => write!(f, "Add `{url}` to `tool.uv.index`"),
+            Self::SetKey {
+                path,
+                new: Some(new),
+                ..
+            } => write!(f, "Set `{path}` to `{new}`"),
+            Self::SetKey {
+                path, new: None, ..
+            } => write!(f, "Remove `{path}`"),
+        }
+    }
+}
+
+impl PyProjectTomlMut {
+    /// The edits that changed the document since it was loaded or last saved, in the order they
+    /// were applied.
+    ///
+    /// Edits that left the document as-is (e.g., adding a requirement that's already present)
+    /// aren't included.
+    pub fn edits(&self) -> &[PyProjectEdit] {
+        &self.log
+    }
+
+    /// Remember the state of the document before each edit, such that edits can be reverted one
+    /// at a time with [`PyProjectTomlMut::revert_last`].
+    ///
+    /// Each edit copies the document, so history is disabled by default.
+    #[must_use]
+    pub fn with_history(mut self) -> Self {
+        self.history = true;
+        self
+    }
+
+    /// Revert all edits, restoring the document as it was loaded or last saved.
+    pub fn revert(&mut self) {
+        self.doc = self.original.clone();
+        self.clear_edits();
+        self.dirty = false;
+        self.modified = false;
+    }
+
+    /// Revert the last edit in [`PyProjectTomlMut::edits`], restoring the document as it was
+    /// before the edit.
+    ///
+    /// Requirements added with [`PyProjectTomlMut::add_dependencies`] are reverted together.
+    ///
+    /// Requires [`PyProjectTomlMut::with_history`]; edits applied without history can only be
+    /// reverted all at once, with [`PyProjectTomlMut::revert`].
+    ///
+    /// Returns the reverted edits, or an empty vector if there are no edits to revert.
+    pub fn revert_last(&mut self) -> Vec<PyProjectEdit> {
+        let Some(checkpoint) = self.undo.pop() else {
+            return Vec::new();
+        };
+        self.doc = checkpoint.doc;
+        self.recorded.truncate(checkpoint.recorded);
+        self.checkpoint = None;
+        let reverted = self.log.split_off(checkpoint.log);
+        self.modified = !self.log.is_empty();
+        reverted
+    }
+}
+
+/// Returns the given dependency entry, with any credentials redacted.
+pub(super) fn redact(entry: &str) -> String {
+    canonical_requirement(entry).unwrap_or_else(|| entry.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use anyhow::Result;
+    use insta::{assert_json_snapshot, assert_snapshot};
+
+    use uv_normalize::{GroupName, PackageName};
+    use uv_pep440::Version;
+    use uv_pep508::Requirement;
+
+    use crate::pyproject_mut::{DependencyTarget, PyProjectTomlMut, SaveOutcome};
+
+    const PYPROJECT: &str = r#"[project]
+name = "albatross"
+version = "1.0.0"
+dependencies = [
+    "anyio>=3", # comment
+    "flask",
+]
+"#;
+
+    #[test]
+    fn edits() -> Result<()> {
+        let mut pyproject =
+            PyProjectTomlMut::from_toml(PYPROJECT, DependencyTarget::PyProjectToml)?;
+        pyproject.add_dependency(&Requirement::from_str("anyio>=4")?, None, false)?;
+        pyproject.add_dependency(&Requirement::from_str("tqdm")?, None, false)?;
+        pyproject.remove_dependency(&PackageName::from_str("flask")?)?;
+        pyproject.ensure_dependency_group(&GroupName::from_str("dev")?)?;
+        pyproject.set_version(&Version::from_str("1.1.0")?)?;
+
+        // No-op edits aren't logged.
+        pyproject.add_dependency(&Requirement::from_str("tqdm")?, None, false)?;
+        pyproject.set_version(&Version::from_str("1.1.0")?)?;
+
+        assert_json_snapshot!(pyproject.edits(), @r#"
+        [
+          {
+            "kind": "add-dependency",
+            "location": "project.dependencies",
+            "requirement": "anyio>=4",
+            "replaced": "anyio>=3",
+            "source": null
+          },
+          {
+            "kind": "add-dependency",
+            "location": "project.dependencies",
+            "requirement": "tqdm",
+            "replaced": null,
+            "source": null
+          },
+          {
+            "kind": "remove-dependency",
+            "location": "project.dependencies",
+            "removed": [
+              "flask"
+            ],
+            "source": null
+          },
+          {
+            "kind": "create-section",
+            "location": "dependency-groups.dev"
+          },
+          {
+            "kind": "set-key",
+            "path": "project.version",
+            "old": "1.0.0",
+            "new": "1.1.0"
+          }
+        ]
+        "#);
+
+        let edits = pyproject
+            .edits()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_snapshot!(edits, @r"
+        Replace `anyio>=3` with `anyio>=4` in `project.dependencies`
+        Add `tqdm` to `project.dependencies`
+        Remove `flask` from `project.dependencies`
+        Create `dependency-groups.dev`
+        Set `project.version` to `1.1.0`
+        ");
+
+        Ok(())
+    }
+
+    #[test]
+    fn revert_last() -> Result<()> {
+        // Without history, edits can't be reverted one at a time.
+        let mut pyproject =
+            PyProjectTomlMut::from_toml(PYPROJECT, DependencyTarget::PyProjectToml)?;
+        pyproject.add_dependency(&Requirement::from_str("tqdm")?, None, false)?;
+        assert!(pyproject.revert_last().is_empty());
+        assert_eq!(pyproject.edits().len(), 1);
+
+        let mut pyproject =
+            PyProjectTomlMut::from_toml(PYPROJECT, DependencyTarget::PyProjectToml)?.with_history();
+        pyproject.add_dependency(&Requirement::from_str("anyio>=4")?, None, false)?;
+        let replaced = pyproject.to_string();
+        pyproject.add_dependency(&Requirement::from_str("tqdm")?, None, false)?;
+        pyproject.remove_dependency(&PackageName::from_str("flask")?)?;
+
+        // Edits are reverted in reverse order.
+        assert!(matches!(
+            pyproject.revert_last().as_slice(),
+            [super::PyProjectEdit::RemoveDependency { .. }]
+        ));
+        assert!(matches!(
+            pyproject.revert_last().as_slice(),
+            [super::PyProjectEdit::AddDependency { .. }]
+        ));
+        assert_eq!(pyproject.to_string(), replaced);
+        assert_eq!(pyproject.edits().len(), 1);
+        assert!(pyproject.is_modified());
+
+        assert_eq!(pyproject.revert_last().len(), 1);
+        assert_eq!(pyproject.to_string(), PYPROJECT);
+        assert!(!pyproject.is_modified());
+        assert!(pyproject.revert_last().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn revert() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("pyproject.toml");
+        fs_err::write(&path, PYPROJECT)?;
+
+        let mut pyproject = PyProjectTomlMut::load(&path, DependencyTarget::PyProjectToml)?;
+        pyproject.add_dependency(&Requirement::from_str("tqdm")?, None, false)?;
+        pyproject.set_version(&Version::from_str("1.1.0")?)?;
+        pyproject.revert();
+        assert_eq!(pyproject.to_string(), PYPROJECT);
+        assert!(pyproject.edits().is_empty());
+        assert!(!pyproject.is_dirty());
+
+        // Saving clears the log, and the saved document becomes the baseline for reverting.
+        pyproject.add_dependency(&Requirement::from_str("tqdm")?, None, false)?;
+        assert_eq!(pyproject.save()?, SaveOutcome::Written);
+        assert!(pyproject.edits().is_empty());
+        let saved = pyproject.to_string();
+
+        pyproject.set_version(&Version::from_str("1.1.0")?)?;
+        pyproject.revert();
+        assert_eq!(pyproject.to_string(), saved);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,374 @@
+//! Merging the edits applied to a [`PyProjectTomlMut`] onto a concurrently modified file.
+
+use itertools::Itertools;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use toml_edit::DocumentMut;
+
+use uv_normalize::PackageName;
+
+use crate::pyproject::DependencyType;
+use crate::pyproject_mut::fs::read_origin;
+use crate::pyproject_mut::{
+    DependencyTarget, Error, PyProjectTomlMut, RecordedEdit, dependency_array, find_dependencies,
+    section,
+};
+
+/// The result of [`PyProjectTomlMut::merge_with_disk`].
+#[derive(Debug, Default)]
+pub struct MergeReport {
+    /// The number of edits that were re-applied onto the file.
+    pub applied: usize,
+    /// The entries that were modified both by the edits and by the file.
+    ///
+    /// The edits to these entries were not re-applied; the entries retain their contents from the
+    /// file.
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl MergeReport {
+    /// Returns `true` if all edits were re-applied without conflicts.
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// An entry that was modified both by the edits and by the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeConflict {
+    /// The entries for a dependency in a given section.
+    Dependency {
+        dependency_type: DependencyType,
+        name: PackageName,
+        /// The entries, after applying the edits.
+        ours: Vec<String>,
+        /// The entries in the file.
+        theirs: Vec<String>,
+    },
+    /// The `project.version` field.
+    Version {
+        /// The version, after applying the edits.
+        ours: Option<String>,
+        /// The version in the file.
+        theirs: Option<String>,
+    },
+    /// The `project.requires-python` field.
+    RequiresPython {
+        /// The specifiers, after applying the edits.
+        ours: Option<String>,
+        /// The specifiers in the file.
+        theirs: Option<String>,
+    },
+}
+
+impl Display for MergeConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fn entries(entries: &[String]) -> String {
+            if entries.is_empty() {
+                "(none)".to_string()
+            } else {
+                entries.iter().map(|entry| format!("`{entry}`")).join(", ")
+            }
+        }
+
+        match self {
+            Self::Dependency {
+                dependency_type,
+                name,
+                ours,
+                theirs,
+            } => write!(
+                f,
+                "`{name}` in `{}`: ours {}, theirs {}",
+                section(dependency_type),
+                entries(ours),
+                entries(theirs)
+            ),
+            Self::Version { ours, theirs } => write!(
+                f,
+                "`project.version`: ours {}, theirs {}",
+                entries(ours.as_slice()),
+                entries(theirs.as_slice())
+            ),
+            Self::RequiresPython { ours, theirs } => write!(
+                f,
+                "`project.requires-python`: ours {}, theirs {}",
+                entries(ours.as_slice()),
+                entries(theirs.as_slice())
+            ),
+        }
+    }
+}
+
+/// An entry in the document that's modified by a [`RecordedEdit`].
+#[derive(Debug, PartialEq, Eq)]
+enum Key {
+    Dependency(DependencyType, PackageName),
+    Version,
+    RequiresPython,
+}
+
+impl RecordedEdit {
+    /// Returns the entry modified by the edit, if the edit can conflict with other modifications.
+    ///
+    /// Edits that only ever add entries (e.g., an index or a workspace member) are always replayed.
+    fn key(&self) -> Option<Key> {
+        match self {
+            Self::AddDependency {
+                dependency_type,
+                requirement,
+                ..
+            } => Some(Key::Dependency(
+                dependency_type.clone(),
+                requirement.name.clone(),
+            )),
+            Self::RemoveDependency {
+                dependency_type,
+                name,
+            } => Some(Key::Dependency(dependency_type.clone(), name.clone())),
+            Self::SetVersion(_) => Some(Key::Version),
+            Self::SetRequiresPython(_) => Some(Key::RequiresPython),
+            Self::AddWorkspace(_)
+            | Self::AddIndex(_)
+            | Self::EnsureOptionalDependency(_)
+            | Self::EnsureDependencyGroup(_) => None,
+        }
+    }
+}
+
+impl PyProjectTomlMut {
+    /// Re-read the file at the given path, and re-apply the edits made since the document was
+    /// loaded or last saved on top of it.
+    ///
+    /// An edit conflicts with the file if the file modified the same entry (e.g., the same
+    /// dependency in the same section) since the document was loaded. Conflicting edits are never
+    /// applied; instead, both versions of the entry are returned in the [`MergeReport`], and the
+    /// entry retains its contents from the file.
+    pub fn merge_with_disk(&mut self, path: &Path) -> Result<MergeReport, Error> {
+        let (contents, origin) = read_origin(path)?;
+        let mut merged = Self::from_toml(&contents, self.target)?;
+        merged.origin = Some(origin);
+
+        let mut report = MergeReport::default();
+        let mut contested = Vec::new();
+        for edit in &self.recorded {
+            if let Some(key) = edit.key() {
+                if contested.contains(&key) {
+                    continue;
+                }
+                if let Some(conflict) = self.conflict(&key, &merged.original) {
+                    contested.push(key);
+                    report.conflicts.push(conflict);
+                    continue;
+                }
+            }
+            merged.apply(edit)?;
+            report.applied += 1;
+        }
+
+        *self = merged;
+        Ok(report)
+    }
+
+    /// Returns the conflict on the given entry, if `theirs` modified it since the document was
+    /// loaded.
+    fn conflict(&self, key: &Key, theirs: &DocumentMut) -> Option<MergeConflict> {
+        match key {
+            Key::Dependency(dependency_type, name) => {
+                let entries =
+                    |doc: &DocumentMut| dependency_entries(doc, self.target, dependency_type, name);
+                let theirs = entries(theirs);
+                if entries(&self.original) == theirs {
+                    return None;
+                }
+                Some(MergeConflict::Dependency {
+                    dependency_type: dependency_type.clone(),
+                    name: name.clone(),
+                    ours: entries(&self.doc),
+                    theirs,
+                })
+            }
+            Key::Version => {
+                let theirs = project_str(theirs, "version");
+                if project_str(&self.original, "version") == theirs {
+                    return None;
+                }
+                Some(MergeConflict::Version {
+                    ours: project_str(&self.doc, "version"),
+                    theirs,
+                })
+            }
+            Key::RequiresPython => {
+                let theirs = project_str(theirs, "requires-python");
+                if project_str(&self.original, "requires-python") == theirs {
+                    return None;
+                }
+                Some(MergeConflict::RequiresPython {
+                    ours: project_str(&self.doc, "requires-python"),
+                    theirs,
+                })
+            }
+        }
+    }
+
+    /// Re-apply a recorded edit.
+    fn apply(&mut self, edit: &RecordedEdit) -> Result<(), Error> {
+        match edit {
+            RecordedEdit::AddWorkspace(path) => {
+                self.add_workspace(path)?;
+            }
+            RecordedEdit::AddDependency {
+                dependency_type,
+                requirement,
+                source,
+                raw,
+            } => {
+                self.add_requirement(dependency_type, requirement, source.as_deref(), *raw)?;
+            }
+            RecordedEdit::RemoveDependency {
+                dependency_type,
+                name,
+            } => {
+                self.remove_requirement(dependency_type, name)?;
+            }
+            RecordedEdit::AddIndex(index) => {
+                self.add_index(index)?;
+            }
+            RecordedEdit::EnsureOptionalDependency(extra) => {
+                self.ensure_optional_dependency(extra)?;
+            }
+            RecordedEdit::EnsureDependencyGroup(group) => {
+                self.ensure_dependency_group(group)?;
+            }
+            RecordedEdit::SetVersion(version) => {
+                self.set_version(version)?;
+            }
+            RecordedEdit::SetRequiresPython(specifiers) => {
+                self.set_requires_python(specifiers)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the raw entries for the given dependency in the given section.
+fn dependency_entries(
+    doc: &DocumentMut,
+    target: DependencyTarget,
+    dependency_type: &DependencyType,
+    name: &PackageName,
+) -> Vec<String> {
+    let Some(array) = dependency_array(doc.as_table(), target, dependency_type) else {
+        return Vec::new();
+    };
+    find_dependencies(name, None, array)
+        .into_iter()
+        .filter_map(|(index, _)| array.get(index).and_then(|entry| entry.as_str()))
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Returns the string value of the given key in the `project` table of the document, if any.
+fn project_str(doc: &DocumentMut, key: &str) -> Option<String> {
+    doc.get("project")?
+        .get(key)?
+        .as_str()
+        .map(ToString::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use anyhow::Result;
+    use insta::assert_snapshot;
+
+    use uv_pep508::Requirement;
+
+    use crate::pyproject_mut::{DependencyTarget, PyProjectTomlMut, SaveOutcome};
+
+    #[test]
+    fn merge_unrelated() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("pyproject.toml");
+        fs_err::write(
+            &path,
+            "[project]\nname = \"albatross\"\ndependencies = [\n    \"anyio>=4\",\n]\n",
+        )?;
+
+        let mut pyproject = PyProjectTomlMut::load(&path, DependencyTarget::PyProjectToml)?;
+        pyproject.add_dependency(&Requirement::from_str("tqdm>=4")?, None, false)?;
+
+        // Another tool adds an unrelated dependency in the meantime.
+        fs_err::write(
+            &path,
+            "[project]\nname = \"albatross\"\ndependencies = [\n    \"anyio>=4\",\n    \"flask\",\n]\n",
+        )?;
+        assert!(pyproject.save().is_err());
+
+        let report = pyproject.merge_with_disk(&path)?;
+        assert!(report.is_clean());
+        assert_eq!(report.applied, 1);
+        assert_snapshot!(pyproject.to_string(), @r#"
+        [project]
+        name = "albatross"
+        dependencies = [
+            "anyio>=4",
+            "flask",
+            "tqdm>=4",
+        ]
+        "#);
+
+        // The merged document can be saved over the file.
+        assert_eq!(pyproject.save()?, SaveOutcome::Written);
+
+        Ok(())
+    }
+
+    #[test]
+    fn merge_conflict() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("pyproject.toml");
+        fs_err::write(
+            &path,
+            "[project]\nname = \"albatross\"\nversion = \"1.0.0\"\ndependencies = [\n    \"anyio>=4\",\n]\n",
+        )?;
+
+        let mut pyproject = PyProjectTomlMut::load(&path, DependencyTarget::PyProjectToml)?;
+        pyproject.add_dependency(&Requirement::from_str("anyio>=4.1")?, None, false)?;
+        pyproject.add_dependency(&Requirement::from_str("tqdm>=4")?, None, false)?;
+        pyproject.set_version(&uv_pep440::Version::from_str("1.1.0")?)?;
+
+        // Another tool modifies the same dependency and the version.
+        fs_err::write(
+            &path,
+            "[project]\nname = \"albatross\"\nversion = \"2.0.0\"\ndependencies = [\n    \"anyio>=3\",\n]\n",
+        )?;
+
+        let report = pyproject.merge_with_disk(&path)?;
+        assert_eq!(report.applied, 1);
+        let conflicts = report
+            .conflicts
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_snapshot!(conflicts, @r"
+        `anyio` in `project.dependencies`: ours `anyio>=4.1`, theirs `anyio>=3`
+        `project.version`: ours `1.1.0`, theirs `2.0.0`
+        ");
+
+        // The contested entries retain their contents from the file.
+        assert_snapshot!(pyproject.to_string(), @r#"
+        [project]
+        name = "albatross"
+        version = "2.0.0"
+        dependencies = [
+            "anyio>=3",
+            "tqdm>=4",
+        ]
+        "#);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,224 @@
+//! Reporting edits to a [`PyProjectTomlMut`] as they're applied.
+
+use std::sync::{Arc, Mutex};
+
+use crate::pyproject_mut::{PyProjectEdit, PyProjectTomlMut};
+
+/// An observer of the edits applied to a [`PyProjectTomlMut`], e.g., to report progress.
+///
+/// The observer is called for each edit that changed the document, at the time it's applied;
+/// edits that left the document as-is aren't reported. [`PyProjectObserver::on_edit`] dispatches
+/// to the method for each kind of edit, all of which do nothing by default.
+pub trait PyProjectObserver: Send {
+    /// Called for each edit that changed the document.
+    fn on_edit(&mut self, edit: &PyProjectEdit) {
+        match edit {
+            PyProjectEdit::AddDependency {
+                location,
+                requirement,
+                replaced: None,
+                source,
+            } => self.on_dependency_added(location, requirement, source.as_deref()),
+            PyProjectEdit::AddDependency {
+                location,
+                requirement,
+                replaced: Some(replaced),
+                source,
+            } => self.on_dependency_replaced(location, replaced, requirement, source.as_deref()),
+            PyProjectEdit::RemoveDependency {
+                location,
+                removed,
+                source,
+            } => self.on_dependency_removed(location, removed, source.as_deref()),
+            PyProjectEdit::CreateSection { location } => self.on_section_created(location),
+            PyProjectEdit::AddWorkspaceMember { member } => self.on_workspace_member_added(member),
+            PyProjectEdit::AddIndex { name, url } => self.on_index_added(name.as_deref(), url),
+            PyProjectEdit::SetKey { path, old, new } => {
+                self.on_key_set(path, old.as_deref(), new.as_deref());
+            }
+        }
+    }
+
+    /// Called when a requirement is added to a dependency section, e.g., `project.dependencies`.
+    fn on_dependency_added(&mut self, location: &str, requirement: &str, source: Option<&str>) {
+        let _ = (location, requirement, source);
+    }
+
+    /// Called when a requirement in a dependency section is replaced, e.g., to change its
+    /// version specifiers or its source.
+    fn on_dependency_replaced(
+        &mut self,
+        location: &str,
+        replaced: &str,
+        requirement: &str,
+        source: Option<&str>,
+    ) {
+        let _ = (location, replaced, requirement, source);
+    }
+
+    /// Called when the requirements for a package are removed from a dependency section.
+    fn on_dependency_removed(&mut self, location: &str, removed: &[String], source: Option<&str>) {
+        let _ = (location, removed, source);
+    }
+
+    /// Called when an empty dependency section is created, e.g., `dependency-groups.dev`.
+    fn on_section_created(&mut self, location: &str) {
+        let _ = location;
+    }
+
+    /// Called when a member is added to `tool.uv.workspace.members`.
+    fn on_workspace_member_added(&mut self, member: &str) {
+        let _ = member;
+    }
+
+    /// Called when an index is added to `tool.uv.index`, or moved to the top.
+    fn on_index_added(&mut self, name: Option<&str>, url: &str) {
+        let _ = (name, url);
+    }
+
+    /// Called when a key is set or removed, e.g., `project.version`.
+    fn on_key_set(&mut self, path: &str, old: Option<&str>, new: Option<&str>) {
+        let _ = (path, old, new);
+    }
+}
+
+/// A [`PyProjectObserver`] that ignores all edits.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoopObserver;
+
+impl PyProjectObserver for NoopObserver {
+    fn on_edit(&mut self, _edit: &PyProjectEdit) {}
+}
+
+/// A [`PyProjectObserver`] that collects the edits it observes.
+///
+/// Clones share the same collection, so a clone can be passed to
+/// [`PyProjectTomlMut::with_observer`] while the original is used to read the edits.
+#[derive(Debug, Default, Clone)]
+pub struct CollectingObserver(Arc<Mutex<Vec<PyProjectEdit>>>);
+
+impl CollectingObserver {
+    /// The edits observed so far, in the order they were applied.
+    pub fn edits(&self) -> Vec<PyProjectEdit> {
+        self.0
+            .lock()
+            .expect("there was a panic in another thread")
+            .clone()
+    }
+}
+
+impl PyProjectObserver for CollectingObserver {
+    fn on_edit(&mut self, edit: &PyProjectEdit) {
+        self.0
+            .lock()
+            .expect("there was a panic in another thread")
+            .push(edit.clone());
+    }
+}
+
+impl PyProjectTomlMut {
+    /// Report each edit that changes the document to the given [`PyProjectObserver`], as it's
+    /// applied.
+    #[must_use]
+    pub fn with_observer(mut self, observer: Box<dyn PyProjectObserver>) -> Self {
+        self.observer = observer;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use anyhow::Result;
+
+    use uv_normalize::{GroupName, PackageName};
+    use uv_pep508::Requirement;
+
+    use crate::pyproject_mut::{DependencyTarget, PyProjectEdit, PyProjectTomlMut};
+
+    use super::{CollectingObserver, PyProjectObserver};
+
+    const PYPROJECT: &str = r#"[project]
+name = "albatross"
+version = "1.0.0"
+dependencies = [
+    "anyio>=3",
+    "flask",
+]
+"#;
+
+    #[test]
+    fn collect() -> Result<()> {
+        let observer = CollectingObserver::default();
+        let mut pyproject =
+            PyProjectTomlMut::from_toml(PYPROJECT, DependencyTarget::PyProjectToml)?
+                .with_observer(Box::new(observer.clone()));
+
+        pyproject.add_dependency(&Requirement::from_str("anyio>=4")?, None, false)?;
+        assert_eq!(observer.edits().len(), 1);
+
+        // No-op edits aren't reported.
+        pyproject.add_dependency(&Requirement::from_str("anyio>=4")?, None, false)?;
+        pyproject.remove_dependency(&PackageName::from_str("flask")?)?;
+        pyproject.ensure_dependency_group(&GroupName::from_str("dev")?)?;
+        pyproject.ensure_dependency_group(&GroupName::from_str("dev")?)?;
+
+        // The observer sees the same edits as the log, at the time they're applied.
+        assert_eq!(observer.edits(), pyproject.edits());
+
+        Ok(())
+    }
+
+    #[test]
+    fn dispatch() {
+        #[derive(Default)]
+        struct Dependencies(Vec<String>);
+
+        impl PyProjectObserver for Dependencies {
+            fn on_dependency_added(&mut self, location: &str, requirement: &str, _: Option<&str>) {
+                self.0.push(format!("+ {requirement} ({location})"));
+            }
+
+            fn on_dependency_replaced(
+                &mut self,
+                location: &str,
+                replaced: &str,
+                requirement: &str,
+                _: Option<&str>,
+            ) {
+                self.0
+                    .push(format!("~ {replaced} -> {requirement} ({location})"));
+            }
+        }
+
+        let mut observer = Dependencies::default();
+        for edit in [
+            PyProjectEdit::AddDependency {
+                location: "project.dependencies".to_string(),
+                requirement: "anyio>=4".to_string(),
+                replaced: Some("anyio>=3".to_string()),
+                source: None,
+            },
+            PyProjectEdit::CreateSection {
+                location: "dependency-groups.dev".to_string(),
+            },
+            PyProjectEdit::AddDependency {
+                location: "dependency-groups.dev".to_string(),
+                requirement: "pytest".to_string(),
+                replaced: None,
+                source: None,
+            },
+        ] {
+            observer.on_edit(&edit);
+        }
+
+        assert_eq!(
+            observer.0,
+            [
+                "~ anyio>=3 -> anyio>=4 (project.dependencies)",
+                "+ pytest (dependency-groups.dev)",
+            ]
+        );
+    }
+}
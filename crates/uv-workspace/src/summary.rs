@@ -0,0 +1,316 @@
+//! Summarizing the dependencies of a workspace by the markers that gate them.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use uv_normalize::{ExtraName, PackageName};
+use uv_pep508::{
+    ExtraOperator, MarkerExpression, MarkerOperator, MarkerValueExtra, MarkerValueString,
+    MarkerValueVersion, Requirement,
+};
+use uv_pypi_types::VerbatimParsedUrl;
+
+use crate::Workspace;
+use crate::pyproject::DependencyType;
+use crate::pyproject_mut::section;
+
+/// The dependencies of a workspace, grouped by the markers that gate them.
+///
+/// A requirement is listed under every bucket that applies to it, e.g., `foo ; sys_platform ==
+/// 'linux' and python_version < '3.10'` is listed under both `sys_platform == 'linux'` and
+/// `<3.10`, and `foo ; sys_platform == 'linux' or sys_platform == 'darwin'` is listed under both
+/// platforms. Markers are normalized before they're bucketed, such that `python_version` is
+/// expressed as a range of `python_full_version`, and `platform_system == 'Windows'` is expressed
+/// as `sys_platform == 'win32'`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DependencySummary {
+    /// The requirements that apply everywhere.
+    pub unconditional: Vec<SummaryRequirement>,
+    /// The requirements gated on the Python version, by the range of versions they apply to,
+    /// e.g., `>=3.9, <3.12`.
+    pub python: BTreeMap<String, Vec<SummaryRequirement>>,
+    /// The requirements gated on the platform, by marker, e.g., `sys_platform == 'linux'`.
+    pub platform: BTreeMap<String, Vec<SummaryRequirement>>,
+    /// The requirements gated on an extra, including those in `project.optional-dependencies`.
+    pub extra: BTreeMap<ExtraName, Vec<SummaryRequirement>>,
+    /// The requirements with markers that don't fit the other buckets, e.g., negations like
+    /// `sys_platform != 'win32'`, substring checks like `sys_platform in 'linux darwin'`, or
+    /// markers on other keys like `platform_machine`.
+    pub complex: Vec<SummaryRequirement>,
+}
+
+/// A requirement in a [`DependencySummary`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SummaryRequirement {
+    /// The workspace member declaring the requirement.
+    pub member: PackageName,
+    /// The section declaring the requirement, e.g., `project.dependencies`.
+    pub section: String,
+    /// The requirement, as written.
+    pub requirement: String,
+}
+
+impl DependencySummary {
+    /// Add a requirement to the buckets that apply to it.
+    fn insert(
+        &mut self,
+        summary: &SummaryRequirement,
+        requirement: &Requirement<VerbatimParsedUrl>,
+        extra: Option<&ExtraName>,
+    ) {
+        if let Some(extra) = extra {
+            push(self.extra.entry(extra.clone()).or_default(), summary);
+        }
+        if requirement.marker.is_true() {
+            if extra.is_none() {
+                self.unconditional.push(summary.clone());
+            }
+            return;
+        }
+
+        let conjunctions = requirement.marker.to_dnf();
+        if conjunctions.is_empty() {
+            // The marker never applies.
+            push(&mut self.complex, summary);
+        }
+        for conjunction in conjunctions {
+            let mut python = Vec::new();
+            for expression in &conjunction {
+                match expression {
+                    MarkerExpression::Version {
+                        key: MarkerValueVersion::PythonFullVersion,
+                        specifier,
+                    } => python.push(specifier.to_string()),
+                    MarkerExpression::String {
+                        key: MarkerValueString::SysPlatform | MarkerValueString::PlatformSystem,
+                        operator: MarkerOperator::Equal,
+                        ..
+                    } => push(
+                        self.platform.entry(expression.to_string()).or_default(),
+                        summary,
+                    ),
+                    MarkerExpression::Extra {
+                        name: MarkerValueExtra::Extra(name),
+                        operator: ExtraOperator::Equal,
+                    } => {
+                        push(self.extra.entry(name.clone()).or_default(), summary);
+                    }
+                    _ => push(&mut self.complex, summary),
+                }
+            }
+            if !python.is_empty() {
+                push(self.python.entry(python.join(", ")).or_default(), summary);
+            }
+        }
+    }
+}
+
+/// Add a requirement to a bucket, unless it's already listed.
+fn push(bucket: &mut Vec<SummaryRequirement>, summary: &SummaryRequirement) {
+    if !bucket.contains(summary) {
+        bucket.push(summary.clone());
+    }
+}
+
+/// Render the summary as a table, with a row for each requirement in each bucket.
+impl Display for DependencySummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let rows = self
+            .unconditional
+            .iter()
+            .map(|requirement| ("unconditional".to_string(), requirement))
+            .chain(self.python.iter().flat_map(|(range, requirements)| {
+                requirements
+                    .iter()
+                    .map(move |requirement| (format!("python {range}"), requirement))
+            }))
+            .chain(self.platform.iter().flat_map(|(marker, requirements)| {
+                requirements
+                    .iter()
+                    .map(move |requirement| (marker.clone(), requirement))
+            }))
+            .chain(self.extra.iter().flat_map(|(extra, requirements)| {
+                requirements
+                    .iter()
+                    .map(move |requirement| (format!("extra {extra}"), requirement))
+            }))
+            .chain(
+                self.complex
+                    .iter()
+                    .map(|requirement| ("complex".to_string(), requirement)),
+            )
+            .collect::<Vec<_>>();
+
+        let rows = rows
+            .into_iter()
+            .map(|(bucket, summary)| {
+                let source = format!("{} ({})", summary.member, summary.section);
+                (bucket, source, &summary.requirement)
+            })
+            .collect::<Vec<_>>();
+        let bucket_width = rows.iter().map(|(bucket, ..)| bucket.len()).max();
+        let source_width = rows.iter().map(|(_, source, _)| source.len()).max();
+        for (bucket, source, requirement) in &rows {
+            writeln!(
+                f,
+                "{bucket:bucket_width$}  {source:source_width$}  {requirement}",
+                bucket_width = bucket_width.unwrap_or_default(),
+                source_width = source_width.unwrap_or_default(),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Workspace {
+    /// Summarize the requirements of the workspace members, including their optional
+    /// dependencies, by the markers that gate them.
+    ///
+    /// Dependency groups aren't included, as they aren't part of the published metadata. Entries
+    /// that aren't valid requirements are skipped.
+    pub fn dependency_summary(&self) -> DependencySummary {
+        let mut summary = DependencySummary::default();
+        for (name, member) in self.packages() {
+            let project = member.project();
+            let sections = project
+                .dependencies
+                .iter()
+                .map(|requirements| (DependencyType::Production, requirements))
+                .chain(project.optional_dependencies.iter().flatten().map(
+                    |(extra, requirements)| (DependencyType::Optional(extra.clone()), requirements),
+                ));
+            for (dependency_type, requirements) in sections {
+                let extra = match &dependency_type {
+                    DependencyType::Optional(extra) => Some(extra),
+                    _ => None,
+                };
+                for requirement in requirements {
+                    let Ok(parsed) = Requirement::<VerbatimParsedUrl>::from_str(requirement) else {
+                        continue;
+                    };
+                    let entry = SummaryRequirement {
+                        member: name.clone(),
+                        section: section(&dependency_type),
+                        requirement: requirement.clone(),
+                    };
+                    summary.insert(&entry, &parsed, extra);
+                }
+            }
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use assert_fs::fixture::ChildPath;
+    use assert_fs::prelude::*;
+    use insta::assert_snapshot;
+
+    use crate::{DiscoveryOptions, Workspace, WorkspaceCache};
+
+    #[tokio::test]
+    async fn dependency_summary() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+        root.child("pyproject.toml").write_str(
+            r#"
+            [project]
+            name = "albatross"
+            version = "0.1.0"
+            dependencies = [
+                "anyio>=4",
+                "tomli ; python_version < '3.11'",
+                "typing-extensions ; python_version >= '3.9' and python_version <= '3.11'",
+                "uvloop ; sys_platform == 'linux' or sys_platform == 'darwin'",
+                "colorama ; platform_system == 'Windows'",
+                "appnope ; sys_platform == 'darwin' and python_version < '3.10'",
+                "pexpect ; sys_platform != 'win32'",
+                "jeepney ; sys_platform in 'linux freebsd'",
+                "numpy ; platform_machine == 'x86_64' or (sys_platform == 'linux' and extra == 'fast')",
+                "rich ; extra == 'cli'",
+            ]
+
+            [project.optional-dependencies]
+            cli = ["click>=8", "shellingham ; sys_platform == 'win32'"]
+
+            [dependency-groups]
+            dev = ["pytest"]
+            "#,
+        )?;
+        let workspace = Workspace::discover(
+            root.path(),
+            &DiscoveryOptions::default(),
+            &WorkspaceCache::default(),
+        )
+        .await?;
+
+        let summary = workspace.dependency_summary();
+        assert_snapshot!(summary, @r"
+        unconditional             albatross (project.dependencies)               anyio>=4
+        python <3.10              albatross (project.dependencies)               appnope ; sys_platform == 'darwin' and python_version < '3.10'
+        python <3.11              albatross (project.dependencies)               tomli ; python_version < '3.11'
+        python >=3.9, <3.12       albatross (project.dependencies)               typing-extensions ; python_version >= '3.9' and python_version <= '3.11'
+        sys_platform == 'darwin'  albatross (project.dependencies)               uvloop ; sys_platform == 'linux' or sys_platform == 'darwin'
+        sys_platform == 'darwin'  albatross (project.dependencies)               appnope ; sys_platform == 'darwin' and python_version < '3.10'
+        sys_platform == 'linux'   albatross (project.dependencies)               uvloop ; sys_platform == 'linux' or sys_platform == 'darwin'
+        sys_platform == 'linux'   albatross (project.dependencies)               numpy ; platform_machine == 'x86_64' or (sys_platform == 'linux' and extra == 'fast')
+        sys_platform == 'win32'   albatross (project.dependencies)               colorama ; platform_system == 'Windows'
+        sys_platform == 'win32'   albatross (project.optional-dependencies.cli)  shellingham ; sys_platform == 'win32'
+        extra cli                 albatross (project.dependencies)               rich ; extra == 'cli'
+        extra cli                 albatross (project.optional-dependencies.cli)  click>=8
+        extra cli                 albatross (project.optional-dependencies.cli)  shellingham ; sys_platform == 'win32'
+        extra fast                albatross (project.dependencies)               numpy ; platform_machine == 'x86_64' or (sys_platform == 'linux' and extra == 'fast')
+        complex                   albatross (project.dependencies)               pexpect ; sys_platform != 'win32'
+        complex                   albatross (project.dependencies)               jeepney ; sys_platform in 'linux freebsd'
+        complex                   albatross (project.dependencies)               numpy ; platform_machine == 'x86_64' or (sys_platform == 'linux' and extra == 'fast')
+        ");
+        assert_snapshot!(serde_json::to_string_pretty(&summary.platform)?, @r#"
+        {
+          "sys_platform == 'darwin'": [
+            {
+              "member": "albatross",
+              "section": "project.dependencies",
+              "requirement": "uvloop ; sys_platform == 'linux' or sys_platform == 'darwin'"
+            },
+            {
+              "member": "albatross",
+              "section": "project.dependencies",
+              "requirement": "appnope ; sys_platform == 'darwin' and python_version < '3.10'"
+            }
+          ],
+          "sys_platform == 'linux'": [
+            {
+              "member": "albatross",
+              "section": "project.dependencies",
+              "requirement": "uvloop ; sys_platform == 'linux' or sys_platform == 'darwin'"
+            },
+            {
+              "member": "albatross",
+              "section": "project.dependencies",
+              "requirement": "numpy ; platform_machine == 'x86_64' or (sys_platform == 'linux' and extra == 'fast')"
+            }
+          ],
+          "sys_platform == 'win32'": [
+            {
+              "member": "albatross",
+              "section": "project.dependencies",
+              "requirement": "colorama ; platform_system == 'Windows'"
+            },
+            {
+              "member": "albatross",
+              "section": "project.optional-dependencies.cli",
+              "requirement": "shellingham ; sys_platform == 'win32'"
+            }
+          ]
+        }
+        "#);
+
+        Ok(())
+    }
+}
@@ -1,9 +1,11 @@
 //! Resolve the current [`ProjectWorkspace`] or [`Workspace`].
 
 use std::collections::{BTreeMap, BTreeSet};
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
+use futures::StreamExt;
 use glob::{GlobError, PatternError, glob};
 use itertools::Itertools;
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -12,20 +14,27 @@ use tracing::{debug, trace, warn};
 use uv_configuration::DependencyGroupsWithDefaults;
 use uv_distribution_types::{Index, Requirement, RequirementSource};
 use uv_fs::{CWD, Simplified};
-use uv_normalize::{DEV_DEPENDENCIES, GroupName, PackageName};
+use uv_normalize::{DEV_DEPENDENCIES, GroupName, PackageName, VerbatimName};
 use uv_pep440::VersionSpecifiers;
-use uv_pep508::{MarkerTree, VerbatimUrl};
+use uv_pep508::{MarkerTree, Pep508Error, VerbatimUrl};
 use uv_pypi_types::{Conflicts, SupportedEnvironments, VerbatimParsedUrl};
 use uv_static::EnvVars;
 use uv_warnings::warn_user_once;
 
 use crate::dependency_groups::{DependencyGroupError, FlatDependencyGroup, FlatDependencyGroups};
+use crate::member_graph::MemberCycle;
+use crate::plan::PlanError;
+use crate::portable;
 use crate::pyproject::{
     Project, PyProjectToml, PyprojectTomlError, Source, Sources, ToolUvSources, ToolUvWorkspace,
 };
+use crate::pyproject_mut;
 
 type WorkspaceMembers = Arc<BTreeMap<PackageName, WorkspaceMember>>;
 
+/// The workspace members discovered for a workspace root, along with the paths that were skipped.
+type DiscoveredMembers = (WorkspaceMembers, Arc<[DiscoveryWarning]>);
+
 /// Cache key for workspace discovery.
 ///
 /// Given this key, the discovered workspace member list is the same.
@@ -40,7 +49,17 @@ struct WorkspaceCacheKey {
 /// Avoid re-reading the `pyproject.toml` files in a workspace for each member by caching the
 /// workspace members by their workspace root.
 #[derive(Debug, Default, Clone)]
-pub struct WorkspaceCache(Arc<Mutex<FxHashMap<WorkspaceCacheKey, WorkspaceMembers>>>);
+pub struct WorkspaceCache(Arc<Mutex<FxHashMap<WorkspaceCacheKey, DiscoveredMembers>>>);
+
+/// A path that was skipped during workspace discovery because it couldn't be read, rather than
+/// failing to load the rest of the workspace.
+#[derive(Debug)]
+pub struct DiscoveryWarning {
+    /// The directory or `pyproject.toml` that couldn't be read.
+    pub path: PathBuf,
+    /// The error encountered when reading the path.
+    pub err: std::io::Error,
+}
 
 #[derive(thiserror::Error, Debug)]
 pub enum WorkspaceError {
@@ -55,14 +74,18 @@ pub enum WorkspaceError {
     MissingWorkspace(PathBuf),
     #[error("The project is marked as unmanaged: {}", _0.simplified_display())]
     NonWorkspace(PathBuf),
-    #[error("Nested workspaces are not supported, but workspace member has a `tool.uv.workspace` table: {}", _0.simplified_display())]
-    NestedWorkspace(PathBuf),
+    #[error("Nested workspaces are not supported, but workspace member has a `tool.uv.workspace` table: {} (in workspace: {})", offending_member.simplified_display(), root.simplified_display())]
+    NestedWorkspace {
+        root: PathBuf,
+        offending_member: PathBuf,
+    },
     #[error("The workspace does not have a member {}: {}", _0, _1.simplified_display())]
     NoSuchMember(PackageName, PathBuf),
-    #[error("Two workspace members are both named `{name}`: `{}` and `{}`", first.simplified_display(), second.simplified_display())]
+    #[error("Two workspace members are both named `{}`: {} and {}", first_name.as_normalized(), display_member(first_name, first), display_member(second_name, second))]
     DuplicatePackage {
-        name: PackageName,
+        first_name: VerbatimName,
         first: PathBuf,
+        second_name: VerbatimName,
         second: PathBuf,
     },
     #[error("pyproject.toml section is declared as dynamic, but must be static: `{0}`")]
@@ -77,12 +100,88 @@ pub enum WorkspaceError {
     // Syntax and other errors.
     #[error("Directory walking failed for `tool.uv.workspace.members` glob: `{0}`")]
     GlobWalk(String, #[source] GlobError),
-    #[error(transparent)]
-    Io(#[from] std::io::Error),
+    #[error("Failed to read: `{}`", _0.user_display())]
+    Io(PathBuf, #[source] std::io::Error),
     #[error("Failed to parse: `{}`", _0.user_display())]
     Toml(PathBuf, #[source] Box<PyprojectTomlError>),
-    #[error("Failed to normalize workspace member path")]
-    Normalize(#[source] std::io::Error),
+    #[error("Failed to normalize workspace member path: `{}`", _0.user_display())]
+    Normalize(PathBuf, #[source] std::io::Error),
+    #[error("Failed to parse dependency of workspace member `{0}`: `{1}`")]
+    DependencyParse(
+        PackageName,
+        String,
+        #[source] Box<Pep508Error<VerbatimParsedUrl>>,
+    ),
+    #[error(transparent)]
+    DependencyGroup(#[from] DependencyGroupError),
+    #[error("Detected a cycle between workspace members: {0}")]
+    MemberCycle(MemberCycle),
+    #[error("Failed to load {} workspace members:{}", _0.len(), display_errors(_0))]
+    Members(Vec<Self>),
+    #[error("Failed to load workspace member")]
+    Join(#[source] tokio::task::JoinError),
+    // Plan errors.
+    #[error("Invalid operation at index {0} of the plan")]
+    InvalidPlan(usize, #[source] PlanError),
+    #[error("Failed to apply operation at index {0} of the plan to `{1}`")]
+    ApplyPlan(usize, PackageName, #[source] Box<pyproject_mut::Error>),
+    #[error("Failed to edit workspace member `{0}`")]
+    EditMember(PackageName, #[source] Box<pyproject_mut::Error>),
+}
+
+impl WorkspaceError {
+    /// Returns the path of the file or directory involved in the error, if any.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::MissingPyprojectTomlMember(path, _)
+            | Self::MissingProject(path)
+            | Self::MissingWorkspace(path)
+            | Self::NonWorkspace(path)
+            | Self::NoSuchMember(_, path)
+            | Self::Io(path, _)
+            | Self::Toml(path, _)
+            | Self::Normalize(path, _) => Some(path),
+            Self::NestedWorkspace {
+                offending_member, ..
+            } => Some(offending_member),
+            Self::DuplicatePackage { second, .. } => Some(second),
+            Self::GlobWalk(_, err) => Some(err.path()),
+            Self::ApplyPlan(_, _, err) | Self::EditMember(_, err) => err.path(),
+            Self::MissingPyprojectToml
+            | Self::DynamicNotAllowed(_)
+            | Self::EditableConflict(_)
+            | Self::Pattern(..)
+            | Self::DependencyParse(..)
+            | Self::DependencyGroup(_)
+            | Self::MemberCycle(_)
+            | Self::Members(_)
+            | Self::Join(_)
+            | Self::InvalidPlan(..) => None,
+        }
+    }
+}
+
+/// Display each error, along with its causes, on its own line.
+fn display_errors(errors: &[WorkspaceError]) -> String {
+    let mut output = String::new();
+    for err in errors {
+        output.push_str("\n- ");
+        output.push_str(
+            &std::iter::successors(Some(err as &dyn std::error::Error), |err| err.source())
+                .join(": "),
+        );
+    }
+    output
+}
+
+/// Display the root of a workspace member, along with its name if it isn't spelled in its
+/// normalized form.
+fn display_member(name: &VerbatimName, root: &Path) -> String {
+    if name.is_normalized() {
+        format!("`{}`", root.simplified_display())
+    } else {
+        format!("`{}` (as `{name}`)", root.simplified_display())
+    }
 }
 
 #[derive(Debug, Default, Clone, Hash, PartialEq, Eq)]
@@ -125,17 +224,86 @@ impl ProjectDiscovery {
 pub struct DiscoveryOptions {
     /// The path to stop discovery at.
     pub stop_discovery_at: Option<PathBuf>,
+    /// Directories above which discovery will not walk, analogous to `GIT_CEILING_DIRECTORIES`.
+    ///
+    /// Unlike `stop_discovery_at`, the ceilings need not be ancestors of the discovery path;
+    /// ceilings that aren't on the path are ignored.
+    pub ceiling_directories: Vec<PathBuf>,
     /// The strategy to use when discovering workspace members.
     pub members: MemberDiscovery,
     /// The strategy to use when discovering the project.
     pub project: ProjectDiscovery,
 }
 
+impl DiscoveryOptions {
+    /// Returns the directories that discovery may consider, starting at `path` and walking up.
+    ///
+    /// The walk stops at `stop_discovery_at`, at any of the `ceiling_directories`, and at the root
+    /// of a version control repository (a directory containing `.git`, `.hg`, or `.jj`). The
+    /// directory that ends the walk is itself included, such that a `pyproject.toml` at the
+    /// repository root is still discovered, but one in a parent of the repository is not.
+    fn ancestors<'a>(&'a self, path: &'a Path) -> impl Iterator<Item = &'a Path> + 'a {
+        let mut done = false;
+        path.ancestors().take_while(move |ancestor| {
+            if done {
+                return false;
+            }
+            if self.stop_discovery_at.as_deref() == Some(*ancestor) {
+                done = true;
+            } else if self
+                .ceiling_directories
+                .iter()
+                .any(|ceiling| ceiling == ancestor)
+            {
+                trace!(
+                    "Stopping discovery at ceiling directory: `{}`",
+                    ancestor.simplified_display()
+                );
+                done = true;
+            } else if is_repository_root(ancestor) {
+                trace!(
+                    "Stopping discovery at repository root: `{}`",
+                    ancestor.simplified_display()
+                );
+                done = true;
+            }
+            true
+        })
+    }
+
+    /// Returns the nearest directory containing a `pyproject.toml`, starting at `path`.
+    ///
+    /// If a parent directory can't be read due to insufficient permissions, discovery stops at
+    /// that directory; permission errors on `path` itself are returned.
+    fn find_project_root<'a>(&'a self, path: &'a Path) -> Result<Option<&'a Path>, WorkspaceError> {
+        for ancestor in self.ancestors(path) {
+            match has_pyproject_toml(ancestor) {
+                Ok(true) => return Ok(Some(ancestor)),
+                Ok(false) => {}
+                Err(err) if ancestor == path => {
+                    return Err(WorkspaceError::Io(ancestor.join("pyproject.toml"), err));
+                }
+                Err(err) => {
+                    debug!(
+                        "Stopping discovery at unreadable directory `{}`: {err}",
+                        ancestor.simplified_display()
+                    );
+                    return Ok(None);
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
 pub type RequiresPythonSources = BTreeMap<(PackageName, Option<GroupName>), VersionSpecifiers>;
 
 pub type Editability = Option<bool>;
 
 /// A workspace, consisting of a root directory and members. See [`ProjectWorkspace`].
+///
+/// The state of the workspace is reference-counted, such that cloning a workspace (or taking a
+/// [`Workspace::snapshot`]) is cheap, regardless of the number of members.
 #[derive(Debug, Clone)]
 #[cfg_attr(test, derive(serde::Serialize))]
 pub struct Workspace {
@@ -148,17 +316,20 @@ pub struct Workspace {
     packages: WorkspaceMembers,
     /// The workspace members that are required by other members, and whether they were requested
     /// as editable.
-    required_members: BTreeMap<PackageName, Editability>,
+    required_members: Arc<BTreeMap<PackageName, Editability>>,
     /// The sources table from the workspace `pyproject.toml`.
     ///
     /// This table is overridden by the project sources.
-    sources: BTreeMap<PackageName, Sources>,
+    sources: Arc<BTreeMap<PackageName, Sources>>,
     /// The index table from the workspace `pyproject.toml`.
     ///
     /// This table is overridden by the project indexes.
-    indexes: Vec<Index>,
+    indexes: Arc<[Index]>,
     /// The `pyproject.toml` of the workspace root.
-    pyproject_toml: PyProjectToml,
+    pyproject_toml: Arc<PyProjectToml>,
+    /// The paths that were skipped while discovering the workspace members.
+    #[cfg_attr(test, serde(skip))]
+    warnings: Arc<[DiscoveryWarning]>,
 }
 
 impl Workspace {
@@ -189,21 +360,22 @@ impl Workspace {
         cache: &WorkspaceCache,
     ) -> Result<Self, WorkspaceError> {
         let path = std::path::absolute(path)
-            .map_err(WorkspaceError::Normalize)?
+            .map_err(|err| WorkspaceError::Normalize(path.to_path_buf(), err))?
             .clone();
         // Remove `.` and `..`
         let path = uv_fs::normalize_path(&path);
         // Trim trailing slashes.
         let path = path.components().collect::<PathBuf>();
 
-        let project_path = path
-            .ancestors()
-            .find(|path| path.join("pyproject.toml").is_file())
+        let project_path = options
+            .find_project_root(&path)?
             .ok_or(WorkspaceError::MissingPyprojectToml)?
             .to_path_buf();
 
         let pyproject_path = project_path.join("pyproject.toml");
-        let contents = fs_err::tokio::read_to_string(&pyproject_path).await?;
+        let contents = fs_err::tokio::read_to_string(&pyproject_path)
+            .await
+            .map_err(|err| WorkspaceError::Io(pyproject_path.clone(), err))?;
         let pyproject_toml = PyProjectToml::from_string(contents, &pyproject_path)
             .map_err(|err| WorkspaceError::Toml(pyproject_path.clone(), Box::new(err)))?;
 
@@ -333,10 +505,10 @@ impl Workspace {
             )?;
 
             Ok(Some(Self {
-                pyproject_toml: workspace_pyproject_toml,
-                sources: workspace_sources,
+                pyproject_toml: Arc::new(workspace_pyproject_toml),
+                sources: Arc::new(workspace_sources),
                 packages,
-                required_members,
+                required_members: Arc::new(required_members),
                 ..self
             }))
         } else {
@@ -349,12 +521,32 @@ impl Workspace {
 
             Ok(Some(Self {
                 packages,
-                required_members,
+                required_members: Arc::new(required_members),
                 ..self
             }))
         }
     }
 
+    /// Take a snapshot of the workspace, to [`Workspace::restore`] after trying out edits, e.g.,
+    /// updating a member with [`Workspace::update_member`] to preview its effect.
+    ///
+    /// Taking a snapshot only copies the path to the workspace root: the members and the metadata
+    /// of the workspace root are shared between the snapshot and the workspace. The cost is paid on
+    /// the first update after taking the snapshot, which copies the table of members (including
+    /// the parsed `pyproject.toml` of each member) rather than modifying it in place. Restoring a
+    /// snapshot is free.
+    ///
+    /// Only the in-memory state of the workspace is captured; a snapshot doesn't undo edits that
+    /// were written to disk (e.g., by [`Workspace::apply_plan`]).
+    pub fn snapshot(&self) -> WorkspaceSnapshot {
+        WorkspaceSnapshot(self.clone())
+    }
+
+    /// Restore the workspace to a [`Workspace::snapshot`], discarding any changes made since.
+    pub fn restore(&mut self, snapshot: WorkspaceSnapshot) {
+        *self = snapshot.0;
+    }
+
     /// Returns `true` if the workspace has a non-project root.
     pub fn is_non_project(&self) -> bool {
         !self
@@ -794,6 +986,12 @@ impl Workspace {
         &self.indexes
     }
 
+    /// The paths that were skipped while discovering the workspace members, e.g., member
+    /// directories that couldn't be read.
+    pub fn warnings(&self) -> &[DiscoveryWarning] {
+        &self.warnings
+    }
+
     /// The `pyproject.toml` of the workspace.
     pub fn pyproject_toml(&self) -> &PyProjectToml {
         &self.pyproject_toml
@@ -847,30 +1045,31 @@ impl Workspace {
             let cache = cache.0.lock().expect("there was a panic in another thread");
             cache.get(&cache_key).cloned()
         };
-        let mut workspace_members = if let Some(workspace_members) = cache_entry {
+        let (mut workspace_members, warnings) = if let Some(discovered) = cache_entry {
             trace!(
                 "Cached workspace members for: `{}`",
                 &workspace_root.simplified_display()
             );
-            workspace_members
+            discovered
         } else {
             trace!(
                 "Discovering workspace members for: `{}`",
                 &workspace_root.simplified_display()
             );
-            let workspace_members = Self::collect_members_only(
+            let (workspace_members, warnings) = Self::collect_members_only(
                 &workspace_root,
                 &workspace_definition,
                 &workspace_pyproject_toml,
                 options,
             )
             .await?;
+            let discovered = (Arc::new(workspace_members), Arc::from(warnings));
             {
                 // Acquire the lock for the minimal required region
                 let mut cache = cache.0.lock().expect("there was a panic in another thread");
-                cache.insert(cache_key, Arc::new(workspace_members.clone()));
+                cache.insert(cache_key, discovered.clone());
             }
-            Arc::new(workspace_members)
+            discovered
         };
 
         // For the cases such as `MemberDiscovery::None`, add the current project if missing.
@@ -929,10 +1128,11 @@ impl Workspace {
         Ok(Self {
             install_path: workspace_root,
             packages: workspace_members,
-            required_members,
-            sources: workspace_sources,
-            indexes: workspace_indexes,
-            pyproject_toml: workspace_pyproject_toml,
+            required_members: Arc::new(required_members),
+            sources: Arc::new(workspace_sources),
+            indexes: Arc::from(workspace_indexes),
+            pyproject_toml: Arc::new(workspace_pyproject_toml),
+            warnings,
         })
     }
 
@@ -941,8 +1141,15 @@ impl Workspace {
         workspace_definition: &ToolUvWorkspace,
         workspace_pyproject_toml: &PyProjectToml,
         options: &DiscoveryOptions,
-    ) -> Result<BTreeMap<PackageName, WorkspaceMember>, WorkspaceError> {
+    ) -> Result<
+        (
+            BTreeMap<PackageName, WorkspaceMember>,
+            Vec<DiscoveryWarning>,
+        ),
+        WorkspaceError,
+    > {
         let mut workspace_members = BTreeMap::new();
+        let mut warnings = Vec::new();
         // Avoid reading a `pyproject.toml` more than once.
         let mut seen = FxHashSet::default();
 
@@ -950,7 +1157,8 @@ impl Workspace {
         // project. If it is the current project, it is added as such in the next step.
         if let Some(project) = &workspace_pyproject_toml.project {
             let pyproject_path = workspace_root.join("pyproject.toml");
-            let contents = fs_err::read_to_string(&pyproject_path)?;
+            let contents = fs_err::read_to_string(&pyproject_path)
+                .map_err(|err| WorkspaceError::Io(pyproject_path.clone(), err))?;
             let pyproject_toml = PyProjectToml::from_string(contents, &pyproject_path)
                 .map_err(|err| WorkspaceError::Toml(pyproject_path.clone(), Box::new(err)))?;
 
@@ -970,10 +1178,15 @@ impl Workspace {
             );
         }
 
-        // Add all other workspace members.
-        for member_glob in workspace_definition.clone().members.unwrap_or_default() {
-            // Normalize the member glob to remove leading `./` and other relative path components
-            let normalized_glob = uv_fs::normalize_path(Path::new(member_glob.as_str()));
+        // Expand the member globs, in order, collecting the member directories to load.
+        let member_globs = workspace_definition.clone().members.unwrap_or_default();
+        let mut candidates = Vec::new();
+        let mut matched = vec![0usize; member_globs.len()];
+        for (glob_index, member_glob) in member_globs.iter().enumerate() {
+            // Normalize the member glob to use forward slashes, and to remove leading `./` and other
+            // relative path components
+            let portable_member_glob = portable::to_forward_slashes(member_glob.as_str());
+            let normalized_glob = uv_fs::normalize_path(Path::new(portable_member_glob.as_ref()));
             let absolute_glob = PathBuf::from(glob::Pattern::escape(
                 workspace_root.simplified().to_string_lossy().as_ref(),
             ))
@@ -983,13 +1196,32 @@ impl Workspace {
             for member_root in glob(&absolute_glob)
                 .map_err(|err| WorkspaceError::Pattern(absolute_glob.clone(), err))?
             {
-                let member_root = member_root
-                    .map_err(|err| WorkspaceError::GlobWalk(absolute_glob.clone(), err))?;
+                matched[glob_index] += 1;
+                let member_root = match member_root {
+                    Ok(member_root) => member_root,
+                    // Skip directories that can't be read, rather than failing to load the rest
+                    // of the workspace.
+                    Err(err) if err.error().kind() == std::io::ErrorKind::PermissionDenied => {
+                        warn_user_once!(
+                            "Ignoring unreadable directory `{}` while expanding workspace member glob `{}`: {}",
+                            err.path().user_display(),
+                            member_glob.as_str(),
+                            err.error()
+                        );
+                        let path = err.path().to_path_buf();
+                        warnings.push(DiscoveryWarning {
+                            path,
+                            err: err.into_error(),
+                        });
+                        continue;
+                    }
+                    Err(err) => return Err(WorkspaceError::GlobWalk(absolute_glob.clone(), err)),
+                };
                 if !seen.insert(member_root.clone()) {
                     continue;
                 }
                 let member_root = std::path::absolute(&member_root)
-                    .map_err(WorkspaceError::Normalize)?
+                    .map_err(|err| WorkspaceError::Normalize(member_root.clone(), err))?
                     .clone();
 
                 // If the directory is explicitly ignored, skip it.
@@ -1000,8 +1232,9 @@ impl Workspace {
                 };
                 if skip {
                     debug!(
-                        "Ignoring workspace member: `{}`",
-                        member_root.simplified_display()
+                        path = %member_root.simplified_display(),
+                        reason = "ignored",
+                        "Ignoring workspace member"
                     );
                     continue;
                 }
@@ -1009,114 +1242,151 @@ impl Workspace {
                 // If the member is excluded, ignore it.
                 if is_excluded_from_workspace(&member_root, workspace_root, workspace_definition)? {
                     debug!(
-                        "Ignoring workspace member: `{}`",
-                        member_root.simplified_display()
+                        path = %member_root.simplified_display(),
+                        reason = "excluded",
+                        "Ignoring workspace member"
                     );
                     continue;
                 }
 
-                trace!(
-                    "Processing workspace member: `{}`",
-                    member_root.user_display()
-                );
-
-                // Read the member `pyproject.toml`.
-                let pyproject_path = member_root.join("pyproject.toml");
-                let contents = match fs_err::tokio::read_to_string(&pyproject_path).await {
-                    Ok(contents) => contents,
-                    Err(err) => {
-                        if !fs_err::metadata(&member_root)?.is_dir() {
-                            warn!(
-                                "Ignoring non-directory workspace member: `{}`",
-                                member_root.simplified_display()
-                            );
-                            continue;
-                        }
-
-                        // A directory exists, but it doesn't contain a `pyproject.toml`.
-                        if err.kind() == std::io::ErrorKind::NotFound {
-                            // If the directory is hidden, skip it.
-                            if member_root
-                                .file_name()
-                                .map(|name| name.as_encoded_bytes().starts_with(b"."))
-                                .unwrap_or(false)
-                            {
-                                debug!(
-                                    "Ignoring hidden workspace member: `{}`",
-                                    member_root.simplified_display()
-                                );
-                                continue;
-                            }
-
-                            // If the directory only contains gitignored files
-                            // (e.g., `__pycache__`), skip it.
-                            if has_only_gitignored_files(&member_root) {
-                                debug!(
-                                    "Ignoring workspace member with only gitignored files: `{}`",
-                                    member_root.simplified_display()
-                                );
-                                continue;
-                            }
-
-                            return Err(WorkspaceError::MissingPyprojectTomlMember(
-                                member_root,
-                                member_glob.to_string(),
-                            ));
-                        }
+                candidates.push((glob_index, member_root));
+            }
+        }
 
-                        return Err(err.into());
-                    }
-                };
-                let pyproject_toml = PyProjectToml::from_string(contents, &pyproject_path)
-                    .map_err(|err| WorkspaceError::Toml(pyproject_path.clone(), Box::new(err)))?;
+        // Read and parse the member `pyproject.toml` files concurrently. The results are yielded
+        // in the order of the candidates, so the checks below are deterministic.
+        let mut loaded = futures::stream::iter(candidates)
+            .map(|(glob_index, member_root)| {
+                let member_glob = member_globs[glob_index].to_string();
+                async move {
+                    let result = tokio::task::spawn_blocking({
+                        let member_root = member_root.clone();
+                        move || load_member(&member_root, &member_glob)
+                    })
+                    .await;
+                    (glob_index, member_root, result)
+                }
+            })
+            .buffered(
+                std::thread::available_parallelism()
+                    .map_or(1, NonZeroUsize::get)
+                    .min(MAX_CONCURRENT_MEMBER_LOADS),
+            );
 
-                // Check if the current project is explicitly marked as unmanaged.
-                if pyproject_toml
-                    .tool
-                    .as_ref()
-                    .and_then(|tool| tool.uv.as_ref())
-                    .and_then(|uv| uv.managed)
-                    == Some(false)
-                {
-                    if let Some(project) = pyproject_toml.project.as_ref() {
-                        debug!(
-                            "Project `{}` is marked as unmanaged; omitting from workspace members",
-                            project.name
-                        );
-                    } else {
-                        debug!(
-                            "Workspace member at `{}` is marked as unmanaged; omitting from workspace members",
-                            member_root.simplified_display()
-                        );
-                    }
+        // Collect the members, along with every error, rather than stopping at the first.
+        let mut errors = Vec::new();
+        let mut added = vec![0usize; member_globs.len()];
+        while let Some((glob_index, member_root, result)) = loaded.next().await {
+            let pyproject_toml = match result {
+                Ok(Ok(MemberLoad::Loaded(pyproject_toml))) => pyproject_toml,
+                Ok(Ok(MemberLoad::Unreadable(err))) => {
+                    warn_user_once!(
+                        "Ignoring workspace member with an unreadable `pyproject.toml`: {err}"
+                    );
+                    warnings.push(DiscoveryWarning {
+                        path: member_root.join("pyproject.toml"),
+                        err,
+                    });
+                    continue;
+                }
+                Ok(Ok(MemberLoad::NonDirectory)) => {
+                    warn!(
+                        "Ignoring non-directory workspace member: `{}`",
+                        member_root.simplified_display()
+                    );
+                    continue;
+                }
+                Ok(Ok(MemberLoad::Ignored(reason))) => {
+                    debug!(
+                        path = %member_root.simplified_display(),
+                        reason,
+                        "Ignoring workspace member"
+                    );
+                    continue;
+                }
+                Ok(Err(err)) => {
+                    errors.push(err);
+                    continue;
+                }
+                Err(err) => {
+                    errors.push(WorkspaceError::Join(err));
                     continue;
                 }
+            };
 
-                // Extract the package name.
-                let Some(project) = pyproject_toml.project.clone() else {
-                    return Err(WorkspaceError::MissingProject(pyproject_path));
-                };
+            // Check if the current project is explicitly marked as unmanaged.
+            //
+            // Unmanaged members are omitted before the nested workspace check below, which
+            // allows a vendored project to declare its own `tool.uv.workspace`.
+            if pyproject_toml
+                .tool
+                .as_ref()
+                .and_then(|tool| tool.uv.as_ref())
+                .and_then(|uv| uv.managed)
+                == Some(false)
+            {
+                if let Some(project) = pyproject_toml.project.as_ref() {
+                    debug!(
+                        "Project `{}` is marked as unmanaged; omitting from workspace members",
+                        project.name
+                    );
+                } else {
+                    debug!(
+                        "Workspace member at `{}` is marked as unmanaged; omitting from workspace members",
+                        member_root.simplified_display()
+                    );
+                }
+                continue;
+            }
 
-                debug!(
-                    "Adding discovered workspace member: `{}`",
-                    member_root.simplified_display()
-                );
+            // Extract the package name.
+            let Some(project) = pyproject_toml.project.clone() else {
+                errors.push(WorkspaceError::MissingProject(
+                    member_root.join("pyproject.toml"),
+                ));
+                continue;
+            };
 
-                if let Some(existing) = workspace_members.insert(
-                    project.name.clone(),
-                    WorkspaceMember {
-                        root: member_root.clone(),
-                        project,
-                        pyproject_toml,
-                    },
-                ) {
-                    return Err(WorkspaceError::DuplicatePackage {
-                        name: existing.project.name,
-                        first: existing.root.clone(),
-                        second: member_root,
-                    });
-                }
+            debug!(
+                "Adding discovered workspace member: `{}`",
+                member_root.simplified_display()
+            );
+
+            if let Some(existing) = workspace_members.get(&project.name) {
+                errors.push(WorkspaceError::DuplicatePackage {
+                    first_name: existing.project.verbatim_name().clone(),
+                    first: existing.root.clone(),
+                    second_name: project.verbatim_name().clone(),
+                    second: member_root,
+                });
+                continue;
             }
+            added[glob_index] += 1;
+            workspace_members.insert(
+                project.name.clone(),
+                WorkspaceMember {
+                    root: member_root,
+                    project,
+                    pyproject_toml: *pyproject_toml,
+                },
+            );
+        }
+
+        for ((member_glob, matched), added) in member_globs.iter().zip(matched).zip(added) {
+            debug!(
+                glob = member_glob.as_str(),
+                matched,
+                added,
+                skipped = matched - added,
+                "Expanded workspace member glob"
+            );
+        }
+
+        if errors.len() > 1 {
+            return Err(WorkspaceError::Members(errors));
+        }
+        if let Some(err) = errors.pop() {
+            return Err(err);
         }
 
         // Test for nested workspaces.
@@ -1130,13 +1400,86 @@ impl Workspace {
                     .and_then(|uv| uv.workspace.as_ref())
                     .is_some()
             {
-                return Err(WorkspaceError::NestedWorkspace(member.root.clone()));
+                return Err(WorkspaceError::NestedWorkspace {
+                    root: workspace_root.clone(),
+                    offending_member: member.root.clone(),
+                });
             }
         }
-        Ok(workspace_members)
+        Ok((workspace_members, warnings))
     }
 }
 
+/// The maximum number of workspace member `pyproject.toml` files to read and parse at once.
+const MAX_CONCURRENT_MEMBER_LOADS: usize = 32;
+
+/// The result of loading the `pyproject.toml` of a workspace member candidate.
+enum MemberLoad {
+    /// The `pyproject.toml` was read and parsed.
+    Loaded(Box<PyProjectToml>),
+    /// The `pyproject.toml` exists, but can't be read.
+    Unreadable(std::io::Error),
+    /// The glob matched a file, rather than a directory.
+    NonDirectory,
+    /// The directory has no `pyproject.toml`, but is skipped for the given reason.
+    Ignored(&'static str),
+}
+
+/// Read and parse the `pyproject.toml` of a workspace member candidate, matched by the given glob.
+fn load_member(member_root: &Path, member_glob: &str) -> Result<MemberLoad, WorkspaceError> {
+    trace!(
+        "Processing workspace member: `{}`",
+        member_root.user_display()
+    );
+
+    let pyproject_path = member_root.join("pyproject.toml");
+    let contents = match fs_err::read_to_string(&pyproject_path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::PermissionDenied => {
+            return Ok(MemberLoad::Unreadable(err));
+        }
+        Err(err) => {
+            if !fs_err::metadata(member_root)
+                .map_err(|err| WorkspaceError::Io(member_root.to_path_buf(), err))?
+                .is_dir()
+            {
+                return Ok(MemberLoad::NonDirectory);
+            }
+
+            // A directory exists, but it doesn't contain a `pyproject.toml`.
+            if err.kind() == std::io::ErrorKind::NotFound {
+                // If the directory is hidden, skip it.
+                if member_root
+                    .file_name()
+                    .map(|name| name.as_encoded_bytes().starts_with(b"."))
+                    .unwrap_or(false)
+                {
+                    return Ok(MemberLoad::Ignored("hidden"));
+                }
+
+                // If the directory only contains gitignored files (e.g., `__pycache__`), skip it.
+                if has_only_gitignored_files(member_root) {
+                    return Ok(MemberLoad::Ignored("gitignored"));
+                }
+
+                return Err(WorkspaceError::MissingPyprojectTomlMember(
+                    member_root.to_path_buf(),
+                    member_glob.to_string(),
+                ));
+            }
+
+            return Err(WorkspaceError::Io(pyproject_path, err));
+        }
+    };
+    let pyproject_toml = PyProjectToml::from_string(contents, &pyproject_path)
+        .map_err(|err| WorkspaceError::Toml(pyproject_path, Box::new(err)))?;
+    Ok(MemberLoad::Loaded(Box::new(pyproject_toml)))
+}
+
+/// The state of a [`Workspace`] at a point in time, taken with [`Workspace::snapshot`].
+#[derive(Debug, Clone)]
+pub struct WorkspaceSnapshot(Workspace);
+
 /// A project in a workspace.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(test, derive(serde::Serialize))]
@@ -1166,6 +1509,36 @@ impl WorkspaceMember {
     pub fn pyproject_toml(&self) -> &PyProjectToml {
         &self.pyproject_toml
     }
+
+    /// Returns `true` if the member declares the dependency with a `{ workspace = true }` source.
+    ///
+    /// If the member doesn't declare any source for the dependency, the given sources of the
+    /// workspace root are used instead.
+    pub(crate) fn has_workspace_source(
+        &self,
+        dependency: &PackageName,
+        workspace_sources: Option<&BTreeMap<PackageName, Sources>>,
+    ) -> bool {
+        let sources = self
+            .pyproject_toml
+            .tool
+            .as_ref()
+            .and_then(|tool| tool.uv.as_ref())
+            .and_then(|uv| uv.sources.as_ref())
+            .and_then(|sources| sources.inner().get(dependency))
+            .or_else(|| workspace_sources.and_then(|sources| sources.get(dependency)));
+        sources.is_some_and(|sources| {
+            sources.iter().any(|source| {
+                matches!(
+                    source,
+                    Source::Workspace {
+                        workspace: true,
+                        ..
+                    }
+                )
+            })
+        })
+    }
 }
 
 /// The current project and the workspace it is part of, with all of the workspace members.
@@ -1266,18 +1639,8 @@ impl ProjectWorkspace {
         options: &DiscoveryOptions,
         cache: &WorkspaceCache,
     ) -> Result<Self, WorkspaceError> {
-        let project_root = path
-            .ancestors()
-            .take_while(|path| {
-                // Only walk up the given directory, if any.
-                options
-                    .stop_discovery_at
-                    .as_deref()
-                    .and_then(Path::parent)
-                    .map(|stop_discovery_at| stop_discovery_at != *path)
-                    .unwrap_or(true)
-            })
-            .find(|path| path.join("pyproject.toml").is_file())
+        let project_root = options
+            .find_project_root(path)?
             .ok_or(WorkspaceError::MissingPyprojectToml)?;
 
         debug!(
@@ -1296,7 +1659,9 @@ impl ProjectWorkspace {
     ) -> Result<Self, WorkspaceError> {
         // Read the current `pyproject.toml`.
         let pyproject_path = project_root.join("pyproject.toml");
-        let contents = fs_err::tokio::read_to_string(&pyproject_path).await?;
+        let contents = fs_err::tokio::read_to_string(&pyproject_path)
+            .await
+            .map_err(|err| WorkspaceError::Io(pyproject_path.clone(), err))?;
         let pyproject_toml = PyProjectToml::from_string(contents, &pyproject_path)
             .map_err(|err| WorkspaceError::Toml(pyproject_path.clone(), Box::new(err)))?;
 
@@ -1384,7 +1749,7 @@ impl ProjectWorkspace {
         cache: &WorkspaceCache,
     ) -> Result<Self, WorkspaceError> {
         let project_path = std::path::absolute(install_path)
-            .map_err(WorkspaceError::Normalize)?
+            .map_err(|err| WorkspaceError::Normalize(install_path.to_path_buf(), err))?
             .clone();
         // Remove `.` and `..`
         let project_path = uv_fs::normalize_path(&project_path);
@@ -1452,12 +1817,13 @@ impl ProjectWorkspace {
                 workspace: Workspace {
                     install_path: project_path.clone(),
                     packages: current_project_as_members,
-                    required_members,
+                    required_members: Arc::new(required_members),
                     // There may be package sources, but we don't need to duplicate them into the
                     // workspace sources.
-                    sources: workspace_sources,
-                    indexes: Vec::default(),
-                    pyproject_toml: project_pyproject_toml.clone(),
+                    sources: Arc::new(workspace_sources),
+                    indexes: Arc::default(),
+                    pyproject_toml: Arc::new(project_pyproject_toml.clone()),
+                    warnings: Arc::default(),
                 },
             });
         };
@@ -1491,30 +1857,34 @@ async fn find_workspace(
     options: &DiscoveryOptions,
 ) -> Result<Option<(PathBuf, ToolUvWorkspace, PyProjectToml)>, WorkspaceError> {
     // Skip 1 to ignore the current project itself.
-    for workspace_root in project_root
-        .ancestors()
-        .take_while(|path| {
-            // Only walk up the given directory, if any.
-            options
-                .stop_discovery_at
-                .as_deref()
-                .and_then(Path::parent)
-                .map(|stop_discovery_at| stop_discovery_at != *path)
-                .unwrap_or(true)
-        })
-        .skip(1)
-    {
+    for workspace_root in options.ancestors(project_root).skip(1) {
         let pyproject_path = workspace_root.join("pyproject.toml");
-        if !pyproject_path.is_file() {
-            continue;
+        match has_pyproject_toml(workspace_root) {
+            Ok(true) => {}
+            Ok(false) => continue,
+            Err(err) => {
+                debug!(
+                    "Stopping workspace discovery at unreadable directory `{}`: {err}",
+                    workspace_root.simplified_display()
+                );
+                return Ok(None);
+            }
         }
         trace!(
             "Found `pyproject.toml` at: `{}`",
             pyproject_path.simplified_display()
         );
 
-        // Read the `pyproject.toml`.
-        let contents = fs_err::tokio::read_to_string(&pyproject_path).await?;
+        // Read the `pyproject.toml`. An unreadable `pyproject.toml` in a parent directory is
+        // treated as the end of discovery, rather than an error.
+        let contents = match fs_err::tokio::read_to_string(&pyproject_path).await {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::PermissionDenied => {
+                debug!("Stopping workspace discovery at unreadable `pyproject.toml`: {err}");
+                return Ok(None);
+            }
+            Err(err) => return Err(WorkspaceError::Io(pyproject_path, err)),
+        };
         let pyproject_toml = PyProjectToml::from_string(contents, &pyproject_path)
             .map_err(|err| WorkspaceError::Toml(pyproject_path.clone(), Box::new(err)))?;
 
@@ -1583,6 +1953,28 @@ async fn find_workspace(
     Ok(None)
 }
 
+/// Returns `true` if the directory contains a `pyproject.toml` file.
+///
+/// Permission errors are returned, such that callers can decide whether to stop discovery; all
+/// other errors are treated as the absence of a `pyproject.toml`.
+fn has_pyproject_toml(path: &Path) -> Result<bool, std::io::Error> {
+    match fs_err::metadata(path.join("pyproject.toml")) {
+        Ok(metadata) => Ok(metadata.is_file()),
+        Err(err) if err.kind() == std::io::ErrorKind::PermissionDenied => Err(err),
+        Err(_) => Ok(false),
+    }
+}
+
+/// Returns `true` if the directory is the root of a version control repository.
+///
+/// Only directories are considered, such that Git submodules and worktrees (which use a `.git`
+/// file) don't bound discovery.
+fn is_repository_root(path: &Path) -> bool {
+    [".git", ".hg", ".jj"]
+        .iter()
+        .any(|marker| path.join(marker).is_dir())
+}
+
 /// Check if a directory only contains files that are ignored.
 ///
 /// Returns `true` if walking the directory while respecting `.gitignore` and `.ignore` rules
@@ -1622,8 +2014,10 @@ fn is_excluded_from_workspace(
     workspace: &ToolUvWorkspace,
 ) -> Result<bool, WorkspaceError> {
     for exclude_glob in workspace.exclude.iter().flatten() {
-        // Normalize the exclude glob to remove leading `./` and other relative path components
-        let normalized_glob = uv_fs::normalize_path(Path::new(exclude_glob.as_str()));
+        // Normalize the exclude glob to use forward slashes, and to remove leading `./` and other
+        // relative path components
+        let portable_exclude_glob = portable::to_forward_slashes(exclude_glob.as_str());
+        let normalized_glob = uv_fs::normalize_path(Path::new(portable_exclude_glob.as_ref()));
         let absolute_glob = PathBuf::from(glob::Pattern::escape(
             workspace_root.simplified().to_string_lossy().as_ref(),
         ))
@@ -1645,8 +2039,10 @@ fn is_included_in_workspace(
     workspace: &ToolUvWorkspace,
 ) -> Result<bool, WorkspaceError> {
     for member_glob in workspace.members.iter().flatten() {
-        // Normalize the member glob to remove leading `./` and other relative path components
-        let normalized_glob = uv_fs::normalize_path(Path::new(member_glob.as_str()));
+        // Normalize the member glob to use forward slashes, and to remove leading `./` and other
+        // relative path components
+        let portable_member_glob = portable::to_forward_slashes(member_glob.as_str());
+        let normalized_glob = uv_fs::normalize_path(Path::new(portable_member_glob.as_ref()));
         let absolute_glob = PathBuf::from(glob::Pattern::escape(
             workspace_root.simplified().to_string_lossy().as_ref(),
         ))
@@ -1690,18 +2086,8 @@ impl VirtualProject {
             path.is_absolute(),
             "virtual project discovery with relative path"
         );
-        let project_root = path
-            .ancestors()
-            .take_while(|path| {
-                // Only walk up the given directory, if any.
-                options
-                    .stop_discovery_at
-                    .as_deref()
-                    .and_then(Path::parent)
-                    .map(|stop_discovery_at| stop_discovery_at != *path)
-                    .unwrap_or(true)
-            })
-            .find(|path| path.join("pyproject.toml").is_file())
+        let project_root = options
+            .find_project_root(path)?
             .ok_or(WorkspaceError::MissingPyprojectToml)?;
 
         debug!(
@@ -1711,7 +2097,9 @@ impl VirtualProject {
 
         // Read the current `pyproject.toml`.
         let pyproject_path = project_root.join("pyproject.toml");
-        let contents = fs_err::tokio::read_to_string(&pyproject_path).await?;
+        let contents = fs_err::tokio::read_to_string(&pyproject_path)
+            .await
+            .map_err(|err| WorkspaceError::Io(pyproject_path.clone(), err))?;
         let pyproject_toml = PyProjectToml::from_string(contents, &pyproject_path)
             .map_err(|err| WorkspaceError::Toml(pyproject_path.clone(), Box::new(err)))?;
 
@@ -1736,7 +2124,7 @@ impl VirtualProject {
             // Otherwise, if it contains a `tool.uv.workspace` table, it's a non-project workspace
             // root.
             let project_path = std::path::absolute(project_root)
-                .map_err(WorkspaceError::Normalize)?
+                .map_err(|err| WorkspaceError::Normalize(project_root.to_path_buf(), err))?
                 .clone();
 
             let workspace = Workspace::collect_members(
@@ -1754,7 +2142,7 @@ impl VirtualProject {
             // Otherwise it's a pyproject.toml that maybe contains dependency-groups
             // that we want to treat like a project/workspace to handle those uniformly
             let project_path = std::path::absolute(project_root)
-                .map_err(WorkspaceError::Normalize)?
+                .map_err(|err| WorkspaceError::Normalize(project_root.to_path_buf(), err))?
                 .clone();
 
             let workspace = Workspace::collect_members(
@@ -1805,7 +2193,7 @@ impl VirtualProject {
                 // If this is a non-project workspace root, then by definition the root isn't a
                 // member, so we can just update the top-level `pyproject.toml`.
                 Some(Self::NonProject(Workspace {
-                    pyproject_toml,
+                    pyproject_toml: Arc::new(pyproject_toml),
                     ..workspace.clone()
                 }))
             }
@@ -1854,6 +2242,7 @@ impl VirtualProject {
 #[cfg(unix)] // Avoid path escaping for the unit tests
 mod tests {
     use std::env;
+    use std::os::unix::fs::PermissionsExt;
     use std::path::Path;
     use std::str::FromStr;
 
@@ -1862,11 +2251,15 @@ mod tests {
     use assert_fs::prelude::*;
     use insta::{assert_json_snapshot, assert_snapshot};
 
-    use uv_normalize::GroupName;
+    use uv_normalize::{GroupName, PackageName};
     use uv_pypi_types::DependencyGroupSpecifier;
 
     use crate::pyproject::PyProjectToml;
-    use crate::workspace::{DiscoveryOptions, ProjectWorkspace};
+    use crate::pyproject_mut::{DependencyTarget, PyProjectTomlMut};
+    use crate::workspace::{
+        DiscoveryOptions, ProjectWorkspace, VirtualProject, Workspace, WorkspaceMember,
+        WorkspaceSnapshot,
+    };
     use crate::{WorkspaceCache, WorkspaceError};
 
     async fn workspace_test(folder: &str) -> (ProjectWorkspace, String) {
@@ -2863,14 +3256,14 @@ bar = ["b"]
         insta::with_settings!({filters => filters}, {
             assert_snapshot!(
                 error,
-            @"Nested workspaces are not supported, but workspace member has a `tool.uv.workspace` table: [ROOT]/packages/seeds");
+            @"Nested workspaces are not supported, but workspace member has a `tool.uv.workspace` table: [ROOT]/packages/seeds (in workspace: [ROOT])");
         });
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn duplicate_names() -> Result<()> {
+    async fn nested_workspace_unmanaged() -> Result<()> {
         let root = tempfile::TempDir::new()?;
         let root = ChildPath::new(root.path());
 
@@ -2888,7 +3281,7 @@ bar = ["b"]
             "#,
         )?;
 
-        // Create an included package (`seeds`).
+        // Create a vendored package (`seeds`) with its own workspace, opting out via `managed`.
         root.child("packages")
             .child("seeds")
             .child("pyproject.toml")
@@ -2900,36 +3293,634 @@ bar = ["b"]
             requires-python = ">=3.12"
             dependencies = ["idna==3.6"]
 
+            [tool.uv]
+            managed = false
+
             [tool.uv.workspace]
             members = ["nested_packages/*"]
             "#,
             )?;
 
-        // Create an included package (`seeds2`).
-        root.child("packages")
-            .child("seeds2")
-            .child("pyproject.toml")
-            .write_str(
-                r#"
+        // The unmanaged member is omitted, rather than rejected as a nested workspace.
+        let (project, _) = temporary_test(root.as_ref()).await.unwrap();
+        assert_eq!(
+            project
+                .workspace()
+                .packages()
+                .keys()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["albatross"]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn repository_boundary() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+
+        // Create a workspace root outside of the repository, e.g., in the user's home directory.
+        root.child("pyproject.toml").write_str(
+            r#"
             [project]
-            name = "seeds"
-            version = "1.0.0"
+            name = "home"
+            version = "0.1.0"
             requires-python = ">=3.12"
-            dependencies = ["idna==3.6"]
 
             [tool.uv.workspace]
-            members = ["nested_packages/*"]
+            members = ["repository"]
             "#,
-            )?;
+        )?;
 
-        let (error, root_escaped) = temporary_test(root.as_ref()).await.unwrap_err();
-        let filters = vec![(root_escaped.as_str(), "[ROOT]")];
-        insta::with_settings!({filters => filters}, {
-            assert_snapshot!(
-                error,
-            @"Two workspace members are both named `seeds`: `[ROOT]/packages/seeds` and `[ROOT]/packages/seeds2`");
-        });
+        // Create a project at the root of a Git repository.
+        let repository = root.child("repository");
+        repository.child(".git").create_dir_all()?;
+        repository.child("pyproject.toml").write_str(
+            r#"
+            [project]
+            name = "albatross"
+            version = "0.1.0"
+            requires-python = ">=3.12"
+            "#,
+        )?;
+
+        // The project is its own workspace root, since discovery stops at the repository root.
+        let (project, _) = temporary_test(repository.as_ref()).await.unwrap();
+        assert_eq!(project.workspace().install_path(), repository.path());
+        assert_eq!(project.project_name().as_ref(), "albatross");
+
+        // A subdirectory without a `pyproject.toml` doesn't fall back to the parent workspace.
+        let src = repository.child("src");
+        src.create_dir_all()?;
+        fs_err::remove_file(repository.child("pyproject.toml"))?;
+        let (error, _) = temporary_test(src.as_ref()).await.unwrap_err();
+        assert!(matches!(error, WorkspaceError::MissingPyprojectToml));
+
+        // A `.git` file, as in a submodule or worktree, doesn't bound discovery.
+        fs_err::remove_dir(repository.child(".git"))?;
+        repository
+            .child(".git")
+            .write_str("gitdir: ../.git/modules/repository")?;
+        repository.child("pyproject.toml").write_str(
+            r#"
+            [project]
+            name = "albatross"
+            version = "0.1.0"
+            requires-python = ">=3.12"
+            "#,
+        )?;
+        let (project, _) = temporary_test(repository.as_ref()).await.unwrap();
+        assert_eq!(project.workspace().install_path(), root.path());
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn ceiling_directories() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+
+        root.child("pyproject.toml").write_str(
+            r#"
+            [project]
+            name = "albatross"
+            version = "0.1.0"
+            requires-python = ">=3.12"
+
+            [tool.uv.workspace]
+            members = ["packages/*"]
+            "#,
+        )?;
+        let member = root.child("packages").child("seeds");
+        member.child("pyproject.toml").write_str(
+            r#"
+            [project]
+            name = "seeds"
+            version = "1.0.0"
+            requires-python = ">=3.12"
+            "#,
+        )?;
+
+        // Without a ceiling, the member is discovered as part of the root workspace.
+        let (project, _) = temporary_test(member.as_ref()).await.unwrap();
+        assert_eq!(project.workspace().install_path(), root.path());
+
+        // With a ceiling at the `packages` directory, the member is its own workspace.
+        let options = DiscoveryOptions {
+            ceiling_directories: vec![root.child("packages").to_path_buf()],
+            ..DiscoveryOptions::default()
+        };
+        let project =
+            ProjectWorkspace::discover(member.as_ref(), &options, &WorkspaceCache::default())
+                .await?;
+        assert_eq!(project.workspace().install_path(), member.path());
+
+        // Ceilings that aren't ancestors of the discovery path are ignored.
+        let options = DiscoveryOptions {
+            ceiling_directories: vec![root.child("elsewhere").to_path_buf()],
+            ..DiscoveryOptions::default()
+        };
+        let project =
+            ProjectWorkspace::discover(member.as_ref(), &options, &WorkspaceCache::default())
+                .await?;
+        assert_eq!(project.workspace().install_path(), root.path());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unreadable_member() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+
+        root.child("pyproject.toml").write_str(
+            r#"
+            [project]
+            name = "albatross"
+            version = "0.1.0"
+            requires-python = ">=3.12"
+
+            [tool.uv.workspace]
+            members = ["packages/*"]
+            "#,
+        )?;
+        root.child("packages")
+            .child("seeds")
+            .child("pyproject.toml")
+            .write_str(
+                r#"
+            [project]
+            name = "seeds"
+            version = "1.0.0"
+            requires-python = ">=3.12"
+            "#,
+            )?;
+
+        // Create a sibling directory that can't be read.
+        let private = root.child("packages").child("private");
+        private.child("pyproject.toml").write_str("")?;
+        fs_err::set_permissions(private.path(), std::fs::Permissions::from_mode(0o000))?;
+
+        // Permissions aren't enforced for privileged users.
+        if fs_err::read_dir(private.path()).is_ok() {
+            fs_err::set_permissions(private.path(), std::fs::Permissions::from_mode(0o755))?;
+            return Ok(());
+        }
+
+        // The unreadable directory is skipped, rather than failing the entire workspace.
+        let result = temporary_test(root.as_ref()).await;
+        fs_err::set_permissions(private.path(), std::fs::Permissions::from_mode(0o755))?;
+        let (project, _) = result.unwrap();
+        assert_eq!(
+            project
+                .workspace()
+                .packages()
+                .keys()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["albatross", "seeds"]
+        );
+
+        // The skipped directory is reported on the workspace.
+        let warnings = project.workspace().warnings();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].path, private.child("pyproject.toml").path());
+        assert_eq!(warnings[0].err.kind(), std::io::ErrorKind::PermissionDenied);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn error_path() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+
+        root.child("pyproject.toml").write_str(
+            r#"
+            [project]
+            name = "albatross"
+            version = "0.1.0"
+            requires-python = ">=3.12"
+
+            [tool.uv.workspace]
+            members = ["packages/*"]
+            "#,
+        )?;
+
+        // A member whose `pyproject.toml` can't be read, since it's a directory.
+        root.child("packages")
+            .child("seeds")
+            .child("pyproject.toml")
+            .create_dir_all()?;
+
+        let (err, _) = temporary_test(root.as_ref()).await.unwrap_err();
+        assert!(matches!(err, WorkspaceError::Io(..)));
+        assert_eq!(
+            err.path(),
+            Some(
+                root.join("packages")
+                    .join("seeds")
+                    .join("pyproject.toml")
+                    .as_path()
+            )
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn duplicate_names() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+
+        // Create the root.
+        root.child("pyproject.toml").write_str(
+            r#"
+            [project]
+            name = "albatross"
+            version = "0.1.0"
+            requires-python = ">=3.12"
+            dependencies = ["tqdm>=4,<5"]
+
+            [tool.uv.workspace]
+            members = ["packages/*"]
+            "#,
+        )?;
+
+        // Create an included package (`seeds`).
+        root.child("packages")
+            .child("seeds")
+            .child("pyproject.toml")
+            .write_str(
+                r#"
+            [project]
+            name = "seeds"
+            version = "1.0.0"
+            requires-python = ">=3.12"
+            dependencies = ["idna==3.6"]
+
+            [tool.uv.workspace]
+            members = ["nested_packages/*"]
+            "#,
+            )?;
+
+        // Create an included package (`seeds2`).
+        root.child("packages")
+            .child("seeds2")
+            .child("pyproject.toml")
+            .write_str(
+                r#"
+            [project]
+            name = "seeds"
+            version = "1.0.0"
+            requires-python = ">=3.12"
+            dependencies = ["idna==3.6"]
+
+            [tool.uv.workspace]
+            members = ["nested_packages/*"]
+            "#,
+            )?;
+
+        let (error, root_escaped) = temporary_test(root.as_ref()).await.unwrap_err();
+        let filters = vec![(root_escaped.as_str(), "[ROOT]")];
+        insta::with_settings!({filters => filters}, {
+            assert_snapshot!(
+                error,
+            @"Two workspace members are both named `seeds`: `[ROOT]/packages/seeds` and `[ROOT]/packages/seeds2`");
+        });
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn duplicate_names_normalized() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+
+        root.child("pyproject.toml").write_str(
+            r#"
+            [project]
+            name = "albatross"
+            version = "0.1.0"
+            requires-python = ">=3.12"
+
+            [tool.uv.workspace]
+            members = ["packages/*"]
+            "#,
+        )?;
+
+        // Create two members whose names only differ before normalization.
+        root.child("packages")
+            .child("a")
+            .child("pyproject.toml")
+            .write_str(
+                r#"
+            [project]
+            name = "My_Lib"
+            version = "1.0.0"
+            requires-python = ">=3.12"
+            "#,
+            )?;
+        root.child("packages")
+            .child("b")
+            .child("pyproject.toml")
+            .write_str(
+                r#"
+            [project]
+            name = "my-lib"
+            version = "1.0.0"
+            requires-python = ">=3.12"
+            "#,
+            )?;
+
+        let (error, root_escaped) = temporary_test(root.as_ref()).await.unwrap_err();
+        let filters = vec![(root_escaped.as_str(), "[ROOT]")];
+        insta::with_settings!({filters => filters}, {
+            assert_snapshot!(
+                error,
+            @"Two workspace members are both named `my-lib`: `[ROOT]/packages/a` (as `My_Lib`) and `[ROOT]/packages/b`");
+        });
+
+        Ok(())
+    }
+
+    /// Errors in several members are reported together, in path order.
+    #[tokio::test]
+    async fn member_errors() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+
+        root.child("pyproject.toml").write_str(
+            r#"
+            [project]
+            name = "albatross"
+            version = "0.1.0"
+            requires-python = ">=3.12"
+
+            [tool.uv.workspace]
+            members = ["packages/*"]
+            "#,
+        )?;
+        // A member without a `pyproject.toml`.
+        root.child("packages").child("a").child("main.py").touch()?;
+        // A valid member.
+        root.child("packages")
+            .child("b")
+            .child("pyproject.toml")
+            .write_str(
+                r#"
+            [project]
+            name = "seeds"
+            version = "1.0.0"
+            requires-python = ">=3.12"
+            "#,
+            )?;
+        // A member without a `project` table.
+        root.child("packages")
+            .child("c")
+            .child("pyproject.toml")
+            .write_str(
+                r"
+            [tool.uv]
+            dev-dependencies = []
+            ",
+            )?;
+        // A member with the same name as another.
+        root.child("packages")
+            .child("d")
+            .child("pyproject.toml")
+            .write_str(
+                r#"
+            [project]
+            name = "seeds"
+            version = "1.0.0"
+            requires-python = ">=3.12"
+            "#,
+            )?;
+
+        let (error, root_escaped) = temporary_test(root.as_ref()).await.unwrap_err();
+        let filters = vec![(root_escaped.as_str(), "[ROOT]")];
+        insta::with_settings!({filters => filters}, {
+            assert_snapshot!(error, @r"
+            Failed to load 3 workspace members:
+            - Workspace member `[ROOT]/packages/a` is missing a `pyproject.toml` (matches: `packages/*`)
+            - No `project` table found in: [ROOT]/packages/c/pyproject.toml
+            - Two workspace members are both named `seeds`: `[ROOT]/packages/b` and `[ROOT]/packages/d`
+            ");
+        });
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn member_without_project() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+
+        root.child("pyproject.toml").write_str(
+            r#"
+            [project]
+            name = "albatross"
+            version = "0.1.0"
+            requires-python = ">=3.12"
+
+            [tool.uv.workspace]
+            members = ["packages/*"]
+            "#,
+        )?;
+
+        // A member without a `[project]` table is rejected, rather than silently dropped.
+        root.child("packages")
+            .child("seeds")
+            .child("pyproject.toml")
+            .write_str(
+                r#"
+            [dependency-groups]
+            dev = ["pytest"]
+            "#,
+            )?;
+
+        let (error, root_escaped) = temporary_test(root.as_ref()).await.unwrap_err();
+        let filters = vec![(root_escaped.as_str(), "[ROOT]")];
+        insta::with_settings!({filters => filters}, {
+            assert_snapshot!(
+                error,
+            @"No `project` table found in: [ROOT]/packages/seeds/pyproject.toml");
+        });
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn windows_style_member_glob() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+
+        root.child("pyproject.toml").write_str(
+            r#"
+            [project]
+            name = "albatross"
+            version = "0.1.0"
+            requires-python = ">=3.12"
+
+            [tool.uv.workspace]
+            members = ['packages\*']
+            exclude = ['packages\seeds']
+            "#,
+        )?;
+        for name in ["bird-feeder", "seeds"] {
+            root.child("packages")
+                .child(name)
+                .child("pyproject.toml")
+                .write_str(&format!(
+                    "[project]\nname = \"{name}\"\nversion = \"1.0.0\"\nrequires-python = \">=3.12\"\n"
+                ))?;
+        }
+
+        // Backslashes are treated as separators on all platforms.
+        let (project, _) = temporary_test(root.as_ref()).await.unwrap();
+        assert_eq!(
+            project
+                .workspace()
+                .packages()
+                .keys()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["albatross", "bird-feeder"]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn trace_member_glob() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+
+        root.child("pyproject.toml").write_str(
+            r#"
+            [project]
+            name = "albatross"
+            version = "0.1.0"
+            requires-python = ">=3.12"
+
+            [tool.uv.workspace]
+            members = ["packages/*"]
+            "#,
+        )?;
+        root.child("packages")
+            .child("seeds")
+            .child("pyproject.toml")
+            .write_str(
+                r#"
+            [project]
+            name = "seeds"
+            version = "1.0.0"
+            requires-python = ">=3.12"
+            "#,
+            )?;
+        root.child("packages").child(".cache").create_dir_all()?;
+
+        temporary_test(root.as_ref())
+            .await
+            .map_err(|(err, _)| err)?;
+        assert!(logs_contain("Ignoring workspace member path="));
+        assert!(logs_contain(r#"reason="hidden""#));
+        assert!(logs_contain(
+            r#"Expanded workspace member glob glob="packages/*" matched=2 added=1 skipped=1"#
+        ));
+
+        Ok(())
+    }
+
+    /// Updating a member after taking a snapshot leaves the snapshot as-is.
+    #[tokio::test]
+    async fn snapshot_restore() -> Result<()> {
+        let root = tempfile::TempDir::new()?;
+        let root = ChildPath::new(root.path());
+        root.child("pyproject.toml").write_str(
+            r#"
+            [project]
+            name = "albatross"
+            version = "0.1.0"
+            requires-python = ">=3.12"
+            dependencies = ["seeds"]
+
+            [tool.uv.sources]
+            seeds = { workspace = true }
+
+            [tool.uv.workspace]
+            members = ["packages/*"]
+            "#,
+        )?;
+        root.child("packages")
+            .child("seeds")
+            .child("pyproject.toml")
+            .write_str(
+                r#"
+            [project]
+            name = "seeds"
+            version = "1.0.0"
+            requires-python = ">=3.12"
+            "#,
+            )?;
+        let mut workspace = Workspace::discover(
+            root.path(),
+            &DiscoveryOptions::default(),
+            &WorkspaceCache::default(),
+        )
+        .await?;
+        let albatross = PackageName::from_str("albatross")?;
+        let seeds = PackageName::from_str("seeds")?;
+        assert!(workspace.is_required_member(&seeds));
+
+        // Preview dropping the dependency on `seeds`.
+        let snapshot = workspace.snapshot();
+        let mut pyproject = PyProjectTomlMut::from_toml(
+            &workspace.pyproject_toml().raw,
+            DependencyTarget::PyProjectToml,
+        )?;
+        pyproject.remove_dependency(&seeds)?;
+        workspace = workspace
+            .update_member(&albatross, pyproject.pyproject_toml()?)?
+            .unwrap();
+        assert!(!workspace.is_required_member(&seeds));
+        assert!(
+            workspace.packages()[&albatross]
+                .pyproject_toml()
+                .project
+                .as_ref()
+                .and_then(|project| project.dependencies.as_ref())
+                .is_some_and(Vec::is_empty)
+        );
+
+        workspace.restore(snapshot);
+        assert!(workspace.is_required_member(&seeds));
+        assert!(
+            workspace.packages()[&albatross]
+                .pyproject_toml()
+                .raw
+                .contains(r#"dependencies = ["seeds"]"#)
+        );
+
+        Ok(())
+    }
+
+    /// Workspaces can be shared across threads, and edits can be moved across them.
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        fn assert_send<T: Send>() {}
+
+        assert_send_sync::<Workspace>();
+        assert_send_sync::<WorkspaceSnapshot>();
+        assert_send_sync::<WorkspaceMember>();
+        assert_send_sync::<ProjectWorkspace>();
+        assert_send_sync::<VirtualProject>();
+        assert_send_sync::<WorkspaceCache>();
+        assert_send::<PyProjectTomlMut>();
+    }
 }
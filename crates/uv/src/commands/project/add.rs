@@ -578,8 +578,7 @@ pub(crate) async fn add(
                     .strip_prefix(project.workspace().install_path())
                     .unwrap_or(&absolute_path);
 
-                toml.add_workspace(relative_path)?;
-                modified |= true;
+                modified |= toml.add_workspace(relative_path)?;
 
                 writeln!(
                     printer.stderr(),
@@ -593,9 +592,10 @@ pub(crate) async fn add(
         // the discovered members, etc.
         target = if modified {
             let workspace_content = toml.to_string();
-            fs_err::write(
+            uv_fs::overwrite_atomic_sync(
                 project.workspace().install_path().join("pyproject.toml"),
                 &workspace_content,
+                false,
             )?;
 
             AddTarget::Project(
@@ -704,7 +704,7 @@ pub(crate) async fn add(
     };
 
     // Update the `pypackage.toml` in-memory.
-    let target = target.update(&content)?;
+    let target = target.update(&toml)?;
 
     // Set the Ctrl-C handler to revert changes on exit.
     let _ = ctrlc::set_handler({
@@ -858,6 +858,7 @@ fn edits(
                 tag,
                 branch,
                 lfs,
+                submodules,
                 marker,
                 extra,
                 group,
@@ -877,6 +878,7 @@ fn edits(
                     tag,
                     branch,
                     lfs,
+                    submodules,
                     marker,
                     extra,
                     group,
@@ -947,7 +949,7 @@ fn edits(
                                 *existing += 1;
                             }
                         }
-                        ArrayEdit::Update(existing) => {
+                        ArrayEdit::Update(existing) | ArrayEdit::Unchanged(existing) => {
                             if *existing >= *index {
                                 *existing += 1;
                             }
@@ -1110,7 +1112,7 @@ async fn lock_and_sync(
             target.write(&content)?;
 
             // Update the `pypackage.toml` in-memory.
-            target = target.update(&content)?;
+            target = target.update(toml)?;
 
             // Invalidate the project metadata.
             if let AddTarget::Project(VirtualProject::Project(ref project), _) = target {
@@ -1328,7 +1330,7 @@ impl AddTarget {
                     Ok(false)
                 } else {
                     let pyproject_path = project.root().join("pyproject.toml");
-                    fs_err::write(pyproject_path, content)?;
+                    uv_fs::overwrite_atomic_sync(pyproject_path, content, false)?;
                     Ok(true)
                 }
             }
@@ -1336,17 +1338,18 @@ impl AddTarget {
     }
 
     /// Update the target in-memory to incorporate the new content.
-    fn update(self, content: &str) -> Result<Self, ProjectError> {
+    fn update(self, toml: &PyProjectTomlMut) -> Result<Self, ProjectError> {
         match self {
             Self::Script(mut script, interpreter) => {
-                script.metadata = Pep723Metadata::from_str(content)
+                script.metadata = Pep723Metadata::from_str(&toml.to_string())
                     .map_err(ProjectError::Pep723ScriptTomlParse)?;
                 Ok(Self::Script(script, interpreter))
             }
             Self::Project(project, venv) => {
                 let project = project
                     .update_member(
-                        toml::from_str(content).map_err(ProjectError::PyprojectTomlParse)?,
+                        toml.pyproject_toml()
+                            .map_err(ProjectError::PyprojectTomlParse)?,
                     )?
                     .ok_or(ProjectError::PyprojectTomlUpdate)?;
                 Ok(Self::Project(project, venv))
@@ -1403,17 +1406,19 @@ impl AddTargetSnapshot {
                 let workspace = project.workspace();
                 if workspace.install_path() != project.root() {
                     debug!("Reverting changes to workspace `pyproject.toml`");
-                    fs_err::write(
+                    uv_fs::overwrite_atomic_sync(
                         workspace.install_path().join("pyproject.toml"),
                         workspace.pyproject_toml().as_ref(),
+                        false,
                     )?;
                 }
 
                 // Write the `pyproject.toml` back to disk.
                 debug!("Reverting changes to `pyproject.toml`");
-                fs_err::write(
+                uv_fs::overwrite_atomic_sync(
                     project.root().join("pyproject.toml"),
                     project.pyproject_toml().as_ref(),
+                    false,
                 )?;
 
                 // Write the lockfile back to disk.
@@ -446,10 +446,7 @@ async fn init_project(
             pyproject.add_workspace(path.strip_prefix(workspace.install_path())?)?;
 
             // Save the modified `pyproject.toml`.
-            fs_err::write(
-                workspace.install_path().join("pyproject.toml"),
-                pyproject.to_string(),
-            )?;
+            pyproject.save_to(workspace.install_path().join("pyproject.toml"))?;
 
             writeln!(
                 printer.stderr(),
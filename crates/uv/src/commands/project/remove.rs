@@ -197,7 +197,7 @@ pub(crate) async fn remove(
     }
 
     // Update the `pypackage.toml` in-memory.
-    let target = target.update(&content)?;
+    let target = target.update(&toml)?;
 
     // Determine enabled groups and extras
     let default_groups = match &target {
@@ -418,7 +418,7 @@ impl RemoveTarget {
                     Ok(false)
                 } else {
                     let pyproject_path = project.root().join("pyproject.toml");
-                    fs_err::write(pyproject_path, content)?;
+                    uv_fs::overwrite_atomic_sync(pyproject_path, content, false)?;
                     Ok(true)
                 }
             }
@@ -426,17 +426,18 @@ impl RemoveTarget {
     }
 
     /// Update the target in-memory to incorporate the new content.
-    fn update(self, content: &str) -> Result<Self, ProjectError> {
+    fn update(self, toml: &PyProjectTomlMut) -> Result<Self, ProjectError> {
         match self {
             Self::Script(mut script) => {
-                script.metadata = Pep723Metadata::from_str(content)
+                script.metadata = Pep723Metadata::from_str(&toml.to_string())
                     .map_err(ProjectError::Pep723ScriptTomlParse)?;
                 Ok(Self::Script(script))
             }
             Self::Project(project) => {
                 let project = project
                     .update_member(
-                        toml::from_str(content).map_err(ProjectError::PyprojectTomlParse)?,
+                        toml.pyproject_toml()
+                            .map_err(ProjectError::PyprojectTomlParse)?,
                     )?
                     .ok_or(ProjectError::PyprojectTomlUpdate)?;
                 Ok(Self::Project(project))
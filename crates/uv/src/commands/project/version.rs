@@ -432,11 +432,14 @@ fn update_project(
     // Save to disk
     toml.set_version(new_version)?;
     let content = toml.to_string();
-    fs_err::write(pyproject_path, &content)?;
+    uv_fs::overwrite_atomic_sync(pyproject_path, &content, false)?;
 
     // Update the `pyproject.toml` in-memory.
     let project = project
-        .update_member(toml::from_str(&content).map_err(ProjectError::PyprojectTomlParse)?)?
+        .update_member(
+            toml.pyproject_toml()
+                .map_err(ProjectError::PyprojectTomlParse)?,
+        )?
         .ok_or(ProjectError::PyprojectTomlUpdate)?;
 
     Ok(project)
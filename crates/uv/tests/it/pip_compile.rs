@@ -13491,6 +13491,7 @@ fn git_source_missing_tag() -> Result<()> {
     ----- stderr -----
       × Failed to download and build `uv-public-pypackage @ git+https://github.com/astral-test/uv-public-pypackage@missing`
       ├─▶ Git operation failed
+      ├─▶ Git tag `missing` was not found in `https://github.com/astral-test/uv-public-pypackage`
       ├─▶ failed to clone into: [CACHE_DIR]/git-v0/db/8dab139913c4b566
       ├─▶ failed to fetch tag `missing`
       ╰─▶ process didn't exit successfully: `git fetch --force --update-head-ok 'https://github.com/astral-test/uv-public-pypackage' '+refs/tags/missing:refs/remotes/origin/tags/missing'` (exit status: 128)
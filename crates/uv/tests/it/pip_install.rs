@@ -2220,6 +2220,7 @@ fn install_git_public_https_missing_branch_or_tag() {
     ----- stderr -----
       × Failed to download and build `uv-public-pypackage @ git+https://github.com/astral-test/uv-public-pypackage@2.0.0`
       ├─▶ Git operation failed
+      ├─▶ Git branch or tag `2.0.0` was not found in `https://github.com/astral-test/uv-public-pypackage`
       ├─▶ failed to clone into: [CACHE_DIR]/git-v0/db/8dab139913c4b566
       ├─▶ failed to fetch branch or tag `2.0.0`
       ╰─▶ process didn't exit successfully: `git fetch [...]` (exit code: 128)